@@ -1,12 +1,17 @@
-use std::ops::Deref;
+use std::{ops::Deref, sync::Arc};
 
 use napi::{bindgen_prelude::External, JsFunction};
-use next_api::route::{Endpoint, WrittenEndpoint};
-use turbo_tasks::Vc;
-use turbopack_binding::turbopack::core::error::PrettyPrintError;
+use next_api::{
+    output_assets::{endpoint_output_assets, EndpointOutputAsset, OutputAssetKind},
+    route::{Endpoint, WrittenEndpoint},
+};
+use turbo_tasks::{TurboTasks, Vc};
+use turbopack_binding::{
+    turbo::tasks_memory::MemoryBackend, turbopack::core::error::PrettyPrintError,
+};
 
 use super::utils::{
-    get_diagnostics, get_issues, subscribe, NapiDiagnostic, NapiIssue, RootTask, TurbopackResult,
+    get_diagnostics, get_issues, subscribe, BuildLimiter, IssueFilter, RootTask, TurbopackResult,
     VcArc,
 };
 
@@ -52,19 +57,63 @@ impl From<&WrittenEndpoint> for NapiWrittenEndpoint {
     }
 }
 
+#[napi(object)]
+pub struct NapiEndpointOutputAsset {
+    pub path: String,
+    pub client: bool,
+    pub kind: String,
+}
+
+impl From<&EndpointOutputAsset> for NapiEndpointOutputAsset {
+    fn from(asset: &EndpointOutputAsset) -> Self {
+        Self {
+            path: asset.path.clone(),
+            client: asset.client,
+            kind: match asset.kind {
+                OutputAssetKind::Script => "script",
+                OutputAssetKind::Stylesheet => "stylesheet",
+                OutputAssetKind::SourceMap => "source-map",
+                OutputAssetKind::Other => "asset",
+            }
+            .to_string(),
+        }
+    }
+}
+
 // NOTE(alexkirsz) We go through an extra layer of indirection here because of
 // two factors:
 // 1. rustc currently has a bug where using a dyn trait as a type argument to
 //    some async functions (in this case `endpoint_write_to_disk`) can cause
 //    higher-ranked lifetime errors. See https://github.com/rust-lang/rust/issues/102211
 // 2. the type_complexity clippy lint.
-pub struct ExternalEndpoint(pub VcArc<Vc<Box<dyn Endpoint>>>);
+pub struct ExternalEndpoint {
+    pub endpoint: VcArc<Vc<Box<dyn Endpoint>>>,
+    /// The issue filter of the project the endpoint belongs to.
+    pub issue_filter: IssueFilter,
+    /// Bounds the builds running concurrently in the project.
+    pub build_limiter: BuildLimiter,
+}
+
+impl ExternalEndpoint {
+    pub fn new(
+        turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+        endpoint: Vc<Box<dyn Endpoint>>,
+        issue_filter: IssueFilter,
+        build_limiter: BuildLimiter,
+    ) -> Self {
+        ExternalEndpoint {
+            endpoint: VcArc::new(turbo_tasks, endpoint),
+            issue_filter,
+            build_limiter,
+        }
+    }
+}
 
 impl Deref for ExternalEndpoint {
     type Target = VcArc<Vc<Box<dyn Endpoint>>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.endpoint
     }
 }
 
@@ -73,22 +122,48 @@ pub async fn endpoint_write_to_disk(
     #[napi(ts_arg_type = "{ __napiType: \"Endpoint\" }")] endpoint: External<ExternalEndpoint>,
 ) -> napi::Result<TurbopackResult<NapiWrittenEndpoint>> {
     let turbo_tasks = endpoint.turbo_tasks().clone();
+    let issue_filter = endpoint.issue_filter.clone();
+    let build_limiter = endpoint.build_limiter.clone();
     let endpoint = ***endpoint;
-    let (written, issues, diags) = turbo_tasks
-        .run_once(async move {
+    let (written, issues, diags) = build_limiter
+        .run(turbo_tasks.run_once(async move {
             let write_to_disk = endpoint.write_to_disk();
             let issues = get_issues(write_to_disk).await?;
             let diags = get_diagnostics(write_to_disk).await?;
             let written = write_to_disk.strongly_consistent().await?;
             Ok((written, issues, diags))
-        })
+        }))
         .await
         .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
     // TODO diagnostics
     Ok(TurbopackResult {
         result: NapiWrittenEndpoint::from(&*written),
-        issues: issues.iter().map(|i| NapiIssue::from(&**i)).collect(),
-        diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
+    })
+}
+
+#[napi]
+pub async fn endpoint_output_assets(
+    #[napi(ts_arg_type = "{ __napiType: \"Endpoint\" }")] endpoint: External<ExternalEndpoint>,
+) -> napi::Result<TurbopackResult<Vec<NapiEndpointOutputAsset>>> {
+    let turbo_tasks = endpoint.turbo_tasks().clone();
+    let issue_filter = endpoint.issue_filter.clone();
+    let endpoint = ***endpoint;
+    let (assets, issues, diags) = turbo_tasks
+        .run_once(async move {
+            let assets = endpoint_output_assets(endpoint);
+            let issues = get_issues(assets).await?;
+            let diags = get_diagnostics(assets).await?;
+            let assets = assets.strongly_consistent().await?;
+            Ok((assets, issues, diags))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(TurbopackResult {
+        result: assets.iter().map(NapiEndpointOutputAsset::from).collect(),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
     })
 }
 
@@ -98,6 +173,7 @@ pub fn endpoint_server_changed_subscribe(
     func: JsFunction,
 ) -> napi::Result<External<RootTask>> {
     let turbo_tasks = endpoint.turbo_tasks().clone();
+    let issue_filter = endpoint.issue_filter.clone();
     let endpoint = ***endpoint;
     subscribe(
         turbo_tasks,
@@ -113,8 +189,8 @@ pub fn endpoint_server_changed_subscribe(
             let (issues, diags) = ctx.value;
             Ok(vec![TurbopackResult {
                 result: (),
-                issues: issues.iter().map(|i| NapiIssue::from(&**i)).collect(),
-                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+                issues: issue_filter.issues(&issues),
+                diagnostics: issue_filter.diagnostics(&diags),
             }])
         },
     )
@@ -126,6 +202,7 @@ pub fn endpoint_client_changed_subscribe(
     func: JsFunction,
 ) -> napi::Result<External<RootTask>> {
     let turbo_tasks = endpoint.turbo_tasks().clone();
+    let issue_filter = endpoint.issue_filter.clone();
     let endpoint = ***endpoint;
     subscribe(
         turbo_tasks,
@@ -141,8 +218,8 @@ pub fn endpoint_client_changed_subscribe(
             let (issues, diags) = ctx.value;
             Ok(vec![TurbopackResult {
                 result: (),
-                issues: issues.iter().map(|i| NapiIssue::from(&**i)).collect(),
-                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+                issues: issue_filter.issues(&issues),
+                diagnostics: issue_filter.diagnostics(&diags),
             }])
         },
     )
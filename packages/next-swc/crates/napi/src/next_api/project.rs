@@ -1,22 +1,50 @@
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    net::{SocketAddr, TcpListener},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
+use dunce::canonicalize;
 use napi::{
     bindgen_prelude::External,
     threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
     JsFunction, Status,
 };
 use next_api::{
-    project::{Middleware, ProjectContainer, ProjectOptions},
+    client_graph::{client_graph, ClientGraph},
+    module_graph::{route_module_graph, ModuleGraph},
+    project::{invalidate_file_systems, Middleware, ProjectContainer, ProjectOptions},
     route::{Endpoint, Route},
+    transform::{transform_module, TransformedModule},
+    unsupported_modules::{unsupported_modules, UnsupportedModule},
+    unused_dependencies::unused_dependencies,
 };
-use next_core::tracing_presets::{
-    TRACING_NEXT_TARGETS, TRACING_NEXT_TURBOPACK_TARGETS, TRACING_NEXT_TURBO_TASKS_TARGETS,
+use next_core::{
+    middleware::MiddlewarePathMatcher,
+    tracing_presets::{
+        TRACING_NEXT_TARGETS, TRACING_NEXT_TURBOPACK_TARGETS, TRACING_NEXT_TURBO_TASKS_TARGETS,
+    },
+    util::{internal_dir, NextRuntime},
 };
+use next_dev::{
+    task_limit::timeout_on_task_limit,
+    update_reasons::{UpdateReasonCount, UpdateReasons},
+    update_tasks::{CreatedTaskCounter, UpdateTaskBreakdown},
+};
+use serde::Serialize;
 use tracing_subscriber::{
     prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, EnvFilter, Registry,
 };
-use turbo_tasks::{TransientInstance, TurboTasks, UpdateInfo, Vc};
+use turbo_tasks::{
+    StatsType, TransientInstance, TryFlatJoinIterExt, TurboTasks, TurboTasksBackendApi, UpdateInfo,
+    Vc,
+};
 use turbopack_binding::{
     turbo::tasks_memory::MemoryBackend,
     turbopack::{
@@ -37,8 +65,8 @@ use turbopack_binding::{
 use super::{
     endpoint::ExternalEndpoint,
     utils::{
-        get_diagnostics, get_issues, subscribe, NapiDiagnostic, NapiIssue, RootTask,
-        TurbopackResult, VcArc,
+        get_diagnostics, get_issues, subscribe, BuildLimiter, IssueFilter, IssueFilterConfig,
+        NapiIssue, RootTask, TurbopackResult,
     },
 };
 use crate::register;
@@ -70,21 +98,78 @@ pub struct NapiProjectOptions {
     /// A map of environment variables to use when compiling code.
     pub env: Vec<NapiEnvVar>,
 
-    /// The address of the dev server.
+    /// Dotenv files to load, relative to `project_path`. Variables from `env`
+    /// take precedence, then the files in the given order.
+    pub env_files: Option<Vec<String>>,
+
+    /// The address of the host's dev server, e.g. `127.0.0.1:3000`. Port `0`
+    /// is replaced by a free port, see [project_server_addr].
     pub server_addr: String,
+
+    /// Whether the host's dev server serves HTTPS, e.g. with
+    /// `next dev --experimental-https`. Defaults to `false`.
+    pub https: Option<bool>,
+
+    /// Whether to minify the server chunks. Defaults to `false`. Client chunks
+    /// are never minified.
+    pub minify: Option<bool>,
+
+    /// Drops issues less severe than this, e.g. `"warning"`, before they are
+    /// passed to JS. Defaults to keeping all issues.
+    pub min_issue_severity: Option<String>,
+
+    /// Only passes diagnostics of these categories to JS. Defaults to keeping
+    /// all diagnostics.
+    pub diagnostic_categories: Option<Vec<String>>,
+
+    /// Passes issues of these categories to JS as errors, even if they are
+    /// only warnings.
+    pub error_on: Option<Vec<String>>,
+
+    /// How much of each issue is passed to JS: `"full"` (the default), or
+    /// `"minimal"` to only pass the severity, file path and title, which is
+    /// cheaper for noisy projects.
+    pub issue_detail: Option<String>,
+
+    /// How many endpoints are built at the same time, when writing them to
+    /// disk or warming them up. Defaults to the number of CPUs. Only read when
+    /// the project is created.
+    pub max_concurrent_builds: Option<u32>,
+
+    /// Renders every page and route with this runtime, `"nodejs"` or
+    /// `"edge"`, instead of the one selected by its `runtime` export.
+    pub runtime: Option<String>,
+
+    /// Whether the client chunks support hot module replacement. Defaults to
+    /// enabling it in development.
+    pub hmr: Option<bool>,
 }
 
+/// There's no `threads` option: Turbo Engine runs on the tokio runtime napi-rs
+/// creates on the first async call, before any project exists, and which is
+/// shared by all projects. It reads `TOKIO_WORKER_THREADS` when it's created.
 #[napi(object)]
 pub struct NapiTurboEngineOptions {
     /// An upper bound of memory that turbopack will attempt to stay under.
     pub memory_limit: Option<f64>,
+
+    /// Fails creating the project and building endpoints when more than this
+    /// many tasks are in progress at the same time, which usually means that
+    /// a misconfigured glob or import pattern matches far too many files.
+    /// Only the call fails, the tasks already started keep running. The count
+    /// covers all work of the project, not just the failing call.
+    pub max_tasks: Option<u32>,
 }
 
-impl From<NapiProjectOptions> for ProjectOptions {
-    fn from(val: NapiProjectOptions) -> Self {
-        ProjectOptions {
-            root_path: val.root_path,
-            project_path: val.project_path,
+impl TryFrom<NapiProjectOptions> for ProjectOptions {
+    type Error = anyhow::Error;
+
+    fn try_from(val: NapiProjectOptions) -> Result<Self> {
+        let (root_path, project_path) =
+            canonicalize_project_paths(&val.root_path, &val.project_path)?;
+        Ok(ProjectOptions {
+            root_path,
+            project_path,
             watch: val.watch,
             next_config: val.next_config,
             js_config: val.js_config,
@@ -93,16 +178,74 @@ impl From<NapiProjectOptions> for ProjectOptions {
                 .into_iter()
                 .map(|NapiEnvVar { name, value }| (name, value))
                 .collect(),
+            env_files: val.env_files.unwrap_or_default(),
             server_addr: val.server_addr,
+            minify: val.minify,
+            runtime: val.runtime.as_deref().map(parse_runtime).transpose()?,
+            hmr: val.hmr,
+        })
+    }
+}
+
+impl From<&NapiProjectOptions> for IssueFilterConfig {
+    fn from(val: &NapiProjectOptions) -> Self {
+        IssueFilterConfig {
+            min_issue_severity: val.min_issue_severity.clone(),
+            diagnostic_categories: val.diagnostic_categories.clone(),
+            error_on: val.error_on.clone(),
+            issue_detail: val.issue_detail.clone(),
         }
     }
 }
 
+/// Resolves symlinks in the root and project paths, like the dev server does,
+/// so that a symlinked project doesn't produce a second module graph.
+fn canonicalize_project_paths(root_path: &str, project_path: &str) -> Result<(String, String)> {
+    let root =
+        canonicalize(root_path).with_context(|| format!("root path {root_path} can't be found"))?;
+    let project = canonicalize(project_path)
+        .with_context(|| format!("project path {project_path} can't be found"))?;
+    if !project.starts_with(&root) {
+        bail!(
+            "project path {} is not inside the root path {}",
+            project.display(),
+            root.display()
+        );
+    }
+    let to_string = |path: PathBuf| {
+        path.into_os_string()
+            .into_string()
+            .map_err(|path| anyhow!("{} contains invalid characters", path.to_string_lossy()))
+    };
+    Ok((to_string(root)?, to_string(project)?))
+}
+
+/// The `distDir` of the serialized next config, if it's set.
+fn config_dist_dir(next_config: &str) -> Option<String> {
+    let config: serde_json::Value = serde_json::from_str(next_config).ok()?;
+    Some(config.get("distDir")?.as_str()?.to_string())
+}
+
+fn parse_runtime(runtime: &str) -> Result<NextRuntime> {
+    Ok(match runtime {
+        "nodejs" => NextRuntime::NodeJs,
+        "edge" => NextRuntime::Edge,
+        _ => bail!("unknown runtime {runtime:?}, expected \"nodejs\" or \"edge\""),
+    })
+}
+
 pub struct ProjectInstance {
     turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
     container: Vc<ProjectContainer>,
+    issue_filter: IssueFilter,
+    build_limiter: BuildLimiter,
+    /// The address from the options, with port `0` replaced by a free port.
+    server_addr: Mutex<SocketAddr>,
+    /// Whether the host's server serves HTTPS.
+    https: AtomicBool,
     #[allow(dead_code)]
     guard: Option<ExitGuard<TraceWriterGuard>>,
+    start: Instant,
 }
 
 #[napi(ts_return_type = "{ __napiType: \"Project\" }")]
@@ -136,7 +279,10 @@ pub async fn project_new(
 
         let subscriber = subscriber.with(EnvFilter::builder().parse(trace).unwrap());
 
-        let internal_dir = PathBuf::from(&options.project_path).join(".next");
+        let internal_dir = internal_dir(
+            Path::new(&options.project_path),
+            config_dist_dir(&options.next_config).as_deref(),
+        );
         std::fs::create_dir_all(&internal_dir)
             .context("Unable to create .next directory")
             .unwrap();
@@ -160,20 +306,40 @@ pub async fn project_new(
             .map(|m| m as usize)
             .unwrap_or(usize::MAX),
     ));
-    let options = options.into();
-    let container = turbo_tasks
-        .run_once(async move {
-            let project = ProjectContainer::new(options);
-            let project = project.resolve().await?;
-            Ok(project)
-        })
-        .await
+    let issue_filter = IssueFilter::default();
+    issue_filter
+        .set(IssueFilterConfig::from(&options))
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    let max_tasks = turbo_engine_options.max_tasks.map(|max| max as usize);
+    let build_limiter = BuildLimiter::new(options.max_concurrent_builds)
+        .timeout_on_task_limit(turbo_tasks.clone(), max_tasks);
+    let server_addr = resolve_server_addr(&options.server_addr, None)
         .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    let https = options.https.unwrap_or(false);
+    let mut options: ProjectOptions = options
+        .try_into()
+        .map_err(|e: anyhow::Error| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    options.server_addr = server_addr.to_string();
+    let create = turbo_tasks.run_once(async move {
+        let project = ProjectContainer::new(options);
+        let project = project.resolve().await?;
+        Ok(project)
+    });
+    let container = match max_tasks {
+        Some(max_tasks) => timeout_on_task_limit(&turbo_tasks, max_tasks, create).await,
+        None => create.await,
+    }
+    .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
     Ok(External::new_with_size_hint(
         ProjectInstance {
             turbo_tasks,
             container,
+            issue_filter,
+            build_limiter,
+            server_addr: Mutex::new(server_addr),
+            https: AtomicBool::new(https),
             guard,
+            start: Instant::now(),
         },
         100,
     ))
@@ -185,7 +351,23 @@ pub async fn project_update(
     options: NapiProjectOptions,
 ) -> napi::Result<()> {
     let turbo_tasks = project.turbo_tasks.clone();
-    let options = options.into();
+    project
+        .issue_filter
+        .set(IssueFilterConfig::from(&options))
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    let server_addr = {
+        let mut server_addr = project.server_addr.lock().unwrap();
+        *server_addr = resolve_server_addr(&options.server_addr, Some(*server_addr))
+            .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+        *server_addr
+    };
+    project
+        .https
+        .store(options.https.unwrap_or(false), Ordering::SeqCst);
+    let mut options: ProjectOptions = options
+        .try_into()
+        .map_err(|e: anyhow::Error| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    options.server_addr = server_addr.to_string();
     let container = project.container;
     turbo_tasks
         .run_once(async move {
@@ -197,6 +379,477 @@ pub async fn project_update(
     Ok(())
 }
 
+/// Returns the address the compiled app is told it is served from, which is
+/// the `serverAddr` option with port `0` replaced by a free port. A host that
+/// passed port `0` binds its server to this address.
+#[napi]
+pub fn project_server_addr(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> String {
+    project.server_addr.lock().unwrap().to_string()
+}
+
+/// Returns the URL to open the app at, e.g. `http://localhost:3000`, built
+/// from the address the host's server listens on and the `https` option.
+#[napi]
+pub fn project_server_url(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> String {
+    server_url(
+        *project.server_addr.lock().unwrap(),
+        project.https.load(Ordering::SeqCst),
+    )
+}
+
+/// An unspecified IP, which listens on all interfaces, is displayed as
+/// `localhost`.
+fn server_url(addr: SocketAddr, https: bool) -> String {
+    let scheme = if https { "https" } else { "http" };
+    if addr.ip().is_unspecified() {
+        format!("{scheme}://localhost:{}", addr.port())
+    } else {
+        format!("{scheme}://{addr}")
+    }
+}
+
+/// Makes every file of the project be read from disk again, as if all files
+/// had changed. This is a debugging aid for problems which seem to go away
+/// after a restart.
+#[napi]
+pub async fn project_invalidate_all(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> napi::Result<()> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let container = project.container;
+    turbo_tasks
+        .run_once(async move { invalidate_file_systems(container).await })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+}
+
+/// Switches between recording essential and full task stats. Full stats are
+/// costly, so they are meant to be enabled temporarily, e.g. to capture a slow
+/// rebuild. Switching only affects the stats recorded from then on.
+#[napi]
+pub fn project_set_stats_type(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    full: bool,
+) {
+    project.turbo_tasks.set_stats_type(match full {
+        true => StatsType::Full,
+        false => StatsType::Essential,
+    });
+}
+
+/// Parses `server_addr` and replaces port `0` with a free port, or the port of
+/// `previous` if it has the same IP, so updating the options doesn't change
+/// the port. The free port isn't reserved, so the host has to bind it before
+/// another process does.
+fn resolve_server_addr(server_addr: &str, previous: Option<SocketAddr>) -> Result<SocketAddr> {
+    let addr: SocketAddr = server_addr
+        .parse()
+        .with_context(|| format!("invalid server address {server_addr:?}, expected IP:PORT"))?;
+    if addr.port() != 0 {
+        return Ok(addr);
+    }
+    if let Some(previous) = previous.filter(|previous| previous.ip() == addr.ip()) {
+        return Ok(previous);
+    }
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("unable to find a free port on {addr}"))?;
+    Ok(listener.local_addr()?)
+}
+
+#[napi(object)]
+pub struct NapiStartupSummary {
+    /// The number of routes in the pages directory, including API routes.
+    pub pages_routes: u32,
+    /// The number of routes in the app directory.
+    pub app_routes: u32,
+    /// Whether the project has a middleware file.
+    pub middleware: bool,
+    /// The time since the project was created, in milliseconds.
+    pub duration: u32,
+}
+
+#[napi]
+pub async fn project_startup_summary(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> napi::Result<NapiStartupSummary> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let container = project.container;
+    let start = project.start;
+    let (pages_routes, app_routes, middleware) = turbo_tasks
+        .run_once(async move {
+            let entrypoints = container.entrypoints().strongly_consistent().await?;
+            let mut pages_routes = 0;
+            let mut app_routes = 0;
+            for route in entrypoints.routes.values() {
+                match route {
+                    Route::Page { .. } | Route::PageApi { .. } => pages_routes += 1,
+                    Route::AppPage { .. } | Route::AppRoute { .. } => app_routes += 1,
+                    Route::Conflict => {}
+                }
+            }
+            Ok((pages_routes, app_routes, entrypoints.middleware.is_some()))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(NapiStartupSummary {
+        pages_routes,
+        app_routes,
+        middleware,
+        duration: start.elapsed().as_millis() as u32,
+    })
+}
+
+#[napi(object)]
+pub struct NapiClientGraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+#[napi(object)]
+pub struct NapiClientGraph {
+    /// The paths of all client output assets of the route.
+    pub nodes: Vec<String>,
+    /// The references between the output assets.
+    pub edges: Vec<NapiClientGraphEdge>,
+    /// The paths of the chunks the route loads directly.
+    pub chunks: Vec<String>,
+}
+
+impl From<&ClientGraph> for NapiClientGraph {
+    fn from(graph: &ClientGraph) -> Self {
+        NapiClientGraph {
+            nodes: graph.nodes.clone(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|edge| NapiClientGraphEdge {
+                    from: edge.from.clone(),
+                    to: edge.to.clone(),
+                })
+                .collect(),
+            chunks: graph.chunks.clone(),
+        }
+    }
+}
+
+/// The endpoint that serves the HTML or API response of the route with the
+/// given pathname.
+async fn route_endpoint(
+    container: Vc<ProjectContainer>,
+    pathname: &str,
+) -> Result<Vc<Box<dyn Endpoint>>> {
+    let entrypoints = container.entrypoints().strongly_consistent().await?;
+    Ok(match entrypoints.routes.get(pathname) {
+        Some(Route::Page { html_endpoint, .. } | Route::AppPage { html_endpoint, .. }) => {
+            *html_endpoint
+        }
+        Some(Route::PageApi { endpoint } | Route::AppRoute { endpoint }) => *endpoint,
+        Some(Route::Conflict) => bail!("route {pathname} is conflicting"),
+        None => bail!("route {pathname} does not exist"),
+    })
+}
+
+#[napi(object)]
+pub struct NapiWarmupResult {
+    pub pathname: String,
+    /// Why the route could not be built, if it failed.
+    pub error: Option<String>,
+    pub issues: Vec<NapiIssue>,
+}
+
+/// Builds the endpoints of the routes with the given pathnames, so they are
+/// compiled before the first request to them. Failures are reported per
+/// route. At most `maxConcurrentBuilds` routes are built at the same time.
+#[napi]
+pub async fn project_warmup(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    pathnames: Vec<String>,
+) -> napi::Result<Vec<NapiWarmupResult>> {
+    let handles: Vec<_> = pathnames
+        .into_iter()
+        .map(|pathname| {
+            let turbo_tasks = project.turbo_tasks.clone();
+            let container = project.container;
+            let issue_filter = project.issue_filter.clone();
+            let build_limiter = project.build_limiter.clone();
+            tokio::spawn(async move {
+                let result = build_limiter
+                    .run(turbo_tasks.run_once({
+                        let pathname = pathname.clone();
+                        async move {
+                            let endpoint = route_endpoint(container, &pathname).await?;
+                            let write_to_disk = endpoint.write_to_disk();
+                            let issues = get_issues(write_to_disk).await?;
+                            let error = write_to_disk
+                                .strongly_consistent()
+                                .await
+                                .err()
+                                .map(|e| PrettyPrintError(&e).to_string());
+                            Ok((issues, error))
+                        }
+                    }))
+                    .await;
+                match result {
+                    Ok((issues, error)) => NapiWarmupResult {
+                        pathname,
+                        error,
+                        issues: issue_filter.issues(&issues),
+                    },
+                    Err(e) => NapiWarmupResult {
+                        pathname,
+                        error: Some(PrettyPrintError(&e).to_string()),
+                        issues: vec![],
+                    },
+                }
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(
+            handle
+                .await
+                .map_err(|e| napi::Error::from_reason(e.to_string()))?,
+        );
+    }
+    Ok(results)
+}
+
+/// Returns the graph of client output assets of the route with the given
+/// pathname. Issues of a route that fails to build are returned together with
+/// the part of the graph that could be resolved.
+#[napi]
+pub async fn project_client_graph(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    pathname: String,
+) -> napi::Result<TurbopackResult<NapiClientGraph>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
+    let container = project.container;
+    let (graph, issues, diags) = turbo_tasks
+        .run_once(async move {
+            let endpoint = route_endpoint(container, &pathname).await?;
+            let graph = client_graph(endpoint);
+            let issues = get_issues(graph).await?;
+            let diags = get_diagnostics(graph).await?;
+            let graph = graph.strongly_consistent().await?;
+            Ok((graph, issues, diags))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(TurbopackResult {
+        result: NapiClientGraph::from(&*graph),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
+    })
+}
+
+#[napi(object)]
+pub struct NapiTransformedModule {
+    pub code: String,
+    /// The source map of the code, serialized to JSON.
+    pub source_map: Option<String>,
+}
+
+impl From<&TransformedModule> for NapiTransformedModule {
+    fn from(module: &TransformedModule) -> Self {
+        NapiTransformedModule {
+            code: module.code.clone(),
+            source_map: module.source_map.clone(),
+        }
+    }
+}
+
+/// Compiles a single module with the client transforms of the project,
+/// without chunking it. `path` is relative to the project path.
+#[napi]
+pub async fn project_transform_module(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    path: String,
+) -> napi::Result<TurbopackResult<NapiTransformedModule>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
+    let container = project.container;
+    let (module, issues, diags) = turbo_tasks
+        .run_once(async move {
+            let module = transform_module(container.project(), path);
+            let issues = get_issues(module).await?;
+            let diags = get_diagnostics(module).await?;
+            let module = module.strongly_consistent().await?;
+            Ok((module, issues, diags))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(TurbopackResult {
+        result: NapiTransformedModule::from(&*module),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
+    })
+}
+
+#[napi(object)]
+pub struct NapiUnsupportedModule {
+    pub module: String,
+    pub importers: Vec<String>,
+}
+
+impl From<&UnsupportedModule> for NapiUnsupportedModule {
+    fn from(module: &UnsupportedModule) -> Self {
+        NapiUnsupportedModule {
+            module: module.module.clone(),
+            importers: module.importers.clone(),
+        }
+    }
+}
+
+/// Returns the deduplicated list of modules reported as unsupported while
+/// building all routes of the project, with the modules importing them.
+#[napi]
+pub async fn project_unsupported_modules(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> napi::Result<TurbopackResult<Vec<NapiUnsupportedModule>>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
+    let container = project.container;
+    let (modules, issues, diags) = turbo_tasks
+        .run_once(async move {
+            let modules = unsupported_modules(container.project());
+            let issues = get_issues(modules).await?;
+            let diags = get_diagnostics(modules).await?;
+            let modules = modules.strongly_consistent().await?;
+            Ok((modules, issues, diags))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(TurbopackResult {
+        result: modules.iter().map(NapiUnsupportedModule::from).collect(),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
+    })
+}
+
+#[napi(object)]
+pub struct NapiUnusedDependency {
+    pub name: String,
+    /// The version range declared in `package.json`.
+    pub version: String,
+}
+
+/// Builds all routes and lists the `dependencies` of the project's
+/// `package.json` that none of them import. They are also reported as an
+/// `unused-dependencies` diagnostic.
+#[napi]
+pub async fn project_unused_dependencies(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> napi::Result<TurbopackResult<Vec<NapiUnusedDependency>>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
+    let container = project.container;
+    let (dependencies, issues, diags) = turbo_tasks
+        .run_once(async move {
+            let dependencies = unused_dependencies(container.project());
+            let issues = get_issues(dependencies).await?;
+            let diags = get_diagnostics(dependencies).await?;
+            let dependencies = dependencies.strongly_consistent().await?;
+            Ok((dependencies, issues, diags))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(TurbopackResult {
+        result: dependencies
+            .iter()
+            .map(|(name, version)| NapiUnusedDependency {
+                name: name.clone(),
+                version: version.clone(),
+            })
+            .collect(),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
+    })
+}
+
+#[napi(object)]
+pub struct NapiModuleGraphNode {
+    /// The kind of the node, e.g. a module or a chunk.
+    pub ty: String,
+    pub title: String,
+    /// The titles of the route's chunks that contain the node.
+    pub chunks: Vec<String>,
+}
+
+#[napi(object)]
+pub struct NapiModuleGraphEdge {
+    /// The index of the referencing node.
+    pub from: u32,
+    /// The index of the referenced node.
+    pub to: u32,
+    pub label: String,
+}
+
+#[napi(object)]
+pub struct NapiModuleGraph {
+    pub nodes: Vec<NapiModuleGraphNode>,
+    pub edges: Vec<NapiModuleGraphEdge>,
+}
+
+impl From<&ModuleGraph> for NapiModuleGraph {
+    fn from(graph: &ModuleGraph) -> Self {
+        NapiModuleGraph {
+            nodes: graph
+                .nodes
+                .iter()
+                .map(|node| NapiModuleGraphNode {
+                    ty: node.ty.clone(),
+                    title: node.title.clone(),
+                    chunks: node.chunks.clone(),
+                })
+                .collect(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|edge| NapiModuleGraphEdge {
+                    from: edge.from as u32,
+                    to: edge.to as u32,
+                    label: edge.label.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns the modules and chunks pulled in by the route with the given
+/// pathname, as shown by the introspection routes.
+#[napi]
+pub async fn project_route_module_graph(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    pathname: String,
+) -> napi::Result<TurbopackResult<NapiModuleGraph>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
+    let container = project.container;
+    let (graph, issues, diags) = turbo_tasks
+        .run_once(async move {
+            let endpoint = route_endpoint(container, &pathname).await?;
+            let graph = route_module_graph(endpoint);
+            let issues = get_issues(graph).await?;
+            let diags = get_diagnostics(graph).await?;
+            let graph = graph.strongly_consistent().await?;
+            Ok((graph, issues, diags))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
+    Ok(TurbopackResult {
+        result: NapiModuleGraph::from(&*graph),
+        issues: issue_filter.issues(&issues),
+        diagnostics: issue_filter.diagnostics(&diags),
+    })
+}
+
 #[napi(object)]
 #[derive(Default)]
 struct NapiRoute {
@@ -211,19 +864,33 @@ struct NapiRoute {
     pub html_endpoint: Option<External<ExternalEndpoint>>,
     pub rsc_endpoint: Option<External<ExternalEndpoint>>,
     pub data_endpoint: Option<External<ExternalEndpoint>>,
+
+    /// Whether an app page or route has any `"use client"` boundaries. Only
+    /// computed when requested from `project_entrypoints_subscribe`.
+    pub has_client_components: Option<bool>,
+
+    /// The runtime the route is rendered with, `"nodejs"` or `"edge"`. Only
+    /// computed when requested from `project_entrypoints_subscribe`.
+    pub runtime: Option<&'static str>,
 }
 
 impl NapiRoute {
     fn from_route(
         pathname: String,
         value: Route,
+        has_client_components: Option<bool>,
+        runtime: Option<&'static str>,
         turbo_tasks: &Arc<TurboTasks<MemoryBackend>>,
+        issue_filter: &IssueFilter,
+        build_limiter: &BuildLimiter,
     ) -> Self {
         let convert_endpoint = |endpoint: Vc<Box<dyn Endpoint>>| {
-            Some(External::new(ExternalEndpoint(VcArc::new(
+            Some(External::new(ExternalEndpoint::new(
                 turbo_tasks.clone(),
                 endpoint,
-            ))))
+                issue_filter.clone(),
+                build_limiter.clone(),
+            )))
         };
         match value {
             Route::Page {
@@ -234,12 +901,14 @@ impl NapiRoute {
                 r#type: "page",
                 html_endpoint: convert_endpoint(html_endpoint),
                 data_endpoint: convert_endpoint(data_endpoint),
+                runtime,
                 ..Default::default()
             },
             Route::PageApi { endpoint } => NapiRoute {
                 pathname,
                 r#type: "page-api",
                 endpoint: convert_endpoint(endpoint),
+                runtime,
                 ..Default::default()
             },
             Route::AppPage {
@@ -250,12 +919,16 @@ impl NapiRoute {
                 r#type: "app-page",
                 html_endpoint: convert_endpoint(html_endpoint),
                 rsc_endpoint: convert_endpoint(rsc_endpoint),
+                has_client_components,
+                runtime,
                 ..Default::default()
             },
             Route::AppRoute { endpoint } => NapiRoute {
                 pathname,
                 r#type: "app-route",
                 endpoint: convert_endpoint(endpoint),
+                has_client_components,
+                runtime,
                 ..Default::default()
             },
             Route::Conflict => NapiRoute {
@@ -267,21 +940,43 @@ impl NapiRoute {
     }
 }
 
+#[napi(object)]
+struct NapiMiddlewareMatcher {
+    /// The regular expression request paths are matched against.
+    pub regexp: String,
+    /// The matcher as written in the middleware config.
+    pub original_source: String,
+}
+
 #[napi(object)]
 struct NapiMiddleware {
     pub endpoint: External<ExternalEndpoint>,
+    /// The paths the middleware runs for.
+    pub matchers: Vec<NapiMiddlewareMatcher>,
 }
 
 impl NapiMiddleware {
     fn from_middleware(
         value: &Middleware,
+        matchers: &[MiddlewarePathMatcher],
         turbo_tasks: &Arc<TurboTasks<MemoryBackend>>,
+        issue_filter: &IssueFilter,
+        build_limiter: &BuildLimiter,
     ) -> Result<Self> {
         Ok(NapiMiddleware {
-            endpoint: External::new(ExternalEndpoint(VcArc::new(
+            endpoint: External::new(ExternalEndpoint::new(
                 turbo_tasks.clone(),
                 value.endpoint,
-            ))),
+                issue_filter.clone(),
+                build_limiter.clone(),
+            )),
+            matchers: matchers
+                .iter()
+                .map(|matcher| NapiMiddlewareMatcher {
+                    regexp: matcher.regexp.clone(),
+                    original_source: matcher.original_source.clone(),
+                })
+                .collect(),
         })
     }
 }
@@ -292,15 +987,28 @@ struct NapiEntrypoints {
     pub pages_document_endpoint: External<ExternalEndpoint>,
     pub pages_app_endpoint: External<ExternalEndpoint>,
     pub pages_error_endpoint: External<ExternalEndpoint>,
+    /// Whether the project has an app directory, i.e. uses the App Router.
+    pub has_app_dir: bool,
+    /// The app directory relative to the project directory, e.g. `src/app`.
+    pub app_dir: Option<String>,
 }
 
 #[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
 pub fn project_entrypoints_subscribe(
     #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
     func: JsFunction,
+    include_client_components: Option<bool>,
+    include_runtime: Option<bool>,
 ) -> napi::Result<External<RootTask>> {
     let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
+    let build_limiter = project.build_limiter.clone();
     let container = project.container;
+    // Walking the module graph of every app route is much slower than
+    // resolving the entrypoints, so it has to be requested.
+    let include_client_components = include_client_components.unwrap_or(false);
+    // Parsing the config of every route is cheaper, but still opt-in.
+    let include_runtime = include_runtime.unwrap_or(false);
     subscribe(
         turbo_tasks.clone(),
         func,
@@ -311,10 +1019,75 @@ pub fn project_entrypoints_subscribe(
 
             let entrypoints = entrypoints.strongly_consistent().await?;
 
-            Ok((entrypoints, issues, diags))
+            let client_components: HashMap<String, bool> = if include_client_components {
+                entrypoints
+                    .routes
+                    .iter()
+                    .map(|(pathname, &route)| async move {
+                        let endpoint = match route {
+                            Route::AppPage { rsc_endpoint, .. } => rsc_endpoint,
+                            Route::AppRoute { endpoint } => endpoint,
+                            Route::Page { .. } | Route::PageApi { .. } | Route::Conflict => {
+                                return Ok(None);
+                            }
+                        };
+                        let has_client_components = *endpoint
+                            .has_client_components()
+                            .strongly_consistent()
+                            .await?;
+                        Ok(Some((pathname.clone(), has_client_components)))
+                    })
+                    .try_flat_join()
+                    .await?
+                    .into_iter()
+                    .collect()
+            } else {
+                HashMap::new()
+            };
+
+            let runtimes: HashMap<String, &'static str> = if include_runtime {
+                entrypoints
+                    .routes
+                    .iter()
+                    .map(|(pathname, &route)| async move {
+                        let endpoint = match route {
+                            Route::Page { html_endpoint, .. }
+                            | Route::AppPage { html_endpoint, .. } => html_endpoint,
+                            Route::PageApi { endpoint } | Route::AppRoute { endpoint } => endpoint,
+                            Route::Conflict => return Ok(None),
+                        };
+                        let runtime = *endpoint.runtime().strongly_consistent().await?;
+                        Ok(Some((pathname.clone(), runtime.as_str())))
+                    })
+                    .try_flat_join()
+                    .await?
+                    .into_iter()
+                    .collect()
+            } else {
+                HashMap::new()
+            };
+
+            let middleware_matchers = match &entrypoints.middleware {
+                Some(middleware) => middleware
+                    .matchers
+                    .strongly_consistent()
+                    .await?
+                    .clone_value(),
+                None => Vec::new(),
+            };
+
+            Ok((
+                entrypoints,
+                client_components,
+                runtimes,
+                middleware_matchers,
+                issues,
+                diags,
+            ))
         },
         move |ctx| {
-            let (entrypoints, issues, diags) = ctx.value;
+            let (entrypoints, client_components, runtimes, middleware_matchers, issues, diags) =
+                ctx.value;
 
             Ok(vec![TurbopackResult {
                 result: NapiEntrypoints {
@@ -322,37 +1095,83 @@ pub fn project_entrypoints_subscribe(
                         .routes
                         .iter()
                         .map(|(pathname, &route)| {
-                            NapiRoute::from_route(pathname.clone(), route, &turbo_tasks)
+                            NapiRoute::from_route(
+                                pathname.clone(),
+                                route,
+                                client_components.get(pathname).copied(),
+                                runtimes.get(pathname).copied(),
+                                &turbo_tasks,
+                                &issue_filter,
+                                &build_limiter,
+                            )
                         })
                         .collect::<Vec<_>>(),
                     middleware: entrypoints
                         .middleware
                         .as_ref()
-                        .map(|m| NapiMiddleware::from_middleware(m, &turbo_tasks))
+                        .map(|m| {
+                            NapiMiddleware::from_middleware(
+                                m,
+                                &middleware_matchers,
+                                &turbo_tasks,
+                                &issue_filter,
+                                &build_limiter,
+                            )
+                        })
                         .transpose()?,
-                    pages_document_endpoint: External::new(ExternalEndpoint(VcArc::new(
+                    pages_document_endpoint: External::new(ExternalEndpoint::new(
                         turbo_tasks.clone(),
                         entrypoints.pages_document_endpoint,
-                    ))),
-                    pages_app_endpoint: External::new(ExternalEndpoint(VcArc::new(
+                        issue_filter.clone(),
+                        build_limiter.clone(),
+                    )),
+                    pages_app_endpoint: External::new(ExternalEndpoint::new(
                         turbo_tasks.clone(),
                         entrypoints.pages_app_endpoint,
-                    ))),
-                    pages_error_endpoint: External::new(ExternalEndpoint(VcArc::new(
+                        issue_filter.clone(),
+                        build_limiter.clone(),
+                    )),
+                    pages_error_endpoint: External::new(ExternalEndpoint::new(
                         turbo_tasks.clone(),
                         entrypoints.pages_error_endpoint,
-                    ))),
+                        issue_filter.clone(),
+                        build_limiter.clone(),
+                    )),
+                    has_app_dir: entrypoints.app_dir.is_some(),
+                    app_dir: entrypoints.app_dir.clone(),
                 },
-                issues: issues
-                    .iter()
-                    .map(|issue| NapiIssue::from(&**issue))
-                    .collect(),
-                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+                issues: issue_filter.issues(&issues),
+                diagnostics: issue_filter.diagnostics(&diags),
             }])
         },
     )
 }
 
+/// Numbers the updates of an HMR subscription in the order they were
+/// computed, so clients can discard updates delivered out of order.
+#[derive(Default)]
+struct HmrSequence {
+    next: AtomicU64,
+}
+
+impl HmrSequence {
+    fn next(&self) -> u64 {
+        self.next.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+/// A [ClientUpdateInstruction] with ordering metadata.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SequencedClientUpdateInstruction<'a> {
+    #[serde(flatten)]
+    instruction: ClientUpdateInstruction<'a>,
+    /// Increases with each update of the same identifier.
+    sequence: u64,
+    /// Milliseconds since the Unix epoch at which the update was computed.
+    timestamp: u64,
+}
+
 #[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
 pub fn project_hmr_events(
     #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
@@ -360,8 +1179,10 @@ pub fn project_hmr_events(
     func: JsFunction,
 ) -> napi::Result<External<RootTask>> {
     let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
     let project = project.container;
     let session = TransientInstance::new(());
+    let sequence = Arc::new(HmrSequence::default());
     subscribe(
         turbo_tasks.clone(),
         func,
@@ -371,6 +1192,7 @@ pub fn project_hmr_events(
             move || {
                 let identifier = identifier.clone();
                 let session = session.clone();
+                let sequence = sequence.clone();
                 async move {
                     let state = project
                         .project()
@@ -388,17 +1210,17 @@ pub fn project_hmr_events(
                             state.set(to.clone()).await?;
                         }
                     }
-                    Ok((update, issues, diags))
+                    let timestamp = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |duration| duration.as_millis() as u64);
+                    Ok((update, issues, diags, sequence.next(), timestamp))
                 }
             }
         },
         move |ctx| {
-            let (update, issues, diags) = ctx.value;
+            let (update, issues, diags, sequence, timestamp) = ctx.value;
 
-            let napi_issues = issues
-                .iter()
-                .map(|issue| NapiIssue::from(&**issue))
-                .collect();
+            let napi_issues = issue_filter.issues(&issues);
             let update_issues = issues
                 .iter()
                 .map(|issue| (&**issue).into())
@@ -408,7 +1230,7 @@ pub fn project_hmr_events(
                 path: identifier.clone(),
                 headers: None,
             };
-            let update = match &*update {
+            let instruction = match &*update {
                 Update::Total(_) => ClientUpdateInstruction::restart(&identifier, &update_issues),
                 Update::Partial(update) => ClientUpdateInstruction::partial(
                     &identifier,
@@ -417,11 +1239,16 @@ pub fn project_hmr_events(
                 ),
                 Update::None => ClientUpdateInstruction::issues(&identifier, &update_issues),
             };
+            let update = SequencedClientUpdateInstruction {
+                instruction,
+                sequence,
+                timestamp,
+            };
 
             Ok(vec![TurbopackResult {
                 result: ctx.env.to_js_value(&update)?,
                 issues: napi_issues,
-                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+                diagnostics: issue_filter.diagnostics(&diags),
             }])
         },
     )
@@ -438,6 +1265,7 @@ pub fn project_hmr_identifiers_subscribe(
     func: JsFunction,
 ) -> napi::Result<External<RootTask>> {
     let turbo_tasks = project.turbo_tasks.clone();
+    let issue_filter = project.issue_filter.clone();
     let container = project.container;
     subscribe(
         turbo_tasks.clone(),
@@ -461,48 +1289,93 @@ pub fn project_hmr_identifiers_subscribe(
                         .map(|ident| ident.to_string())
                         .collect::<Vec<_>>(),
                 },
-                issues: issues
-                    .iter()
-                    .map(|issue| NapiIssue::from(&**issue))
-                    .collect(),
-                diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
+                issues: issue_filter.issues(&issues),
+                diagnostics: issue_filter.diagnostics(&diags),
             }])
         },
     )
 }
 
+#[napi(object)]
+struct NapiUpdateReason {
+    pub reason: String,
+    pub count: u32,
+}
+
 #[napi(object)]
 struct NapiUpdateInfo {
     pub duration: u32,
     pub tasks: u32,
+    /// The invalidation reasons of the update, grouped by kind.
+    pub reasons: Vec<NapiUpdateReason>,
+    /// The number of executed tasks which had never run before.
+    pub new_tasks: u32,
+    /// The number of executed tasks whose cached result was invalidated.
+    pub invalidated_tasks: u32,
 }
 
-impl From<UpdateInfo> for NapiUpdateInfo {
-    fn from(update_info: UpdateInfo) -> Self {
+impl From<(UpdateInfo, UpdateTaskBreakdown)> for NapiUpdateInfo {
+    fn from((update_info, breakdown): (UpdateInfo, UpdateTaskBreakdown)) -> Self {
         Self {
             duration: update_info.duration.as_millis() as u32,
             tasks: update_info.tasks as u32,
+            reasons: UpdateReasons::new(&update_info.reasons)
+                .kinds
+                .into_iter()
+                .map(|UpdateReasonCount { reason, count }| NapiUpdateReason { reason, count })
+                .collect(),
+            new_tasks: breakdown.new_tasks as u32,
+            invalidated_tasks: breakdown.invalidated_tasks as u32,
         }
     }
 }
 
+/// The aggregation window of update infos when none is requested.
+const DEFAULT_UPDATE_INFO_INTERVAL: Duration = Duration::from_secs(1);
+/// Shorter windows report updates before they have settled.
+const MIN_UPDATE_INFO_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_UPDATE_INFO_INTERVAL: Duration = Duration::from_secs(60);
+
+fn update_info_interval(interval_ms: Option<u32>) -> napi::Result<Duration> {
+    let Some(interval_ms) = interval_ms else {
+        return Ok(DEFAULT_UPDATE_INFO_INTERVAL);
+    };
+    let interval = Duration::from_millis(interval_ms.into());
+    if !(MIN_UPDATE_INFO_INTERVAL..=MAX_UPDATE_INFO_INTERVAL).contains(&interval) {
+        return Err(napi::Error::from_reason(format!(
+            "update info interval must be between {}ms and {}ms, got {interval_ms}ms",
+            MIN_UPDATE_INFO_INTERVAL.as_millis(),
+            MAX_UPDATE_INFO_INTERVAL.as_millis(),
+        )));
+    }
+    Ok(interval)
+}
+
 #[napi]
 pub fn project_update_info_subscribe(
     #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
     func: JsFunction,
+    interval_ms: Option<u32>,
 ) -> napi::Result<()> {
-    let func: ThreadsafeFunction<UpdateInfo> = func.create_threadsafe_function(0, |ctx| {
-        let update_info = ctx.value;
-        Ok(vec![NapiUpdateInfo::from(update_info)])
-    })?;
+    let interval = update_info_interval(interval_ms)?;
+    let func: ThreadsafeFunction<(UpdateInfo, UpdateTaskBreakdown)> =
+        func.create_threadsafe_function(0, |ctx| Ok(vec![NapiUpdateInfo::from(ctx.value)]))?;
     let turbo_tasks = project.turbo_tasks.clone();
     tokio::spawn(async move {
+        let mut created_tasks = CreatedTaskCounter::new(&turbo_tasks);
         loop {
             let update_info = turbo_tasks
-                .get_or_wait_aggregated_update_info(Duration::from_secs(1))
+                .get_or_wait_aggregated_update_info(interval)
                 .await;
+            let breakdown = UpdateTaskBreakdown::new(
+                update_info.tasks,
+                created_tasks.created_since_last(&turbo_tasks),
+            );
 
-            let status = func.call(Ok(update_info), ThreadsafeFunctionCallMode::NonBlocking);
+            let status = func.call(
+                Ok((update_info, breakdown)),
+                ThreadsafeFunctionCallMode::NonBlocking,
+            );
             if !matches!(status, Status::Ok) {
                 let error = anyhow!("Error calling JS function: {}", status);
                 eprintln!("{}", error);
@@ -512,3 +1385,121 @@ pub fn project_update_info_subscribe(
     });
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use next_core::util::NextRuntime;
+
+    use super::{
+        canonicalize_project_paths, config_dist_dir, parse_runtime, resolve_server_addr,
+        server_url, update_info_interval,
+    };
+
+    #[test]
+    fn runtime_is_parsed() {
+        assert!(matches!(
+            parse_runtime("nodejs").unwrap(),
+            NextRuntime::NodeJs
+        ));
+        assert!(matches!(parse_runtime("edge").unwrap(), NextRuntime::Edge));
+        let err = parse_runtime("deno").unwrap_err().to_string();
+        assert!(err.contains("unknown runtime \"deno\""), "{err}");
+    }
+
+    #[test]
+    fn update_info_interval_is_validated() {
+        assert_eq!(update_info_interval(None).unwrap(), Duration::from_secs(1));
+        assert_eq!(
+            update_info_interval(Some(200)).unwrap(),
+            Duration::from_millis(200)
+        );
+        assert!(update_info_interval(Some(10)).is_err());
+        assert!(update_info_interval(Some(120_000)).is_err());
+    }
+
+    #[test]
+    fn server_addr_port_is_resolved() {
+        let addr = resolve_server_addr("0.0.0.0:0", None).unwrap();
+        assert!(addr.ip().is_unspecified());
+        assert_ne!(addr.port(), 0);
+        // Updating the options keeps the port.
+        assert_eq!(resolve_server_addr("0.0.0.0:0", Some(addr)).unwrap(), addr);
+        assert_eq!(
+            resolve_server_addr("127.0.0.1:3000", Some(addr))
+                .unwrap()
+                .to_string(),
+            "127.0.0.1:3000"
+        );
+        assert_eq!(
+            resolve_server_addr("[::]:3000", None).unwrap().to_string(),
+            "[::]:3000"
+        );
+        assert!(resolve_server_addr("localhost:3000", None).is_err());
+        assert!(resolve_server_addr("127.0.0.1", None).is_err());
+    }
+
+    #[test]
+    fn server_url_includes_scheme_and_host() {
+        assert_eq!(
+            server_url("0.0.0.0:3000".parse().unwrap(), false),
+            "http://localhost:3000"
+        );
+        assert_eq!(
+            server_url("127.0.0.1:3000".parse().unwrap(), false),
+            "http://127.0.0.1:3000"
+        );
+        assert_eq!(
+            server_url("[::1]:3000".parse().unwrap(), false),
+            "http://[::1]:3000"
+        );
+    }
+
+    #[test]
+    fn server_url_follows_https() {
+        assert_eq!(
+            server_url("0.0.0.0:3000".parse().unwrap(), true),
+            "https://localhost:3000"
+        );
+        assert_eq!(
+            server_url("[::1]:3000".parse().unwrap(), true),
+            "https://[::1]:3000"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_project_paths_are_canonicalized() {
+        let root = std::env::temp_dir().join(format!("next-napi-symlink-{}", std::process::id()));
+        let project = root.join("project");
+        let link = root.join("link");
+        std::fs::create_dir_all(&project).unwrap();
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&project, &link).unwrap();
+
+        let (root_path, project_path) =
+            canonicalize_project_paths(root.to_str().unwrap(), link.to_str().unwrap()).unwrap();
+        let real_root = dunce::canonicalize(&root).unwrap();
+        assert_eq!(root_path, real_root.to_str().unwrap());
+        assert_eq!(project_path, real_root.join("project").to_str().unwrap());
+        assert!(
+            canonicalize_project_paths(project.to_str().unwrap(), root.to_str().unwrap()).is_err()
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn trace_dir_follows_dist_dir() {
+        let project = std::path::Path::new("/project");
+        let dist_dir = config_dist_dir(r#"{"distDir":"build","env":{}}"#);
+        assert_eq!(dist_dir.as_deref(), Some("build"));
+        assert_eq!(
+            next_core::util::internal_dir(project, dist_dir.as_deref()).join("trace.log"),
+            project.join("build/trace.log")
+        );
+        assert_eq!(config_dist_dir("{}"), None);
+        assert_eq!(config_dist_dir("not json"), None);
+    }
+}
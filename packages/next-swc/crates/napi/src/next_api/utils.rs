@@ -1,19 +1,27 @@
-use std::{collections::HashMap, future::Future, ops::Deref, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    ops::Deref,
+    sync::{Arc, RwLock},
+    thread::available_parallelism,
+};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use napi::{
     bindgen_prelude::{External, ToNapiValue},
     threadsafe_function::{ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode},
     JsFunction, JsObject, JsUnknown, NapiRaw, NapiValue, Status,
 };
+use next_dev::{issue_counts::IssueCounts, task_limit::timeout_on_task_limit};
 use serde::Serialize;
+use tokio::sync::Semaphore;
 use turbo_tasks::{ReadRef, TaskId, TryJoinIterExt, TurboTasks, Vc};
 use turbopack_binding::{
     turbo::{tasks_fs::FileContent, tasks_memory::MemoryBackend},
     turbopack::core::{
         diagnostics::{Diagnostic, DiagnosticContextExt, PlainDiagnostic},
         error::PrettyPrintError,
-        issue::{IssueDescriptionExt, PlainIssue, PlainIssueSource, PlainSource},
+        issue::{IssueDescriptionExt, IssueSeverity, PlainIssue, PlainIssueSource, PlainSource},
         source_pos::SourcePos,
     },
 };
@@ -115,6 +123,24 @@ pub struct NapiIssue {
     pub sub_issues: Vec<NapiIssue>,
 }
 
+impl NapiIssue {
+    /// An issue with only its severity, file path and title, which are cheap
+    /// to pass to JS.
+    fn minimal(severity: String, file_path: String, title: String) -> Self {
+        Self {
+            severity,
+            category: String::new(),
+            file_path,
+            title,
+            description: String::new(),
+            detail: String::new(),
+            source: None,
+            documentation_link: String::new(),
+            sub_issues: vec![],
+        }
+    }
+}
+
 impl From<&PlainIssue> for NapiIssue {
     fn from(issue: &PlainIssue) -> Self {
         Self {
@@ -135,6 +161,30 @@ impl From<&PlainIssue> for NapiIssue {
     }
 }
 
+/// The number of errors and warnings of a result, e.g. to show a badge. Bugs
+/// and fatal issues count as errors.
+#[napi(object)]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct NapiIssueCounts {
+    pub errors: u32,
+    pub warnings: u32,
+}
+
+impl NapiIssueCounts {
+    /// Counts like the dev server's summary, see [IssueCounts].
+    fn from_issues(issues: &[NapiIssue]) -> Self {
+        let IssueCounts { errors, warnings } = IssueCounts::from_severities(
+            issues
+                .iter()
+                .filter_map(|issue| parse_issue_severity(&issue.severity).ok()),
+        );
+        NapiIssueCounts {
+            errors: errors as u32,
+            warnings: warnings as u32,
+        }
+    }
+}
+
 #[napi(object)]
 pub struct NapiIssueSource {
     pub source: NapiSource,
@@ -211,6 +261,229 @@ impl NapiDiagnostic {
     }
 }
 
+/// Which issues and diagnostics are passed to JS. Converting them is costly
+/// for projects with many issues, so the ones nobody looks at are dropped
+/// early.
+///
+/// The filter is shared by a project and its endpoints, so updating the
+/// project options applies to all of them.
+#[derive(Clone, Default)]
+pub struct IssueFilter(Arc<RwLock<IssueFilterOptions>>);
+
+/// The options of an [IssueFilter], as passed from JS.
+#[derive(Default)]
+pub struct IssueFilterConfig {
+    /// Keeps issues at least as severe as this, e.g. `"warning"`. `None`
+    /// keeps all issues.
+    pub min_issue_severity: Option<String>,
+    /// Keeps the diagnostics of these categories. `None` keeps all
+    /// diagnostics.
+    pub diagnostic_categories: Option<Vec<String>>,
+    /// Passes issues of these categories as errors.
+    pub error_on: Option<Vec<String>>,
+    /// `"minimal"` only passes the severity, file path and title of each
+    /// issue, `"full"` (the default) passes everything.
+    pub issue_detail: Option<String>,
+}
+
+#[derive(Default)]
+struct IssueFilterOptions {
+    min_severity: Option<IssueSeverity>,
+    diagnostic_categories: Option<Vec<String>>,
+    error_on: Vec<String>,
+    detail: IssueDetail,
+}
+
+/// How much of each issue is passed to JS.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum IssueDetail {
+    /// Only the severity, file path and title. Skips copying the source of
+    /// the issue, which is expensive for frequent updates.
+    Minimal,
+    #[default]
+    Full,
+}
+
+impl IssueFilter {
+    /// Replaces the options of the filter, failing if a severity or detail
+    /// level is unknown.
+    pub fn set(&self, config: IssueFilterConfig) -> Result<()> {
+        let IssueFilterConfig {
+            min_issue_severity,
+            diagnostic_categories,
+            error_on,
+            issue_detail,
+        } = config;
+        let min_severity = min_issue_severity
+            .as_deref()
+            .map(parse_issue_severity)
+            .transpose()?;
+        let detail = issue_detail
+            .as_deref()
+            .map(parse_issue_detail)
+            .transpose()?
+            .unwrap_or_default();
+        *self.0.write().unwrap() = IssueFilterOptions {
+            min_severity,
+            diagnostic_categories,
+            error_on: error_on.unwrap_or_default(),
+            detail,
+        };
+        Ok(())
+    }
+
+    fn escalated_severity(&self, severity: IssueSeverity, category: &str) -> IssueSeverity {
+        if severity_rank(severity) > severity_rank(IssueSeverity::Error)
+            && self
+                .0
+                .read()
+                .unwrap()
+                .error_on
+                .iter()
+                .any(|c| c == category)
+        {
+            IssueSeverity::Error
+        } else {
+            severity
+        }
+    }
+
+    fn includes_severity(&self, severity: IssueSeverity) -> bool {
+        self.0
+            .read()
+            .unwrap()
+            .min_severity
+            .map_or(true, |min_severity| {
+                severity_rank(severity) <= severity_rank(min_severity)
+            })
+    }
+
+    pub fn issues(&self, issues: &[ReadRef<PlainIssue>]) -> Vec<NapiIssue> {
+        let detail = self.0.read().unwrap().detail;
+        issues
+            .iter()
+            .filter_map(|issue| {
+                let severity = self.escalated_severity(issue.severity, &issue.category);
+                if !self.includes_severity(severity) {
+                    return None;
+                }
+                let severity = severity.as_str().to_string();
+                Some(match detail {
+                    IssueDetail::Minimal => {
+                        NapiIssue::minimal(severity, issue.file_path.clone(), issue.title.clone())
+                    }
+                    IssueDetail::Full => NapiIssue {
+                        severity,
+                        ..NapiIssue::from(&**issue)
+                    },
+                })
+            })
+            .collect()
+    }
+
+    pub fn diagnostics(&self, diagnostics: &[ReadRef<PlainDiagnostic>]) -> Vec<NapiDiagnostic> {
+        let options = self.0.read().unwrap();
+        diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                options
+                    .diagnostic_categories
+                    .as_ref()
+                    .map_or(true, |categories| categories.contains(&diagnostic.category))
+            })
+            .map(|diagnostic| NapiDiagnostic::from(diagnostic))
+            .collect()
+    }
+}
+
+/// Bounds how many endpoint builds run at the same time, so building many
+/// endpoints at once doesn't push memory usage past the memory limit.
+///
+/// The limiter is shared by a project and its endpoints.
+#[derive(Clone)]
+pub struct BuildLimiter {
+    semaphore: Arc<Semaphore>,
+    task_limit: Option<(Arc<TurboTasks<MemoryBackend>>, usize)>,
+}
+
+impl BuildLimiter {
+    /// Allows `max_concurrent_builds` builds at a time. Defaults to the number
+    /// of CPUs.
+    pub fn new(max_concurrent_builds: Option<u32>) -> Self {
+        let permits = max_concurrent_builds
+            .map(|max| max as usize)
+            .unwrap_or_else(|| available_parallelism().map_or(1, |cpus| cpus.get()))
+            .max(1);
+        BuildLimiter {
+            semaphore: Arc::new(Semaphore::new(permits)),
+            task_limit: None,
+        }
+    }
+
+    /// Stops waiting for builds with an error as soon as more than
+    /// `max_tasks` tasks are in progress at the same time, instead of letting
+    /// a runaway task graph hang them. See [timeout_on_task_limit].
+    pub fn timeout_on_task_limit(
+        mut self,
+        turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+        max_tasks: Option<usize>,
+    ) -> Self {
+        self.task_limit = max_tasks.map(|max_tasks| (turbo_tasks, max_tasks));
+        self
+    }
+
+    /// Runs `build` once fewer than the maximum number of builds are running.
+    pub async fn run<T>(&self, build: impl Future<Output = Result<T>>) -> Result<T> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("the build semaphore is never closed");
+        match &self.task_limit {
+            Some((turbo_tasks, max_tasks)) => {
+                timeout_on_task_limit(turbo_tasks, *max_tasks, build).await
+            }
+            None => build.await,
+        }
+    }
+}
+
+/// Lower is more severe.
+fn severity_rank(severity: IssueSeverity) -> u8 {
+    match severity {
+        IssueSeverity::Bug => 0,
+        IssueSeverity::Fatal => 1,
+        IssueSeverity::Error => 2,
+        IssueSeverity::Warning => 3,
+        IssueSeverity::Hint => 4,
+        IssueSeverity::Note => 5,
+        IssueSeverity::Suggestion => 6,
+        IssueSeverity::Info => 7,
+    }
+}
+
+fn parse_issue_severity(severity: &str) -> Result<IssueSeverity> {
+    Ok(match severity {
+        "bug" => IssueSeverity::Bug,
+        "fatal" => IssueSeverity::Fatal,
+        "error" => IssueSeverity::Error,
+        "warning" => IssueSeverity::Warning,
+        "hint" => IssueSeverity::Hint,
+        "note" => IssueSeverity::Note,
+        "suggestion" => IssueSeverity::Suggestion,
+        "info" => IssueSeverity::Info,
+        _ => bail!("unknown issue severity {severity:?}"),
+    })
+}
+
+fn parse_issue_detail(detail: &str) -> Result<IssueDetail> {
+    Ok(match detail {
+        "minimal" => IssueDetail::Minimal,
+        "full" => IssueDetail::Full,
+        _ => bail!("unknown issue detail {detail:?}, expected \"minimal\" or \"full\""),
+    })
+}
+
 pub struct TurbopackResult<T: ToNapiValue> {
     pub result: T,
     pub issues: Vec<NapiIssue>,
@@ -236,6 +509,7 @@ impl<T: ToNapiValue> ToNapiValue for TurbopackResult<T> {
             }
         }
 
+        obj.set_named_property("issueCounts", NapiIssueCounts::from_issues(&val.issues))?;
         obj.set_named_property("issues", val.issues)?;
         obj.set_named_property("diagnostics", val.diagnostics)?;
 
@@ -273,3 +547,192 @@ pub fn subscribe<T: 'static + Send + Sync, F: Future<Output = Result<T>> + Send,
         task_id: Some(task_id),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use turbo_tasks::{ReadRef, TurboTasks, Vc};
+    use turbopack_binding::{
+        turbo::{
+            tasks_fs::{FileSystem, VirtualFileSystem},
+            tasks_memory::MemoryBackend,
+        },
+        turbopack::core::{
+            ident::AssetIdent,
+            issue::{analyze::AnalyzeIssue, IssueSeverity, PlainIssue},
+        },
+    };
+
+    use super::{BuildLimiter, IssueFilter, IssueFilterConfig, NapiIssue, NapiIssueCounts};
+
+    /// An issue as [IssueFilter::issues] receives it.
+    async fn plain_issue(severity: IssueSeverity, category: &str) -> ReadRef<PlainIssue> {
+        crate::register();
+        let category = category.to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        tt.run_once(async move {
+            let path = VirtualFileSystem::new()
+                .root()
+                .join("pages/index.js".to_string());
+            AnalyzeIssue {
+                severity: severity.cell(),
+                source_ident: AssetIdent::from_path(path),
+                title: Vc::cell("Parsing ecmascript source code failed".to_string()),
+                message: Vc::cell("Expression expected\n".repeat(100)),
+                category: Vc::cell(category),
+                code: None,
+                source: None,
+            }
+            .cell()
+            .into_plain(Vc::cell(None))
+            .await
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_builds() {
+        let limiter = BuildLimiter::new(Some(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let running = running.clone();
+                let max_running = max_running.clone();
+                tokio::spawn(async move {
+                    limiter
+                        .run(async {
+                            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                            max_running.fetch_max(now, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(10)).await;
+                            running.fetch_sub(1, Ordering::SeqCst);
+                            Ok(())
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn drops_issues_below_min_severity() {
+        let filter = IssueFilter::default();
+        assert!(filter.includes_severity(IssueSeverity::Info));
+
+        filter
+            .set(IssueFilterConfig {
+                min_issue_severity: Some("warning".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(!filter.includes_severity(IssueSeverity::Info));
+        assert!(!filter.includes_severity(IssueSeverity::Hint));
+        assert!(filter.includes_severity(IssueSeverity::Warning));
+        assert!(filter.includes_severity(IssueSeverity::Error));
+
+        assert!(filter
+            .set(IssueFilterConfig {
+                min_issue_severity: Some("loud".to_string()),
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn escalates_error_on_categories() {
+        let filter = IssueFilter::default();
+        filter
+            .set(IssueFilterConfig {
+                error_on: Some(vec!["resolve".to_string()]),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            filter.escalated_severity(IssueSeverity::Warning, "resolve"),
+            IssueSeverity::Error
+        );
+        assert_eq!(
+            filter.escalated_severity(IssueSeverity::Fatal, "resolve"),
+            IssueSeverity::Fatal
+        );
+        assert_eq!(
+            filter.escalated_severity(IssueSeverity::Warning, "parse"),
+            IssueSeverity::Warning
+        );
+    }
+
+    #[test]
+    fn counts_errors_and_warnings() {
+        let issues = ["fatal", "error", "warning", "warning", "hint"]
+            .iter()
+            .map(|severity| NapiIssue::minimal(severity.to_string(), String::new(), String::new()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            NapiIssueCounts::from_issues(&issues),
+            NapiIssueCounts {
+                errors: 2,
+                warnings: 2,
+            }
+        );
+    }
+
+    fn payload_size(issue: &NapiIssue) -> usize {
+        let source_size = issue.source.as_ref().map_or(0, |source| {
+            source.source.ident.len() + source.source.content.as_ref().map_or(0, String::len)
+        });
+        [
+            &issue.severity,
+            &issue.category,
+            &issue.file_path,
+            &issue.title,
+            &issue.description,
+            &issue.detail,
+            &issue.documentation_link,
+        ]
+        .iter()
+        .map(|field| field.len())
+        .sum::<usize>()
+            + source_size
+            + issue.sub_issues.iter().map(payload_size).sum::<usize>()
+    }
+
+    #[tokio::test]
+    async fn minimal_issues_are_smaller() {
+        let issues = [plain_issue(IssueSeverity::Error, "parse").await];
+        let filter = IssueFilter::default();
+        let full = filter.issues(&issues);
+        filter
+            .set(IssueFilterConfig {
+                issue_detail: Some("minimal".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let minimal = filter.issues(&issues);
+
+        assert_eq!(minimal[0].severity, "error");
+        assert_eq!(minimal[0].file_path, full[0].file_path);
+        assert_eq!(minimal[0].title, full[0].title);
+        assert!(minimal[0].description.is_empty());
+        assert!(payload_size(&minimal[0]) * 10 < payload_size(&full[0]));
+
+        assert!(filter
+            .set(IssueFilterConfig {
+                issue_detail: Some("short".to_string()),
+                ..Default::default()
+            })
+            .is_err());
+    }
+}
@@ -8,7 +8,10 @@ use napi::bindgen_prelude::*;
 use next_build::{
     build as turbo_next_build, build_options::BuildContext, BuildOptions as NextBuildOptions,
 };
-use next_core::next_config::{Rewrite, Rewrites, RouteHas};
+use next_core::{
+    next_config::{Rewrite, Rewrites, RouteHas},
+    turbopack::core::issue::IssueSeverity,
+};
 
 use crate::util::MapErr;
 
@@ -27,6 +30,13 @@ pub struct NextBuildContext {
 
     /// The rewrites, as computed by Next.js.
     pub rewrites: Option<NapiRewrites>,
+
+    /// Issue categories which fail the build, even if their issues are only
+    /// warnings.
+    pub error_on: Option<Vec<String>>,
+
+    /// Whether warnings fail the build.
+    pub fail_on_warning: Option<bool>,
     // TODO(alexkirsz) These are detected directly by Turbopack for now.
     // pub app_dir: Option<String>,
     // pub pages_dir: Option<String>,
@@ -46,6 +56,11 @@ impl TryFrom<NextBuildContext> for NextBuildOptions {
             show_all: true,
             log_detail: true,
             full_stats: true,
+            error_on: value.error_on.unwrap_or_default(),
+            fail_on: value
+                .fail_on_warning
+                .unwrap_or(false)
+                .then_some(IssueSeverity::Warning),
             memory_limit: None,
             build_context: Some(BuildContext {
                 build_id: value
@@ -5,7 +5,7 @@ use next_core::{
         get_entrypoints, Entrypoint as AppEntrypoint, Entrypoints as AppEntrypoints, LoaderTree,
         MetadataItem,
     },
-    get_edge_resolve_options_context,
+    get_edge_resolve_options_context, get_next_package,
     mode::NextMode,
     next_app::{
         get_app_client_references_chunks, get_app_client_shared_chunks, get_app_page_entry,
@@ -109,7 +109,7 @@ impl AppProject {
     }
 
     #[turbo_tasks::function]
-    fn app_dir(&self) -> Vc<FileSystemPath> {
+    pub fn app_dir(&self) -> Vc<FileSystemPath> {
         self.app_dir
     }
 
@@ -673,7 +673,11 @@ impl AppEndpoint {
             )))
         }
 
-        let endpoint_output = match app_entry.config.await?.runtime.unwrap_or_default() {
+        let runtime = match *this.app_project.project().runtime_override().await? {
+            Some(runtime) => runtime,
+            None => app_entry.config.await?.runtime.unwrap_or_default(),
+        };
+        let endpoint_output = match runtime {
             NextRuntime::Edge => {
                 // create edge chunks
                 let chunking_context = this.app_project.project().edge_rsc_chunking_context();
@@ -920,6 +924,63 @@ impl Endpoint for AppEndpoint {
             .project()
             .client_changed(self.output().client_assets()))
     }
+
+    #[turbo_tasks::function]
+    fn client_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output().client_assets()
+    }
+
+    #[turbo_tasks::function]
+    fn server_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output().server_assets()
+    }
+
+    #[turbo_tasks::function]
+    async fn has_client_components(self: Vc<Self>) -> Result<Vc<bool>> {
+        let this = self.await?;
+        let app_entry = match this.ty {
+            AppEndpointType::Page { loader_tree, .. } => self.app_page_entry(loader_tree),
+            AppEndpointType::Route { path } => self.app_route_entry(path),
+            AppEndpointType::Metadata { metadata } => self.app_metadata_entry(metadata),
+        };
+        let rsc_entry_asset = Vc::upcast(app_entry.await?.rsc_entry);
+        let client_reference_types =
+            ClientReferenceGraph::new(Vc::cell(vec![rsc_entry_asset])).types();
+        // The app-page template itself imports client components of Next.js,
+        // like LayoutRouter, so only client references outside of the Next.js
+        // package count.
+        let next_package = get_next_package(this.app_project.project().project_path()).await?;
+        for ty in client_reference_types.await?.iter() {
+            let ClientReferenceType::EcmascriptClientReference(module) = ty else {
+                continue;
+            };
+            let path = module.await?.server_ident.path().await?;
+            if !path.is_inside_ref(&*next_package) {
+                return Ok(Vc::cell(true));
+            }
+        }
+        Ok(Vc::cell(false))
+    }
+
+    #[turbo_tasks::function]
+    async fn runtime(self: Vc<Self>) -> Result<Vc<NextRuntime>> {
+        let this = self.await?;
+        if let Some(runtime) = *this.app_project.project().runtime_override().await? {
+            return Ok(runtime.cell());
+        }
+        let app_entry = match this.ty {
+            AppEndpointType::Page { loader_tree, .. } => self.app_page_entry(loader_tree),
+            AppEndpointType::Route { path } => self.app_route_entry(path),
+            AppEndpointType::Metadata { metadata } => self.app_metadata_entry(metadata),
+        };
+        Ok(app_entry
+            .await?
+            .config
+            .await?
+            .runtime
+            .unwrap_or_default()
+            .cell())
+    }
 }
 
 #[turbo_tasks::value]
@@ -0,0 +1,71 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, Vc};
+use turbopack_binding::turbopack::core::output::OutputAsset;
+
+use crate::route::Endpoint;
+
+/// A reference from one client output asset to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct ClientGraphEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// The client output assets of an endpoint and the references between them.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default)]
+pub struct ClientGraph {
+    /// The paths of all output assets reachable from the endpoint's client
+    /// chunks.
+    pub nodes: Vec<String>,
+    pub edges: Vec<ClientGraphEdge>,
+    /// The paths of the chunks the endpoint loads directly.
+    pub chunks: Vec<String>,
+}
+
+/// Walks the client output assets of `endpoint`. If the endpoint fails to
+/// build, the graph contains what could be resolved, and the failures are
+/// reported as issues of the returned value.
+#[turbo_tasks::function]
+pub async fn client_graph(endpoint: Vc<Box<dyn Endpoint>>) -> Result<Vc<ClientGraph>> {
+    let mut graph = ClientGraph::default();
+    let Ok(client_assets) = endpoint.client_assets().await else {
+        return Ok(graph.cell());
+    };
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for &asset in client_assets.iter() {
+        let path = asset_path(asset).await?;
+        graph.chunks.push(path.clone());
+        if visited.insert(path.clone()) {
+            queue.push_back((asset, path));
+        }
+    }
+
+    while let Some((asset, path)) = queue.pop_front() {
+        graph.nodes.push(path.clone());
+        let Ok(references) = asset.references().await else {
+            continue;
+        };
+        for &reference in references.iter() {
+            let reference_path = asset_path(reference).await?;
+            graph.edges.push(ClientGraphEdge {
+                from: path.clone(),
+                to: reference_path.clone(),
+            });
+            if visited.insert(reference_path.clone()) {
+                queue.push_back((reference, reference_path));
+            }
+        }
+    }
+
+    Ok(graph.cell())
+}
+
+async fn asset_path(asset: Vc<Box<dyn OutputAsset>>) -> Result<String> {
+    Ok(asset.ident().path().await?.path.clone())
+}
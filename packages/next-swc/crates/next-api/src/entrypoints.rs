@@ -13,4 +13,39 @@ pub struct Entrypoints {
     pub pages_document_endpoint: Vc<Box<dyn Endpoint>>,
     pub pages_app_endpoint: Vc<Box<dyn Endpoint>>,
     pub pages_error_endpoint: Vc<Box<dyn Endpoint>>,
+    /// The app directory relative to the project directory, e.g. `src/app`,
+    /// when the project uses the App Router.
+    pub app_dir: Option<String>,
+}
+
+impl Entrypoints {
+    /// All endpoints of the project, including the special pages and the
+    /// middleware.
+    pub fn endpoints(&self) -> Vec<Vc<Box<dyn Endpoint>>> {
+        let mut endpoints = vec![
+            self.pages_document_endpoint,
+            self.pages_app_endpoint,
+            self.pages_error_endpoint,
+        ];
+        if let Some(middleware) = &self.middleware {
+            endpoints.push(middleware.endpoint);
+        }
+        for route in self.routes.values() {
+            match *route {
+                Route::Page {
+                    html_endpoint,
+                    data_endpoint,
+                } => endpoints.extend([html_endpoint, data_endpoint]),
+                Route::PageApi { endpoint } | Route::AppRoute { endpoint } => {
+                    endpoints.push(endpoint)
+                }
+                Route::AppPage {
+                    html_endpoint,
+                    rsc_endpoint,
+                } => endpoints.extend([html_endpoint, rsc_endpoint]),
+                Route::Conflict => {}
+            }
+        }
+        endpoints
+    }
 }
@@ -3,11 +3,18 @@
 #![feature(async_fn_in_trait)]
 
 mod app;
+pub mod client_graph;
 mod entrypoints;
+pub mod memory_fs;
 mod middleware;
+pub mod module_graph;
+pub mod output_assets;
 mod pages;
 pub mod project;
 pub mod route;
+pub mod transform;
+pub mod unsupported_modules;
+pub mod unused_dependencies;
 mod versioned_content_map;
 
 // Declare build-time information variables generated in build.rs
@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use turbo_tasks::{Completion, ValueToString, Vc};
+use turbopack_binding::turbo::tasks_fs::{
+    DirectoryContent, DirectoryEntry, File, FileContent, FileMeta, FileSystem, FileSystemPath,
+    LinkContent,
+};
+
+/// A read-only file system holding the given files in memory, e.g. to serve a
+/// project through [crate::project::ProjectFileSystems] in hermetic tests.
+/// Directories exist implicitly when they contain a file.
+#[turbo_tasks::value]
+pub struct MemoryFileSystem {
+    name: String,
+    /// The content of each file by its path relative to the root, separated
+    /// by `/`.
+    files: BTreeMap<String, String>,
+}
+
+impl MemoryFileSystem {
+    pub fn new(name: String, files: BTreeMap<String, String>) -> Vc<Self> {
+        MemoryFileSystem { name, files }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl FileSystem for MemoryFileSystem {
+    #[turbo_tasks::function]
+    async fn read(&self, fs_path: Vc<FileSystemPath>) -> Result<Vc<FileContent>> {
+        let fs_path = fs_path.await?;
+        Ok(match self.files.get(&fs_path.path) {
+            Some(content) => FileContent::Content(File::from(content.as_str())).cell(),
+            None => FileContent::NotFound.cell(),
+        })
+    }
+
+    #[turbo_tasks::function]
+    fn read_link(&self, _fs_path: Vc<FileSystemPath>) -> Vc<LinkContent> {
+        LinkContent::NotFound.cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn raw_read_dir(&self, fs_path: Vc<FileSystemPath>) -> Result<Vc<DirectoryContent>> {
+        let path = &fs_path.await?.path;
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{path}/")
+        };
+        let mut entries = IndexMap::new();
+        for file in self.files.keys() {
+            let Some(relative) = file.strip_prefix(&prefix) else {
+                continue;
+            };
+            let (name, entry) = match relative.split_once('/') {
+                Some((dir, _)) => (
+                    dir,
+                    DirectoryEntry::Directory(fs_path.join(dir.to_string())),
+                ),
+                None => (
+                    relative,
+                    DirectoryEntry::File(fs_path.join(relative.to_string())),
+                ),
+            };
+            entries.insert(name.to_string(), entry);
+        }
+        Ok(if entries.is_empty() {
+            DirectoryContent::NotFound.cell()
+        } else {
+            DirectoryContent::Entries(entries).cell()
+        })
+    }
+
+    #[turbo_tasks::function]
+    fn write(
+        &self,
+        _fs_path: Vc<FileSystemPath>,
+        _content: Vc<FileContent>,
+    ) -> Result<Vc<Completion>> {
+        bail!("the memory file system {} is read-only", self.name)
+    }
+
+    #[turbo_tasks::function]
+    fn write_link(
+        &self,
+        _fs_path: Vc<FileSystemPath>,
+        _target: Vc<LinkContent>,
+    ) -> Result<Vc<Completion>> {
+        bail!("the memory file system {} is read-only", self.name)
+    }
+
+    #[turbo_tasks::function]
+    fn metadata(&self, _fs_path: Vc<FileSystemPath>) -> Result<Vc<FileMeta>> {
+        bail!("the memory file system {} has no metadata", self.name)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for MemoryFileSystem {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<String> {
+        Vc::cell(self.name.clone())
+    }
+}
@@ -1,12 +1,12 @@
 use anyhow::{bail, Context, Result};
 use next_core::{
     all_server_paths,
-    middleware::get_middleware_module,
+    middleware::{get_middleware_module, middleware_matchers, MiddlewarePathMatchers},
     mode::NextMode,
     next_edge::entry::wrap_edge_entry,
     next_manifests::{EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2},
     next_server::{get_server_runtime_entries, ServerContextType},
-    util::parse_config_from_source,
+    util::{parse_config_from_source, NextRuntime},
 };
 use turbo_tasks::{Completion, TryJoinIterExt, Value, Vc};
 use turbopack_binding::{
@@ -52,6 +52,15 @@ impl MiddlewareEndpoint {
         .cell()
     }
 
+    /// The paths the middleware runs for.
+    #[turbo_tasks::function]
+    pub fn matchers(&self) -> Vc<MiddlewarePathMatchers> {
+        middleware_matchers(
+            parse_config_from_source(self.userland_module),
+            self.project.next_config(),
+        )
+    }
+
     #[turbo_tasks::function]
     async fn edge_files(&self) -> Result<Vc<OutputAssets>> {
         let module = get_middleware_module(
@@ -103,8 +112,6 @@ impl MiddlewareEndpoint {
     async fn output_assets(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
         let this = self.await?;
 
-        let config = parse_config_from_source(this.userland_module);
-
         let mut output_assets = self.edge_files().await?.clone_value();
 
         let node_root = this.project.node_root();
@@ -123,21 +130,16 @@ impl MiddlewareEndpoint {
                 .await?
         };
 
-        let matchers = if let Some(matchers) = config.await?.matcher.as_ref() {
-            matchers
-                .iter()
-                .map(|matcher| MiddlewareMatcher {
-                    original_source: matcher.to_string(),
-                    ..Default::default()
-                })
-                .collect()
-        } else {
-            vec![MiddlewareMatcher {
-                regexp: Some("^/.*$".to_string()),
-                original_source: "/:path*".to_string(),
+        let matchers = self
+            .matchers()
+            .await?
+            .iter()
+            .map(|matcher| MiddlewareMatcher {
+                regexp: Some(matcher.regexp.clone()),
+                original_source: matcher.original_source.clone(),
                 ..Default::default()
-            }]
-        };
+            })
+            .collect();
 
         let edge_function_definition = EdgeFunctionDefinition {
             files: files_paths_from_root,
@@ -216,4 +218,23 @@ impl Endpoint for MiddlewareEndpoint {
     fn client_changed(self: Vc<Self>) -> Vc<Completion> {
         Completion::immutable()
     }
+
+    #[turbo_tasks::function]
+    fn client_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        OutputAssets::empty()
+    }
+
+    #[turbo_tasks::function]
+    fn server_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output_assets()
+    }
+    #[turbo_tasks::function]
+    fn has_client_components(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    #[turbo_tasks::function]
+    fn runtime(self: Vc<Self>) -> Vc<NextRuntime> {
+        NextRuntime::Edge.cell()
+    }
 }
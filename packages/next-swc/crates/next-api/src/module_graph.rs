@@ -0,0 +1,137 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, Vc};
+use turbopack_binding::turbopack::core::introspect::{
+    output_asset::IntrospectableOutputAsset, Introspectable,
+};
+
+use crate::route::Endpoint;
+
+/// A module, chunk or other asset in the graph of a route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct ModuleGraphNode {
+    /// The kind of the node, as shown by the introspection routes.
+    pub ty: String,
+    pub title: String,
+    /// The titles of the route's chunks that contain the node.
+    pub chunks: Vec<String>,
+}
+
+/// An import or other reference between two nodes, given as indices into
+/// [ModuleGraph::nodes].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct ModuleGraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub label: String,
+}
+
+/// The graph the introspection routes show for the client chunks of a route.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub nodes: Vec<ModuleGraphNode>,
+    pub edges: Vec<ModuleGraphEdge>,
+}
+
+/// Walks the introspection graph of the client chunks of `endpoint`. If the
+/// endpoint fails to build, the graph is empty and the failures are reported
+/// as issues of the returned value.
+#[turbo_tasks::function]
+pub async fn route_module_graph(endpoint: Vc<Box<dyn Endpoint>>) -> Result<Vc<ModuleGraph>> {
+    let mut graph = ModuleGraph::default();
+    let Ok(client_assets) = endpoint.client_assets().await else {
+        return Ok(graph.cell());
+    };
+
+    let mut ids: IndexMap<Vc<Box<dyn Introspectable>>, usize> = IndexMap::new();
+    let mut queue = VecDeque::new();
+    let mut chunks = vec![];
+    for &asset in client_assets.iter() {
+        let node: Vc<Box<dyn Introspectable>> =
+            Vc::upcast(IntrospectableOutputAsset::new(asset).resolve().await?);
+        if ids.contains_key(&node) {
+            continue;
+        }
+        let id = add_node(&mut graph, &mut ids, node).await?;
+        chunks.push(id);
+        queue.push_back((node, id));
+    }
+
+    while let Some((node, id)) = queue.pop_front() {
+        let Ok(children) = node.children().await else {
+            continue;
+        };
+        for &(label, child) in children.iter() {
+            let child = child.resolve().await?;
+            let child_id = match ids.get(&child) {
+                Some(&child_id) => child_id,
+                None => {
+                    let child_id = add_node(&mut graph, &mut ids, child).await?;
+                    queue.push_back((child, child_id));
+                    child_id
+                }
+            };
+            graph.edges.push(ModuleGraphEdge {
+                from: id,
+                to: child_id,
+                label: label.await?.clone_value(),
+            });
+        }
+    }
+
+    for (chunk, members) in chunks.iter().zip(chunk_members(&graph, &chunks)) {
+        let title = graph.nodes[*chunk].title.clone();
+        for member in members {
+            graph.nodes[member].chunks.push(title.clone());
+        }
+    }
+
+    Ok(graph.cell())
+}
+
+async fn add_node(
+    graph: &mut ModuleGraph,
+    ids: &mut IndexMap<Vc<Box<dyn Introspectable>>, usize>,
+    node: Vc<Box<dyn Introspectable>>,
+) -> Result<usize> {
+    let id = graph.nodes.len();
+    graph.nodes.push(ModuleGraphNode {
+        ty: node.ty().await?.clone_value(),
+        title: node.title().await?.clone_value(),
+        chunks: vec![],
+    });
+    ids.insert(node, id);
+    Ok(id)
+}
+
+/// The nodes reachable from each chunk without passing through another
+/// chunk.
+fn chunk_members(graph: &ModuleGraph, chunks: &[usize]) -> Vec<Vec<usize>> {
+    let mut adjacency = vec![vec![]; graph.nodes.len()];
+    for edge in &graph.edges {
+        adjacency[edge.from].push(edge.to);
+    }
+    let chunk_set: HashSet<_> = chunks.iter().copied().collect();
+    chunks
+        .iter()
+        .map(|&chunk| {
+            let mut members = vec![];
+            let mut visited = HashSet::from([chunk]);
+            let mut stack = vec![chunk];
+            while let Some(node) = stack.pop() {
+                for &next in &adjacency[node] {
+                    if chunk_set.contains(&next) || !visited.insert(next) {
+                        continue;
+                    }
+                    members.push(next);
+                    stack.push(next);
+                }
+            }
+            members
+        })
+        .collect()
+}
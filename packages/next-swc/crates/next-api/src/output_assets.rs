@@ -0,0 +1,63 @@
+use anyhow::Result;
+use next_core::all_assets_from_entries;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, Vc};
+use turbopack_binding::turbopack::core::output::OutputAsset;
+
+use crate::route::Endpoint;
+
+/// What an output asset contains, derived from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub enum OutputAssetKind {
+    Script,
+    Stylesheet,
+    SourceMap,
+    Other,
+}
+
+impl OutputAssetKind {
+    fn from_extension(extension: Option<&str>) -> Self {
+        match extension {
+            Some("js" | "mjs" | "cjs") => OutputAssetKind::Script,
+            Some("css") => OutputAssetKind::Stylesheet,
+            Some("map") => OutputAssetKind::SourceMap,
+            _ => OutputAssetKind::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct EndpointOutputAsset {
+    /// Relative to the root of the output file system, i.e. the project path
+    /// for server assets and the client root for client assets.
+    pub path: String,
+    pub client: bool,
+    pub kind: OutputAssetKind,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct EndpointOutputAssets(Vec<EndpointOutputAsset>);
+
+/// Lists the assets the endpoint emits, including the assets referenced by
+/// its chunks, like source maps. Reading the result strongly consistent gives
+/// the set of the latest build.
+#[turbo_tasks::function]
+pub async fn endpoint_output_assets(
+    endpoint: Vc<Box<dyn Endpoint>>,
+) -> Result<Vc<EndpointOutputAssets>> {
+    let mut assets = vec![];
+    for (client, entries) in [
+        (false, endpoint.server_assets()),
+        (true, endpoint.client_assets()),
+    ] {
+        for asset in all_assets_from_entries(entries).await?.iter() {
+            let path = asset.ident().path().await?;
+            assets.push(EndpointOutputAsset {
+                path: path.path.clone(),
+                client,
+                kind: OutputAssetKind::from_extension(path.extension_ref()),
+            });
+        }
+    }
+    Ok(Vc::cell(assets))
+}
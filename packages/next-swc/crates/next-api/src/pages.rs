@@ -597,8 +597,11 @@ impl PageEndpoint {
 
         let ssr_module = module_context.process(self.source(), reference_type.clone());
 
-        let config = parse_config_from_source(ssr_module).await?;
-        let is_edge = matches!(config.runtime, NextRuntime::Edge);
+        let runtime = match *this.pages_project.project().runtime_override().await? {
+            Some(runtime) => runtime,
+            None => parse_config_from_source(ssr_module).await?.runtime,
+        };
+        let is_edge = matches!(runtime, NextRuntime::Edge);
 
         if is_edge {
             let ssr_module = create_page_ssr_entry_module(
@@ -608,7 +611,7 @@ impl PageEndpoint {
                 Vc::upcast(edge_module_context),
                 self.source(),
                 this.original_name,
-                config.runtime,
+                runtime,
             );
 
             let mut evaluatable_assets = edge_runtime_entries.await?.clone_value();
@@ -631,7 +634,7 @@ impl PageEndpoint {
                 Vc::upcast(module_context),
                 self.source(),
                 this.original_name,
-                config.runtime,
+                runtime,
             );
 
             let asset_path = get_asset_path_from_pathname(&this.pathname.await?, ".js");
@@ -959,6 +962,39 @@ impl Endpoint for PageEndpoint {
             .project()
             .client_changed(self.output().client_assets()))
     }
+
+    #[turbo_tasks::function]
+    fn client_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output().client_assets()
+    }
+
+    #[turbo_tasks::function]
+    fn server_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output().server_assets()
+    }
+    #[turbo_tasks::function]
+    fn has_client_components(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
+    #[turbo_tasks::function]
+    async fn runtime(self: Vc<Self>) -> Result<Vc<NextRuntime>> {
+        let this = self.await?;
+        if let Some(runtime) = *this.pages_project.project().runtime_override().await? {
+            return Ok(runtime.cell());
+        }
+        let reference_type = match this.ty {
+            PageEndpointType::Api => EntryReferenceSubType::PagesApi,
+            PageEndpointType::Html | PageEndpointType::Data | PageEndpointType::SsrOnly => {
+                EntryReferenceSubType::Page
+            }
+        };
+        let ssr_module = this.pages_project.ssr_module_context().process(
+            self.source(),
+            Value::new(ReferenceType::Entry(reference_type)),
+        );
+        Ok(parse_config_from_source(ssr_module).await?.runtime.cell())
+    }
 }
 
 #[turbo_tasks::value]
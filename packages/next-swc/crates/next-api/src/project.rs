@@ -1,13 +1,16 @@
-use std::{net::SocketAddr, path::MAIN_SEPARATOR};
+use std::{
+    net::SocketAddr,
+    path::{Path, MAIN_SEPARATOR},
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use indexmap::{map::Entry, IndexMap};
 use next_core::{
     all_assets_from_entries,
     app_structure::find_app_dir,
     emit_assets, get_edge_chunking_context, get_edge_compile_time_info,
     get_edge_resolve_options_context,
-    middleware::middleware_files,
+    middleware::{middleware_files, MiddlewarePathMatchers},
     mode::NextMode,
     next_client::{get_client_chunking_context, get_client_compile_time_info},
     next_config::{JsConfig, NextConfig},
@@ -16,6 +19,7 @@ use next_core::{
         get_server_module_options_context, ServerContextType,
     },
     next_telemetry::NextFeatureTelemetry,
+    util::{NextRuntime, OptionNextRuntime},
 };
 use serde::{Deserialize, Serialize};
 use turbo_tasks::{
@@ -49,6 +53,7 @@ use turbopack_binding::{
         },
         dev::DevChunkingContext,
         ecmascript::chunk::EcmascriptChunkingContext,
+        env::TryDotenvProcessEnv,
         node::execution_context::ExecutionContext,
         turbopack::{evaluate_context::node_build_environment, ModuleAssetContext},
     },
@@ -83,22 +88,53 @@ pub struct ProjectOptions {
     /// A map of environment variables to use when compiling code.
     pub env: Vec<(String, String)>,
 
+    /// Dotenv files to load, relative to `project_path`. Variables from `env`
+    /// take precedence, then the files in the given order.
+    pub env_files: Vec<String>,
+
     /// Whether to watch the filesystem for file changes.
     pub watch: bool,
 
     /// The address of the dev server.
     pub server_addr: String,
+
+    /// Whether to minify the server chunks. Defaults to `false`. Client
+    /// chunks are never minified: they are produced by a `DevChunkingContext`,
+    /// which has no minification.
+    pub minify: Option<bool>,
+
+    /// Renders every page and route with this runtime, instead of the one
+    /// selected by its `runtime` export. Middleware always uses the edge
+    /// runtime.
+    pub runtime: Option<NextRuntime>,
+
+    /// Whether the client chunks support hot module replacement. Defaults to
+    /// enabling it in development, e.g. disabling it measures its overhead.
+    pub hmr: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, TraceRawVcs, PartialEq, Eq, ValueDebugFormat)]
 pub struct Middleware {
     pub endpoint: Vc<Box<dyn Endpoint>>,
+    /// The paths the middleware runs for.
+    pub matchers: Vc<MiddlewarePathMatchers>,
+}
+
+/// File systems to use instead of the [DiskFileSystem]s created from the
+/// [ProjectOptions], e.g. in-memory ones for hermetic tests.
+#[turbo_tasks::value(shared)]
+pub struct ProjectFileSystems {
+    /// Serves the sources. Its root corresponds to `root_path`.
+    pub project_fs: Vc<Box<dyn FileSystem>>,
+    /// Receives the server output. Its root corresponds to `project_path`.
+    pub node_fs: Vc<Box<dyn FileSystem>>,
 }
 
 #[turbo_tasks::value]
 pub struct ProjectContainer {
     options_state: State<ProjectOptions>,
     versioned_content_map: Vc<VersionedContentMap>,
+    file_systems: Option<Vc<ProjectFileSystems>>,
 }
 
 #[turbo_tasks::value_impl]
@@ -108,6 +144,23 @@ impl ProjectContainer {
         ProjectContainer {
             options_state: State::new(options),
             versioned_content_map: VersionedContentMap::new(),
+            file_systems: None,
+        }
+        .cell()
+    }
+
+    /// Like [ProjectContainer::new], but reads and writes through the given
+    /// file systems. `watch` has no effect, the file systems are responsible
+    /// for invalidating reads themselves.
+    #[turbo_tasks::function]
+    pub fn new_with_file_systems(
+        options: ProjectOptions,
+        file_systems: Vc<ProjectFileSystems>,
+    ) -> Vc<Self> {
+        ProjectContainer {
+            options_state: State::new(options),
+            versioned_content_map: VersionedContentMap::new(),
+            file_systems: Some(file_systems),
         }
         .cell()
     }
@@ -133,11 +186,16 @@ impl ProjectContainer {
             next_config,
             js_config,
             env: Vc::upcast(env),
+            env_files: options.env_files.clone(),
+            minify: options.minify,
+            runtime: options.runtime,
+            hmr: options.hmr,
             browserslist_query: "last 1 Chrome versions, last 1 Firefox versions, last 1 Safari \
                                  versions, last 1 Edge versions"
                 .to_string(),
             mode: NextMode::Development,
             versioned_content_map: this.versioned_content_map,
+            file_systems: this.file_systems,
         }
         .cell())
     }
@@ -180,11 +238,26 @@ pub struct Project {
     /// A map of environment variables to use when compiling code.
     env: Vc<Box<dyn ProcessEnv>>,
 
+    /// Dotenv files whose variables are added to `env`.
+    env_files: Vec<String>,
+
+    /// Overrides whether the server chunks are minified.
+    minify: Option<bool>,
+
+    /// Overrides the runtime of all pages and routes.
+    runtime: Option<NextRuntime>,
+
+    /// Overrides whether the client chunks support hot module replacement.
+    hmr: Option<bool>,
+
     browserslist_query: String,
 
     mode: NextMode,
 
     versioned_content_map: Vc<VersionedContentMap>,
+
+    /// Replaces the project and node file systems when set.
+    file_systems: Option<Vc<ProjectFileSystems>>,
 }
 
 #[turbo_tasks::value_impl]
@@ -202,7 +275,7 @@ impl Project {
     }
 
     #[turbo_tasks::function]
-    async fn pages_project(self: Vc<Self>) -> Result<Vc<PagesProject>> {
+    pub(super) async fn pages_project(self: Vc<Self>) -> Result<Vc<PagesProject>> {
         let this = self.await?;
         Ok(PagesProject::new(self, this.mode))
     }
@@ -210,6 +283,9 @@ impl Project {
     #[turbo_tasks::function]
     async fn project_fs(self: Vc<Self>) -> Result<Vc<Box<dyn FileSystem>>> {
         let this = self.await?;
+        if let Some(file_systems) = this.file_systems {
+            return Ok(file_systems.await?.project_fs);
+        }
         let disk_fs = DiskFileSystem::new(
             PROJECT_FILESYSTEM_NAME.to_string(),
             this.root_path.to_string(),
@@ -229,6 +305,9 @@ impl Project {
     #[turbo_tasks::function]
     async fn node_fs(self: Vc<Self>) -> Result<Vc<Box<dyn FileSystem>>> {
         let this = self.await?;
+        if let Some(file_systems) = this.file_systems {
+            return Ok(file_systems.await?.node_fs);
+        }
         let disk_fs = DiskFileSystem::new("node".to_string(), this.project_path.clone());
         disk_fs.await?.start_watching_with_invalidation_reason()?;
         Ok(Vc::upcast(disk_fs))
@@ -240,8 +319,9 @@ impl Project {
     }
 
     #[turbo_tasks::function]
-    pub(super) fn node_root(self: Vc<Self>) -> Vc<FileSystemPath> {
-        self.node_fs().root().join(".next".to_string())
+    pub(super) async fn node_root(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
+        let dist_dir = self.next_config().dist_dir().await?;
+        Ok(self.node_fs().root().join(dist_dir.clone_value()))
     }
 
     #[turbo_tasks::function]
@@ -273,7 +353,15 @@ impl Project {
 
     #[turbo_tasks::function]
     pub(super) async fn env(self: Vc<Self>) -> Result<Vc<Box<dyn ProcessEnv>>> {
-        Ok(self.await?.env)
+        let this = self.await?;
+        let root = self.project_root_path();
+        // Like `load_env`, earlier files don't get overwritten by later ones.
+        let mut env = this.env;
+        for env_file in &this.env_files {
+            let path = root_relative_path(&this.root_path, &this.project_path, env_file)?;
+            env = Vc::upcast(TryDotenvProcessEnv::new(env, root.join(path)));
+        }
+        Ok(env)
     }
 
     #[turbo_tasks::function]
@@ -308,9 +396,21 @@ impl Project {
         )
     }
 
+    /// The runtime forced on all pages and routes by the options, if any.
+    #[turbo_tasks::function]
+    pub(super) fn runtime_override(&self) -> Vc<OptionNextRuntime> {
+        Vc::cell(self.runtime)
+    }
+
     #[turbo_tasks::function]
-    pub(super) fn client_compile_time_info(&self) -> Vc<CompileTimeInfo> {
-        get_client_compile_time_info(self.mode, self.browserslist_query.clone())
+    pub(super) async fn client_compile_time_info(self: Vc<Self>) -> Result<Vc<CompileTimeInfo>> {
+        let this = self.await?;
+        Ok(get_client_compile_time_info(
+            this.mode,
+            this.browserslist_query.clone(),
+            self.project_path(),
+            self.next_config(),
+        ))
     }
 
     #[turbo_tasks::function]
@@ -320,12 +420,18 @@ impl Project {
             this.mode,
             self.env(),
             self.server_addr(),
+            self.next_config(),
         ))
     }
 
     #[turbo_tasks::function]
-    pub(super) fn edge_compile_time_info(self: Vc<Self>) -> Vc<CompileTimeInfo> {
-        get_edge_compile_time_info(self.project_path(), self.server_addr())
+    pub(super) async fn edge_compile_time_info(self: Vc<Self>) -> Result<Vc<CompileTimeInfo>> {
+        let this = self.await?;
+        Ok(get_edge_compile_time_info(
+            self.project_path(),
+            self.server_addr(),
+            self.next_config().node_env(this.mode),
+        ))
     }
 
     #[turbo_tasks::function]
@@ -338,17 +444,21 @@ impl Project {
             self.client_root(),
             self.client_compile_time_info().environment(),
             this.mode,
+            this.hmr,
+            self.next_config().client_source_maps(),
         ))
     }
 
     #[turbo_tasks::function]
-    fn server_chunking_context(self: Vc<Self>) -> Vc<BuildChunkingContext> {
-        get_server_chunking_context(
+    async fn server_chunking_context(self: Vc<Self>) -> Result<Vc<BuildChunkingContext>> {
+        let this = self.await?;
+        Ok(get_server_chunking_context(
             self.project_path(),
             self.node_root(),
             self.client_root(),
             self.server_compile_time_info().environment(),
-        )
+            this.minify.unwrap_or(false),
+        ))
     }
 
     #[turbo_tasks::function]
@@ -497,9 +607,15 @@ impl Project {
         let app_project = self.app_project();
         let pages_project = self.pages_project();
 
+        let mut app_dir = None;
         if let Some(app_project) = &*app_project.await? {
             let app_routes = app_project.routes();
             routes.extend(app_routes.await?.iter().map(|(k, v)| (k.clone(), *v)));
+            app_dir = self
+                .project_path()
+                .await?
+                .get_path_to(&*app_project.app_dir().await?)
+                .map(str::to_string);
         }
 
         for (pathname, page_route) in pages_project.routes().await?.iter() {
@@ -519,8 +635,10 @@ impl Project {
         );
         let middleware = if let FindContextFileResult::Found(fs_path, _) = *middleware.await? {
             let source = Vc::upcast(FileSource::new(fs_path));
+            let endpoint = self.middleware_endpoint(source);
             Some(Middleware {
-                endpoint: Vc::upcast(self.middleware_endpoint(source)),
+                endpoint: Vc::upcast(endpoint),
+                matchers: endpoint.matchers(),
             })
         } else {
             None
@@ -532,6 +650,7 @@ impl Project {
             pages_document_endpoint: self.pages_project().document_endpoint(),
             pages_app_endpoint: self.pages_project().app_endpoint(),
             pages_error_endpoint: self.pages_project().error_endpoint(),
+            app_dir,
         }
         .cell())
     }
@@ -679,6 +798,24 @@ impl Project {
     }
 }
 
+/// Makes every read of the project's disk file systems go to disk again, as if
+/// all files had changed. Subscriptions receive fresh results afterwards.
+///
+/// This is a debugging aid for problems which seem to go away after a
+/// restart. Every file is read again, but work depending on a file whose
+/// content didn't change is not repeated. File systems passed to
+/// [ProjectContainer::new_with_file_systems] are not disk file systems and are
+/// left alone.
+pub async fn invalidate_file_systems(container: Vc<ProjectContainer>) -> Result<()> {
+    let project = container.project();
+    for fs in [project.project_fs(), project.node_fs()] {
+        if let Some(disk_fs) = Vc::try_resolve_downcast_type::<DiskFileSystem>(fs).await? {
+            disk_fs.await?.invalidate();
+        }
+    }
+    Ok(())
+}
+
 #[turbo_tasks::function]
 async fn any_output_changed(
     roots: Vc<OutputAssets>,
@@ -726,3 +863,174 @@ fn all_assets_from_entries_operation(
 ) -> Vc<OutputAssetsOperation> {
     Vc::cell(all_assets_from_entries_operation_inner(operation))
 }
+
+/// Returns the path of `file` relative to `root_path`, with relative paths
+/// resolved against `project_path`.
+fn root_relative_path(root_path: &str, project_path: &str, file: &str) -> Result<String> {
+    let path = Path::new(project_path).join(file);
+    let relative = path
+        .strip_prefix(root_path)
+        .with_context(|| format!("{file} is outside of the root path {root_path}"))?;
+    Ok(relative.to_string_lossy().replace(MAIN_SEPARATOR, "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use next_core::{next_config::NextConfig, util::NextRuntime};
+    use turbo_tasks::{TurboTasks, Vc};
+    use turbopack_binding::{
+        turbo::{tasks_env::ProcessEnv, tasks_memory::MemoryBackend},
+        turbopack::core::chunk::ChunkingContext,
+    };
+
+    use super::{root_relative_path, ProjectContainer, ProjectFileSystems, ProjectOptions};
+    use crate::{memory_fs::MemoryFileSystem, register, route::Route};
+
+    fn options(hmr: Option<bool>) -> ProjectOptions {
+        let dir = env!("CARGO_MANIFEST_DIR").to_string();
+        ProjectOptions {
+            root_path: dir.clone(),
+            project_path: dir,
+            next_config: serde_json::to_string(&NextConfig::default()).unwrap(),
+            js_config: "{}".to_string(),
+            env: vec![],
+            env_files: vec![],
+            watch: false,
+            server_addr: "127.0.0.1:3000".to_string(),
+            minify: None,
+            runtime: None,
+            hmr,
+        }
+    }
+
+    #[test]
+    fn resolves_env_files() {
+        assert_eq!(
+            root_relative_path("/repo", "/repo/apps/web", ".env.custom").unwrap(),
+            "apps/web/.env.custom"
+        );
+        assert_eq!(
+            root_relative_path("/repo", "/repo/apps/web", "/repo/.env").unwrap(),
+            ".env"
+        );
+        assert!(root_relative_path("/repo", "/repo/apps/web", "/other/.env").is_err());
+    }
+
+    #[tokio::test]
+    async fn env_overrides_env_files() {
+        register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let root_path = env!("CARGO_MANIFEST_DIR").to_string();
+        let options = ProjectOptions {
+            project_path: format!("{root_path}/tests/fixtures/env-files"),
+            root_path,
+            env: vec![("OVERRIDDEN".to_string(), "options".to_string())],
+            env_files: vec![".env.first".to_string(), ".env.second".to_string()],
+            ..options(None)
+        };
+        let env = tt
+            .run_once(async move {
+                let env = ProjectContainer::new(options).project().env();
+                Ok(env.read_all().await?.clone_value())
+            })
+            .await
+            .unwrap();
+
+        // Explicit variables win over the files, earlier files over later ones.
+        assert_eq!(env.get("OVERRIDDEN").map(String::as_str), Some("options"));
+        assert_eq!(env.get("FROM_FILE").map(String::as_str), Some("first"));
+        assert_eq!(env.get("SECOND").map(String::as_str), Some("second"));
+    }
+
+    #[tokio::test]
+    async fn runtime_option_overrides_the_route_config() {
+        register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let api_runtime = |runtime| {
+            tt.run_once(async move {
+                let root_path = env!("CARGO_MANIFEST_DIR").to_string();
+                let options = ProjectOptions {
+                    project_path: format!("{root_path}/tests/fixtures/runtime"),
+                    root_path,
+                    runtime,
+                    ..options(None)
+                };
+                let entrypoints = ProjectContainer::new(options).entrypoints().await?;
+                let Some(Route::PageApi { endpoint }) = entrypoints.routes.get("/api/hello") else {
+                    anyhow::bail!("expected the /api/hello route");
+                };
+                Ok(*endpoint.runtime().await?)
+            })
+        };
+
+        // The route exports `runtime: 'edge'`.
+        assert!(matches!(
+            api_runtime(None).await.unwrap(),
+            NextRuntime::Edge
+        ));
+        assert!(matches!(
+            api_runtime(Some(NextRuntime::NodeJs)).await.unwrap(),
+            NextRuntime::NodeJs
+        ));
+    }
+
+    #[tokio::test]
+    async fn discovers_routes_from_injected_file_systems() {
+        register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let routes = tt
+            .run_once(async move {
+                // The project is a directory of the root, which only exists
+                // in memory.
+                let files = [
+                    ("web/pages/index.js", "export default () => null"),
+                    ("web/pages/api/hello.js", "export default () => {}"),
+                    ("web/package.json", "{}"),
+                ]
+                .into_iter()
+                .map(|(path, content)| (path.to_string(), content.to_string()))
+                .collect();
+                let file_systems = ProjectFileSystems {
+                    project_fs: Vc::upcast(MemoryFileSystem::new("project".to_string(), files)),
+                    node_fs: Vc::upcast(MemoryFileSystem::new(
+                        "node".to_string(),
+                        Default::default(),
+                    )),
+                }
+                .cell();
+                let options = ProjectOptions {
+                    root_path: "/repo".to_string(),
+                    project_path: "/repo/web".to_string(),
+                    ..options(None)
+                };
+                let entrypoints = ProjectContainer::new_with_file_systems(options, file_systems)
+                    .entrypoints()
+                    .await?;
+                Ok(entrypoints.routes.keys().cloned().collect::<Vec<_>>())
+            })
+            .await
+            .unwrap();
+
+        assert!(routes.contains(&"/".to_string()), "{routes:?}");
+        assert!(routes.contains(&"/api/hello".to_string()), "{routes:?}");
+    }
+
+    #[tokio::test]
+    async fn hmr_can_be_disabled() {
+        register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let hmr_enabled = |hmr| {
+            tt.run_once(async move {
+                let chunking_context = ProjectContainer::new(options(hmr))
+                    .project()
+                    .client_chunking_context();
+                Ok(*Vc::upcast::<Box<dyn ChunkingContext>>(chunking_context)
+                    .is_hot_module_replacement_enabled()
+                    .await?)
+            })
+        };
+
+        assert!(hmr_enabled(None).await.unwrap());
+        assert!(!hmr_enabled(Some(false)).await.unwrap());
+    }
+}
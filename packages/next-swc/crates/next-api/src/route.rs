@@ -1,5 +1,7 @@
 use indexmap::IndexMap;
+use next_core::util::NextRuntime;
 use turbo_tasks::{Completion, Vc};
+use turbopack_binding::turbopack::core::output::OutputAssets;
 
 #[turbo_tasks::value(shared)]
 #[derive(Copy, Clone, Debug)]
@@ -26,6 +28,16 @@ pub trait Endpoint {
     fn write_to_disk(self: Vc<Self>) -> Vc<WrittenEndpoint>;
     fn server_changed(self: Vc<Self>) -> Vc<Completion>;
     fn client_changed(self: Vc<Self>) -> Vc<Completion>;
+    /// The chunks loaded by the browser for this endpoint.
+    fn client_assets(self: Vc<Self>) -> Vc<OutputAssets>;
+    /// The chunks the server loads for this endpoint.
+    fn server_assets(self: Vc<Self>) -> Vc<OutputAssets>;
+    /// Whether any module of the endpoint is a `"use client"` boundary. Only
+    /// walks the server module graph, without chunking.
+    fn has_client_components(self: Vc<Self>) -> Vc<bool>;
+    /// The runtime the endpoint is rendered with: the one forced by the
+    /// project options, otherwise the one selected by its `runtime` export.
+    fn runtime(self: Vc<Self>) -> Vc<NextRuntime>;
 }
 
 #[turbo_tasks::value(shared)]
@@ -0,0 +1,58 @@
+use anyhow::{bail, Result};
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::turbopack::{
+    core::{
+        context::AssetContext,
+        file_source::FileSource,
+        reference_type::{EntryReferenceSubType, ReferenceType},
+        source_map::GenerateSourceMap,
+    },
+    ecmascript::chunk::{EcmascriptChunkItem, EcmascriptChunkPlaceable},
+};
+
+use crate::project::Project;
+
+/// The result of compiling a single module.
+#[turbo_tasks::value(shared)]
+#[derive(Debug)]
+pub struct TransformedModule {
+    /// The compiled code of the module, as it would appear in a client chunk.
+    pub code: String,
+    /// The source map of `code`, serialized to JSON.
+    pub source_map: Option<String>,
+}
+
+/// Compiles the file at `path`, relative to the project path, with the same
+/// transforms as the pages of the project are compiled for the client. The
+/// module is not chunked, and its references are not followed. Errors in the
+/// module are reported as issues of the returned value.
+#[turbo_tasks::function]
+pub async fn transform_module(project: Vc<Project>, path: String) -> Result<Vc<TransformedModule>> {
+    let source = FileSource::new(project.project_path().join(path.clone()));
+    let module = project.pages_project().client_module_context().process(
+        Vc::upcast(source),
+        Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
+    );
+    let Some(module) =
+        Vc::try_resolve_sidecast::<Box<dyn EcmascriptChunkPlaceable>>(module).await?
+    else {
+        bail!("{path} is not an ecmascript module");
+    };
+
+    let content = module
+        .as_chunk_item(project.client_chunking_context())
+        .content()
+        .await?;
+    let source_map = match content.source_map {
+        Some(source_map) => match &*source_map.generate_source_map().await? {
+            Some(source_map) => Some(source_map.to_rope().await?.to_str()?.to_string()),
+            None => None,
+        },
+        None => None,
+    };
+    Ok(TransformedModule {
+        code: content.inner_code.to_str()?.to_string(),
+        source_map,
+    }
+    .cell())
+}
@@ -0,0 +1,64 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, ValueToString, Vc};
+use turbopack_binding::turbopack::core::issue::{
+    unsupported_module::UnsupportedModuleIssue, IssueDescriptionExt,
+};
+
+use crate::project::Project;
+
+/// A module which was reported as unsupported, with the directories it is
+/// resolved from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct UnsupportedModule {
+    /// The package, followed by the subpath if only that is unsupported.
+    pub module: String,
+    pub importers: Vec<String>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct UnsupportedModules(Vec<UnsupportedModule>);
+
+/// Resolves the server and client assets of every endpoint of the project
+/// and collects the unsupported module issues the resolver reported on the
+/// way, e.g. for Node.js-only builtins imported by client code when
+/// `experimental.turbo.reportNodeBuiltins` is set.
+#[turbo_tasks::function]
+pub async fn unsupported_modules(project: Vc<Project>) -> Result<Vc<UnsupportedModules>> {
+    let endpoints = project.entrypoints().await?.endpoints();
+
+    let mut modules: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for endpoint in endpoints {
+        for assets in [endpoint.server_assets(), endpoint.client_assets()] {
+            let issues = assets.peek_issues_with_path().await?.await?;
+            for (issue, _) in issues.iter_with_shortest_path() {
+                let Some(issue) =
+                    Vc::try_resolve_downcast_type::<UnsupportedModuleIssue>(issue).await?
+                else {
+                    continue;
+                };
+                let issue = issue.await?;
+                let module = match &issue.package_path {
+                    Some(path) => format!("{}{}", issue.package, path),
+                    None => issue.package.clone(),
+                };
+                modules
+                    .entry(module)
+                    .or_default()
+                    .insert(issue.file_path.to_string().await?.clone_value());
+            }
+        }
+    }
+
+    Ok(Vc::cell(
+        modules
+            .into_iter()
+            .map(|(module, importers)| UnsupportedModule {
+                module,
+                importers: importers.into_iter().collect(),
+            })
+            .collect(),
+    ))
+}
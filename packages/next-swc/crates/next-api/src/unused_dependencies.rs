@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+use anyhow::Result;
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::FileJsonContent,
+    turbopack::core::{
+        diagnostics::{Diagnostic, DiagnosticExt, DiagnosticPayload},
+        introspect::{output_asset::IntrospectableOutputAsset, Introspectable},
+    },
+};
+
+use crate::project::Project;
+
+/// Packages from the `dependencies` of the project's `package.json` that no
+/// route imports, with their declared version ranges.
+#[turbo_tasks::value(transparent)]
+pub struct UnusedDependencies(BTreeMap<String, String>);
+
+/// Walks the graphs of the server and client assets of every endpoint of the
+/// project and reports the `dependencies` of the project's `package.json`
+/// that none of them import. Dynamic imports are followed through their async
+/// chunks, and the peer dependencies of imported packages count as used.
+/// This builds every route, so it's only done when requested.
+#[turbo_tasks::function]
+pub async fn unused_dependencies(project: Vc<Project>) -> Result<Vc<UnusedDependencies>> {
+    let project_path = project.project_path();
+    let FileJsonContent::Content(package_json) = &*project_path
+        .join("package.json".to_string())
+        .read_json()
+        .await?
+    else {
+        return Ok(Vc::cell(BTreeMap::new()));
+    };
+    let mut declared = dependency_map(&package_json["dependencies"]);
+    for peer in dependency_map(&package_json["peerDependencies"]).keys() {
+        declared.remove(peer);
+    }
+    if declared.is_empty() {
+        return Ok(Vc::cell(declared));
+    }
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for endpoint in project.entrypoints().await?.endpoints() {
+        for assets in [endpoint.server_assets(), endpoint.client_assets()] {
+            // Routes that fail to build are reported elsewhere.
+            let Ok(assets) = assets.await else {
+                continue;
+            };
+            for &asset in assets.iter() {
+                let node: Vc<Box<dyn Introspectable>> =
+                    Vc::upcast(IntrospectableOutputAsset::new(asset).resolve().await?);
+                if visited.insert(node) {
+                    queue.push_back(node);
+                }
+            }
+        }
+    }
+
+    let mut used = BTreeSet::new();
+    while let Some(node) = queue.pop_front() {
+        if let Some(package) = package_name(&node.title().await?) {
+            used.insert(package.to_string());
+        }
+        let Ok(children) = node.children().await else {
+            continue;
+        };
+        for &(_, child) in children.iter() {
+            let child = child.resolve().await?;
+            if visited.insert(child) {
+                queue.push_back(child);
+            }
+        }
+    }
+
+    let mut peers = BTreeSet::new();
+    for package in &used {
+        let package_json = project_path
+            .join(format!("node_modules/{package}/package.json"))
+            .read_json()
+            .await?;
+        if let FileJsonContent::Content(package_json) = &*package_json {
+            peers.extend(dependency_map(&package_json["peerDependencies"]).into_keys());
+        }
+    }
+
+    declared.retain(|package, _| !used.contains(package) && !peers.contains(package));
+    if !declared.is_empty() {
+        UnusedDependenciesDiagnostic {
+            packages: declared.clone(),
+        }
+        .cell()
+        .emit();
+    }
+    Ok(Vc::cell(declared))
+}
+
+fn dependency_map(dependencies: &serde_json::Value) -> BTreeMap<String, String> {
+    dependencies
+        .as_object()
+        .map(|dependencies| {
+            dependencies
+                .iter()
+                .map(|(name, version)| {
+                    (
+                        name.clone(),
+                        version.as_str().unwrap_or_default().to_string(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The name of the package a module belongs to, taken from the innermost
+/// `node_modules` directory in its path, e.g. `@scope/pkg` for
+/// `[project]/node_modules/.pnpm/x/node_modules/@scope/pkg/index.js`.
+fn package_name(path: &str) -> Option<&str> {
+    let (_, rest) = path.rsplit_once("node_modules/")?;
+    let end = if rest.starts_with('@') {
+        let scope_end = rest.find('/')?;
+        rest[scope_end + 1..]
+            .find('/')
+            .map_or(rest.len(), |i| scope_end + 1 + i)
+    } else {
+        rest.find('/').unwrap_or(rest.len())
+    };
+    let name = rest[..end].split_whitespace().next()?;
+    (!name.is_empty() && !name.starts_with('.')).then_some(name)
+}
+
+/// Lists the declared dependencies that no route imports.
+#[turbo_tasks::value(shared)]
+pub struct UnusedDependenciesDiagnostic {
+    pub packages: BTreeMap<String, String>,
+}
+
+#[turbo_tasks::value_impl]
+impl Diagnostic for UnusedDependenciesDiagnostic {
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("UnusedDependencies".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn name(&self) -> Vc<String> {
+        Vc::cell("unused-dependencies".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn payload(&self) -> Vc<DiagnosticPayload> {
+        Vc::cell(self.packages.clone().into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::package_name;
+
+    #[test]
+    fn package_names() {
+        assert_eq!(
+            package_name("[project]/node_modules/react/index.js (ecmascript)"),
+            Some("react")
+        );
+        assert_eq!(
+            package_name("[project]/node_modules/@scope/pkg/dist/a.js"),
+            Some("@scope/pkg")
+        );
+        assert_eq!(
+            package_name("[project]/node_modules/.pnpm/lodash@4.17.21/node_modules/lodash/map.js"),
+            Some("lodash")
+        );
+        assert_eq!(package_name("[project]/pages/index.js"), None);
+    }
+}
@@ -25,6 +25,14 @@ pub struct BuildOptions {
     /// Whether to compute full stats.
     pub full_stats: bool,
 
+    /// Issue categories which fail the build, even if their issues are only
+    /// warnings.
+    pub error_on: Vec<String>,
+
+    /// Issues of this severity or worse fail the build, e.g. warnings for
+    /// strict CI builds. Only fatal issues fail the build by default.
+    pub fail_on: Option<IssueSeverity>,
+
     /// The Next.js build context.
     pub build_context: Option<BuildContext>,
 }
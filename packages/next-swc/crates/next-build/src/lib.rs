@@ -54,7 +54,8 @@ fn setup_tracing() {
 
     let subscriber = subscriber.with(EnvFilter::from_default_env());
 
-    subscriber.init();
+    // Builds in the same process, e.g. in tests, share the first subscriber.
+    let _ = subscriber.try_init();
 }
 
 pub fn register() {
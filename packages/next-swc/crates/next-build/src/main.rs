@@ -6,7 +6,9 @@ use std::path::PathBuf;
 use anyhow::Result;
 use clap::Parser;
 use next_build::BuildOptions;
-use turbopack_binding::turbopack::cli_utils::issue::IssueSeverityCliOption;
+use turbopack_binding::turbopack::{
+    cli_utils::issue::IssueSeverityCliOption, core::issue::IssueSeverity,
+};
 
 #[global_allocator]
 static ALLOC: turbopack_binding::turbo::malloc::TurboMalloc =
@@ -46,6 +48,20 @@ pub struct BuildCliArgs {
     #[clap(long)]
     pub full_stats: bool,
 
+    /// Fail the build on issues of this category, even if they are only
+    /// warnings. Can be passed multiple times.
+    #[clap(long)]
+    pub error_on: Vec<String>,
+
+    /// Fail the build on issues of this severity or worse, e.g. `error`. Only
+    /// fatal issues fail the build by default.
+    #[clap(long)]
+    pub fail_on: Option<IssueSeverityCliOption>,
+
+    /// Fail the build on any warning. Shorthand for `--fail-on warning`.
+    #[clap(long)]
+    pub fail_on_warning: bool,
+
     /// Enable experimental garbage collection with the provided memory limit in
     /// MB.
     #[clap(long)]
@@ -99,6 +115,12 @@ async fn main_inner() -> Result<()> {
         show_all: args.show_all,
         log_detail: args.log_detail,
         full_stats: args.full_stats,
+        error_on: args.error_on,
+        fail_on: if args.fail_on_warning {
+            Some(IssueSeverity::Warning)
+        } else {
+            args.fail_on.map(|s| s.0)
+        },
         build_context: None,
     })
     .await
@@ -7,6 +7,7 @@ use std::{
 use anyhow::{Context, Result};
 use dunce::canonicalize;
 use next_core::{
+    issue_reporter::ErrorOnIssueReporter,
     mode::NextMode,
     next_app::get_app_client_references_chunks,
     next_client::{get_client_chunking_context, get_client_compile_time_info},
@@ -54,7 +55,7 @@ use crate::{
 };
 
 // TODO this should be Error, but we need to fix the errors happening first
-static MIN_FAILING_SEVERITY: IssueSeverity = IssueSeverity::Fatal;
+static DEFAULT_MIN_FAILING_SEVERITY: IssueSeverity = IssueSeverity::Fatal;
 
 #[turbo_tasks::function]
 pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Result<Vc<Completion>> {
@@ -90,16 +91,22 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         log_level: options.log_level.unwrap_or(IssueSeverity::Warning),
     };
 
-    let issue_reporter: Vc<Box<dyn IssueReporter>> =
-        Vc::upcast(ConsoleUi::new(TransientInstance::new(log_options)));
-    let node_fs = node_fs(project_root.clone(), issue_reporter);
+    let issue_reporter: Vc<Box<dyn IssueReporter>> = Vc::upcast(ErrorOnIssueReporter::new(
+        Vc::upcast(ConsoleUi::new(TransientInstance::new(log_options))),
+        Vc::cell(options.error_on.clone()),
+    ));
+    let min_failing_severity = options
+        .fail_on
+        .unwrap_or(DEFAULT_MIN_FAILING_SEVERITY)
+        .cell();
+    let node_fs = node_fs(project_root.clone(), issue_reporter, min_failing_severity);
     let node_root = node_fs.root().join(".next".to_string());
-    let client_fs = client_fs(project_root.clone(), issue_reporter);
+    let client_fs = client_fs(project_root.clone(), issue_reporter, min_failing_severity);
     let client_root = client_fs.root().join(".next".to_string());
     // TODO(alexkirsz) This should accept a URL for assetPrefix.
     // let client_public_fs = VirtualFileSystem::new();
     // let client_public_root = client_public_fs.root();
-    let workspace_fs = workspace_fs(workspace_root.clone(), issue_reporter);
+    let workspace_fs = workspace_fs(workspace_root.clone(), issue_reporter, min_failing_severity);
     let project_relative = project_root.strip_prefix(&workspace_root).unwrap();
     let project_relative = project_relative
         .strip_prefix(MAIN_SEPARATOR)
@@ -127,8 +134,10 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let next_config = load_next_config(execution_context.with_layer("next_config".to_string()));
 
     let mode = NextMode::Build;
-    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
-    let server_compile_time_info = get_server_compile_time_info(mode, env, ServerAddr::empty());
+    let client_compile_time_info =
+        get_client_compile_time_info(mode, browserslist_query, project_root, next_config);
+    let server_compile_time_info =
+        get_server_compile_time_info(mode, env, ServerAddr::empty(), next_config);
 
     // TODO(alexkirsz) Pages should build their own routes, outside of a FS.
     let next_router_fs = Vc::upcast::<Box<dyn FileSystem>>(VirtualFileSystem::new());
@@ -155,7 +164,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     handle_issues(
         page_entries,
         issue_reporter,
-        MIN_FAILING_SEVERITY.cell(),
+        min_failing_severity,
         None,
         None,
     )
@@ -163,7 +172,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     handle_issues(
         app_entries,
         issue_reporter,
-        MIN_FAILING_SEVERITY.cell(),
+        min_failing_severity,
         None,
         None,
     )
@@ -241,6 +250,8 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         client_root,
         client_compile_time_info.environment(),
         mode,
+        None,
+        next_config.client_source_maps(),
     );
 
     let server_chunking_context = get_server_chunking_context(
@@ -248,6 +259,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         node_root,
         client_root,
         server_compile_time_info.environment(),
+        false,
     );
     // TODO(alexkirsz) This should be the same chunking context. The layer should
     // be applied on the AssetContext level instead.
@@ -444,23 +456,22 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         .await?,
     );
 
-    Ok(Completions::all(completions))
+    // Modules are transformed while their chunks are emitted, so e.g.
+    // transform warnings are only reported by the emit tasks.
+    let completion = Completions::all(completions);
+    handle_issues(completion, issue_reporter, min_failing_severity, None, None).await?;
+
+    Ok(completion)
 }
 
 #[turbo_tasks::function]
 async fn workspace_fs(
     workspace_root: String,
     issue_reporter: Vc<Box<dyn IssueReporter>>,
+    min_failing_severity: Vc<IssueSeverity>,
 ) -> Result<Vc<Box<dyn FileSystem>>> {
     let disk_fs = DiskFileSystem::new("workspace".to_string(), workspace_root.to_string());
-    handle_issues(
-        disk_fs,
-        issue_reporter,
-        MIN_FAILING_SEVERITY.cell(),
-        None,
-        None,
-    )
-    .await?;
+    handle_issues(disk_fs, issue_reporter, min_failing_severity, None, None).await?;
     Ok(Vc::upcast(disk_fs))
 }
 
@@ -468,16 +479,10 @@ async fn workspace_fs(
 async fn node_fs(
     node_root: String,
     issue_reporter: Vc<Box<dyn IssueReporter>>,
+    min_failing_severity: Vc<IssueSeverity>,
 ) -> Result<Vc<Box<dyn FileSystem>>> {
     let disk_fs = DiskFileSystem::new("node".to_string(), node_root.to_string());
-    handle_issues(
-        disk_fs,
-        issue_reporter,
-        MIN_FAILING_SEVERITY.cell(),
-        None,
-        None,
-    )
-    .await?;
+    handle_issues(disk_fs, issue_reporter, min_failing_severity, None, None).await?;
     Ok(Vc::upcast(disk_fs))
 }
 
@@ -485,16 +490,10 @@ async fn node_fs(
 async fn client_fs(
     client_root: String,
     issue_reporter: Vc<Box<dyn IssueReporter>>,
+    min_failing_severity: Vc<IssueSeverity>,
 ) -> Result<Vc<Box<dyn FileSystem>>> {
     let disk_fs = DiskFileSystem::new("client".to_string(), client_root.to_string());
-    handle_issues(
-        disk_fs,
-        issue_reporter,
-        MIN_FAILING_SEVERITY.cell(),
-        None,
-        None,
-    )
-    .await?;
+    handle_issues(disk_fs, issue_reporter, min_failing_severity, None, None).await?;
     Ok(Vc::upcast(disk_fs))
 }
 
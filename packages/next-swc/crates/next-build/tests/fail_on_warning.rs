@@ -0,0 +1,75 @@
+use std::{fs, path::Path};
+
+use next_build::BuildOptions;
+use next_core::turbopack::core::issue::IssueSeverity;
+
+/// Copies the fixture into a temporary directory next to it, so the build
+/// output doesn't end up in the repository while `next` still resolves from
+/// the workspace.
+fn copy_fixture(name: &str) -> tempfile::TempDir {
+    let tests_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let dir = tempfile::tempdir_in(&tests_dir).unwrap();
+    copy_dir(&tests_dir.join("fixtures").join(name), dir.path());
+    dir
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+fn build_options(dir: &Path, fail_on: Option<IssueSeverity>) -> BuildOptions {
+    let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../..");
+    BuildOptions {
+        root: Some(workspace_root),
+        dir: Some(dir.to_path_buf()),
+        memory_limit: None,
+        log_level: None,
+        show_all: false,
+        log_detail: false,
+        full_stats: false,
+        error_on: vec![],
+        fail_on,
+        build_context: None,
+    }
+}
+
+#[tokio::test]
+async fn transform_warning_fails_build_with_fail_on_warning() {
+    let dir = copy_fixture("transform-warning");
+
+    // The warning is reported while the client chunks are emitted, after the
+    // entries were checked for issues.
+    next_build::build(build_options(dir.path(), None))
+        .await
+        .expect("warnings don't fail the build by default");
+    next_build::build(build_options(dir.path(), Some(IssueSeverity::Warning)))
+        .await
+        .expect_err("the transform warning must fail the build");
+}
+
+#[tokio::test]
+async fn error_on_category_fails_build() {
+    let dir = copy_fixture("transform-warning");
+
+    next_build::build(BuildOptions {
+        error_on: vec!["resolve".to_string()],
+        ..build_options(dir.path(), None)
+    })
+    .await
+    .expect("issues of other categories don't fail the build");
+    next_build::build(BuildOptions {
+        error_on: vec!["code generation".to_string()],
+        ..build_options(dir.path(), None)
+    })
+    .await
+    .expect_err("the transform warning's category must fail the build");
+}
@@ -0,0 +1,6 @@
+// `text` isn't a supported import attribute type, which is a warning.
+import data from '../data.json' with { type: 'text' }
+
+export default function Page() {
+  return <div>{data.message}</div>
+}
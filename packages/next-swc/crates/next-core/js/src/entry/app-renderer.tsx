@@ -129,7 +129,7 @@ async function runOperation(renderData: RenderData) {
     clientReferenceManifest,
     runtime: 'nodejs',
     serverComponents: true,
-    assetPrefix: '',
+    assetPrefix: renderData.data?.assetPrefix ?? '',
     pageConfig: {},
     reactLoadableManifest: {},
     nextConfigOutput: renderData.data?.nextConfigOutput,
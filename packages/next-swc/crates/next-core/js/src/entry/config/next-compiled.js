@@ -0,0 +1,17 @@
+// Loads a config that Next.js can't load itself, e.g. `next.config.ts`, from
+// the module compiled by Turbopack.
+import * as configModule from 'CONFIG'
+import { defaultConfig } from 'next/dist/server/config-shared'
+import { PHASE_DEVELOPMENT_SERVER } from 'next/dist/shared/lib/constants'
+import loadNextConfig from './next'
+
+const loadCompiledNextConfig = async (silent) => {
+  let userConfig = configModule.default ?? configModule
+  if (typeof userConfig === 'function') {
+    userConfig = await userConfig(PHASE_DEVELOPMENT_SERVER, { defaultConfig })
+  }
+
+  return loadNextConfig(silent, userConfig)
+}
+
+export { loadCompiledNextConfig as default }
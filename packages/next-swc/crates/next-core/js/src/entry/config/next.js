@@ -3,14 +3,11 @@ import loadCustomRoutes from 'next/dist/lib/load-custom-routes'
 import { PHASE_DEVELOPMENT_SERVER } from 'next/dist/shared/lib/constants'
 import assert from 'node:assert'
 
-const loadNextConfig = async (silent) => {
-  const nextConfig = await loadConfig(
-    PHASE_DEVELOPMENT_SERVER,
-    process.cwd(),
-    undefined,
-    undefined,
-    silent
-  )
+const loadNextConfig = async (silent, customConfig) => {
+  const nextConfig = await loadConfig(PHASE_DEVELOPMENT_SERVER, process.cwd(), {
+    customConfig,
+    silent,
+  })
 
   nextConfig.generateBuildId = await nextConfig.generateBuildId?.()
 
@@ -27,6 +27,20 @@ initializeHMR({
   assetPrefix: '',
 })
 
+// Set by the branding module, which runs right before this entry.
+const branding: { label: string | null; favicon: string | null } =
+  (globalThis as any).__NEXT_FALLBACK_BRANDING__ ?? {
+    label: null,
+    favicon: null,
+  }
+
+if (branding.favicon) {
+  const link = document.createElement('link')
+  link.rel = 'icon'
+  link.href = branding.favicon
+  document.head.appendChild(link)
+}
+
 const el = document.getElementById('__next')!
 
 const innerHtml = {
@@ -35,6 +49,9 @@ const innerHtml = {
 
 createRoot(el).render(
   <ReactDevOverlay>
+    {branding.label ? (
+      <header data-nextjs-fallback-label="">{branding.label}</header>
+    ) : null}
     <div dangerouslySetInnerHTML={innerHtml}></div>
   </ReactDevOverlay>
 )
@@ -12,13 +12,14 @@ import type { RenderOpts } from 'next/dist/server/render'
 import { getRedirectStatus } from 'next/dist/lib/redirect-status'
 import { PERMANENT_REDIRECT_STATUS } from 'next/dist/shared/lib/constants'
 import { buildStaticPaths } from 'next/dist/build/utils'
+import { setConfig } from 'next/dist/shared/lib/runtime-config.external'
 import type { BuildManifest } from 'next/dist/server/get-page-files'
 import type { ReactLoadableManifest } from 'next/dist/server/load-components'
 
 import { headersFromEntries, initProxiedHeaders } from './headers'
 import { createServerResponse } from './http'
 import type { Ipc } from '@vercel/turbopack-node/ipc/index'
-import type { RenderData } from 'types/turbopack'
+import type { RenderData, RuntimeConfig } from 'types/turbopack'
 import type { ChunkGroup } from 'types/next'
 import type { NextComponentType } from 'next/types'
 import { parse } from 'node:querystring'
@@ -126,6 +127,19 @@ export default function startHandler({
       }
     }
 
+    // Depending on `experimental.turbo.runtimeConfig`, the runtime config is
+    // either sent along with the request or compiled into the bundle.
+    let runtimeConfig: RuntimeConfig = {
+      publicRuntimeConfig: {},
+      serverRuntimeConfig: {},
+    }
+    if (renderData.data?.runtimeConfig) {
+      runtimeConfig = renderData.data.runtimeConfig
+    } else if (process.env.__NEXT_RUNTIME_CONFIG) {
+      runtimeConfig = process.env.__NEXT_RUNTIME_CONFIG as any
+    }
+    setConfig(runtimeConfig)
+
     // TODO(alexkirsz) This is missing *a lot* of data, but it's enough to get a
     // basic render working.
 
@@ -173,8 +187,8 @@ export default function startHandler({
 
       /* RenderOptsPartial */
       isDataReq,
-      runtimeConfig: {},
-      assetPrefix: '',
+      runtimeConfig: runtimeConfig.publicRuntimeConfig,
+      assetPrefix: renderData.data?.assetPrefix ?? '',
       canonicalBase: '',
       previewProps: {
         previewModeId: '',
@@ -182,7 +196,7 @@ export default function startHandler({
         previewModeSigningKey: '',
       },
       params: renderData.params,
-      basePath: '',
+      basePath: renderData.data?.basePath ?? '',
       // TODO(WEB-583) this isn't correct, instead it should set `dev: true`
       nextExport: true,
       nextConfigOutput: renderData.data?.nextConfigOutput,
@@ -60,7 +60,10 @@ export function getOriginalStackFrame(
         clearTimeout(tm)
       })
     if (!res.ok || res.status === 204) {
-      return Promise.reject(new Error(await res.text()))
+      // Turbopack explains why a frame can't be traced, e.g. because source
+      // maps are disabled, in a header.
+      const reason = res.headers.get('x-nextjs-original-frame-unavailable')
+      return Promise.reject(new Error(reason ?? (await res.text())))
     }
 
     const body: OriginalStackFrameResponse = await res.json()
@@ -1,6 +1,11 @@
 import type { ServerInfo } from '@vercel/turbopack-next/internal/server'
 import type { RenderOptsPartial } from 'next/dist/server/render'
 
+export type RuntimeConfig = {
+  publicRuntimeConfig: Record<string, any>
+  serverRuntimeConfig: Record<string, any>
+}
+
 export type RenderData = {
   params: Record<string, string | string[]>
   method: string
@@ -15,5 +20,8 @@ export type RenderData = {
     allowedRevalidateHeaderKeys?: string[]
     fetchCacheKeyPrefix?: string
     isrMemoryCacheSize?: number
+    runtimeConfig?: RuntimeConfig
+    basePath?: string
+    assetPrefix?: string
   }
 }
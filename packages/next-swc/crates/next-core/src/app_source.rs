@@ -57,7 +57,8 @@ use crate::{
     bootstrap::{route_bootstrap, BootstrapConfig},
     embed_js::{next_asset, next_js_file_path},
     env::env_for_js,
-    fallback::get_fallback_page,
+    fallback::{get_fallback_page, FallbackBranding},
+    instrumentation::instrumentation_runtime_entries,
     loader_tree::{LoaderTreeModule, ServerComponentTransition},
     mode::NextMode,
     next_app::{metadata::route::get_app_metadata_route_source, AppPage, AppPath, PathSegment},
@@ -182,7 +183,12 @@ fn next_ssr_client_module_transition(
                 next_config,
                 execution_context,
             ),
-            ssr_environment: get_server_compile_time_info(mode, process_env, server_addr),
+            ssr_environment: get_server_compile_time_info(
+                mode,
+                process_env,
+                server_addr,
+                next_config,
+            ),
         }
         .cell(),
     )
@@ -214,7 +220,11 @@ fn next_edge_ssr_client_module_transition(
                 next_config,
                 execution_context,
             ),
-            ssr_environment: get_edge_compile_time_info(project_path, server_addr),
+            ssr_environment: get_edge_compile_time_info(
+                project_path,
+                server_addr,
+                next_config.node_env(mode),
+            ),
         }
         .cell(),
     )
@@ -239,7 +249,8 @@ fn next_server_component_transition(
             ecmascript_client_reference_transition_name,
         ),
     });
-    let rsc_compile_time_info = get_server_compile_time_info(mode, process_env, server_addr);
+    let rsc_compile_time_info =
+        get_server_compile_time_info(mode, process_env, server_addr, next_config);
     let rsc_resolve_options_context =
         get_server_resolve_options_context(project_path, ty, mode, next_config, execution_context);
     let rsc_module_options_context =
@@ -268,7 +279,8 @@ fn next_route_transition(
     let mode = NextMode::DevServer;
     let server_ty = Value::new(ServerContextType::AppRoute { app_dir });
 
-    let server_compile_time_info = get_server_compile_time_info(mode, process_env, server_addr);
+    let server_compile_time_info =
+        get_server_compile_time_info(mode, process_env, server_addr, next_config);
 
     let server_resolve_options_context = get_server_resolve_options_context(
         project_path,
@@ -305,7 +317,8 @@ fn next_edge_server_component_transition(
             ecmascript_client_reference_transition_name,
         ),
     });
-    let rsc_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let rsc_compile_time_info =
+        get_edge_compile_time_info(project_path, server_addr, next_config.node_env(mode));
     let rsc_resolve_options_context =
         get_edge_resolve_options_context(project_path, ty, mode, next_config, execution_context);
     let rsc_module_options_context =
@@ -335,7 +348,8 @@ fn next_edge_route_transition(
     let mode = NextMode::DevServer;
     let server_ty = Value::new(ServerContextType::AppRoute { app_dir });
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(project_path, server_addr, next_config.node_env(mode));
 
     let edge_chunking_context = Vc::upcast(
         DevChunkingContext::builder(
@@ -384,7 +398,8 @@ fn next_edge_page_transition(
 ) -> Vc<Box<dyn Transition>> {
     let server_ty = Value::new(ServerContextType::AppSSR { app_dir });
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(project_path, server_addr, next_config.node_env(mode));
 
     let edge_chunking_context = Vc::upcast(
         DevChunkingContext::builder(
@@ -557,7 +572,7 @@ fn app_context(
     let ssr_ty = Value::new(ServerContextType::AppSSR { app_dir });
     ModuleAssetContext::new(
         Vc::cell(transitions),
-        get_server_compile_time_info(mode, env, server_addr),
+        get_server_compile_time_info(mode, env, server_addr, next_config),
         get_server_module_options_context(
             project_path,
             execution_context,
@@ -589,6 +604,7 @@ pub async fn create_app_source(
     client_compile_time_info: Vc<CompileTimeInfo>,
     next_config: Vc<NextConfig>,
     server_addr: Vc<ServerAddr>,
+    fallback_branding: Vc<FallbackBranding>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let Some(app_dir) = *app_dir.await? else {
         return Ok(Vc::upcast(NoContentSource::new()));
@@ -629,10 +645,15 @@ pub async fn create_app_source(
     let injected_env = env_for_js(Vc::upcast(EnvMap::empty()), false, next_config);
     let env = Vc::upcast(CustomProcessEnv::new(env, next_config.env()));
 
-    let server_runtime_entries = Vc::cell(vec![Vc::upcast(ProcessEnvAsset::new(
-        project_path,
-        injected_env,
-    ))]);
+    let mut server_runtime_entries =
+        vec![Vc::upcast(ProcessEnvAsset::new(project_path, injected_env))];
+    server_runtime_entries.extend(
+        instrumentation_runtime_entries(project_path, next_config)
+            .await?
+            .iter()
+            .copied(),
+    );
+    let server_runtime_entries = Vc::cell(server_runtime_entries);
 
     let fallback_page = get_fallback_page(
         project_path,
@@ -640,7 +661,9 @@ pub async fn create_app_source(
         server_root,
         env,
         client_compile_time_info,
+        client_chunking_context,
         next_config,
+        fallback_branding,
     );
     let render_data = render_data(next_config, server_addr);
 
@@ -4,7 +4,7 @@ use turbopack_binding::{
     turbo::tasks_fs::{FileSystemEntryType, FileSystemPath},
     turbopack::{
         core::{
-            issue::{Issue, IssueExt, IssueSeverity},
+            issue::{IssueExt, IssueSeverity},
             resolve::{parse::Request, pattern::Pattern, resolve},
         },
         node::transforms::webpack::WebpackLoaderItem,
@@ -16,6 +16,8 @@ use turbopack_binding::{
     },
 };
 
+use crate::config_issue::ConfigIssue;
+
 const BABEL_CONFIG_FILES: &[&str] = &[
     ".babelrc",
     ".babelrc.json",
@@ -31,24 +33,28 @@ const BABEL_CONFIG_FILES: &[&str] = &[
 /// If the user has a babel configuration file (see list above) alongside their
 /// `next.config.js` configuration, automatically add `babel-loader` as a
 /// webpack loader for each eligible file type if it doesn't already exist.
+///
+/// Unless `suppress_warning` is set, a warning is emitted when this happens, as
+/// babel is much slower than the built-in SWC transforms.
 #[turbo_tasks::function]
 pub async fn maybe_add_babel_loader(
     project_root: Vc<FileSystemPath>,
     webpack_rules: Option<Vc<WebpackRules>>,
+    suppress_warning: Vc<bool>,
 ) -> Result<Vc<OptionWebpackRules>> {
-    let has_babel_config = {
-        let mut has_babel_config = false;
+    let babel_config_file = {
+        let mut babel_config_file = None;
         for filename in BABEL_CONFIG_FILES {
             let filetype = *project_root.join(filename.to_string()).get_type().await?;
             if matches!(filetype, FileSystemEntryType::File) {
-                has_babel_config = true;
+                babel_config_file = Some(*filename);
                 break;
             }
         }
-        has_babel_config
+        babel_config_file
     };
 
-    if has_babel_config {
+    if let Some(babel_config_file) = babel_config_file {
         let mut rules = if let Some(webpack_rules) = webpack_rules {
             webpack_rules.await?.clone_value()
         } else {
@@ -71,7 +77,7 @@ pub async fn maybe_add_babel_loader(
                 if !has_emitted_babel_resolve_issue
                     && !*is_babel_loader_available(project_root).await?
                 {
-                    BabelIssue {
+                    ConfigIssue {
                         path: project_root,
                         title: Vc::cell(
                             "Unable to resolve babel-loader, but a babel config is present"
@@ -111,6 +117,25 @@ pub async fn maybe_add_babel_loader(
         }
 
         if has_changed {
+            if !*suppress_warning.await? {
+                ConfigIssue {
+                    path: project_root.join(babel_config_file.to_string()),
+                    title: Vc::cell(format!(
+                        "Babel is used to transform code because {babel_config_file} was found"
+                    )),
+                    description: Vc::cell(
+                        "A babel config disables the faster built-in SWC transforms for all \
+                         JavaScript and TypeScript files, which slows down compilation. Remove \
+                         the config if it is not needed, or set \
+                         `experimental.turbo.suppressBabelWarning` in next.config.js to hide this \
+                         warning."
+                            .to_string(),
+                    ),
+                    severity: IssueSeverity::Warning.cell(),
+                }
+                .cell()
+                .emit();
+            }
             return Ok(Vc::cell(Some(Vc::cell(rules))));
         }
     }
@@ -138,39 +163,3 @@ pub async fn is_babel_loader_available(project_path: Vc<FileSystemPath>) -> Resu
     let assets = result.primary_sources().await?;
     Ok(Vc::cell(!assets.is_empty()))
 }
-
-#[turbo_tasks::value]
-struct BabelIssue {
-    path: Vc<FileSystemPath>,
-    title: Vc<String>,
-    description: Vc<String>,
-    severity: Vc<IssueSeverity>,
-}
-
-#[turbo_tasks::value_impl]
-impl Issue for BabelIssue {
-    #[turbo_tasks::function]
-    fn category(&self) -> Vc<String> {
-        Vc::cell("other".to_string())
-    }
-
-    #[turbo_tasks::function]
-    fn severity(&self) -> Vc<IssueSeverity> {
-        self.severity
-    }
-
-    #[turbo_tasks::function]
-    fn file_path(&self) -> Vc<FileSystemPath> {
-        self.path
-    }
-
-    #[turbo_tasks::function]
-    fn title(&self) -> Vc<String> {
-        self.title
-    }
-
-    #[turbo_tasks::function]
-    fn description(&self) -> Vc<String> {
-        self.description
-    }
-}
@@ -0,0 +1,423 @@
+use anyhow::{bail, Result};
+use indexmap::IndexSet;
+use mime::APPLICATION_JSON;
+use serde_json::Value as JsonValue;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent},
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            introspect::{Introspectable, IntrospectableChildren},
+            version::VersionedContentExt,
+        },
+        dev_server::source::{
+            route_tree::{RouteTree, RouteType},
+            wrapping_source::{ContentSourceProcessor, WrappedGetContentSourceContent},
+            ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataVary,
+            GetContentSourceContent, ProxyResult, RewriteBuilder,
+        },
+    },
+};
+
+/// The response header which carries why an original stack frame is
+/// unavailable. The error overlay shows it in place of the frame.
+const UNAVAILABLE_REASON_HEADER: &str = "x-nextjs-original-frame-unavailable";
+
+/// Wraps the original stack frame source when client chunks have no source
+/// maps (`experimental.turbo.sourceMaps: 'none'`). Client frames are answered
+/// with `204 No Content` and the reason in [UNAVAILABLE_REASON_HEADER], which
+/// the error overlay shows. Server frames are still traced by `inner`.
+#[turbo_tasks::value(shared)]
+pub struct ClientSourceMapsDisabledContentSource {
+    inner: Vc<Box<dyn ContentSource>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ClientSourceMapsDisabledContentSource {
+    #[turbo_tasks::function]
+    pub fn new(inner: Vc<Box<dyn ContentSource>>) -> Vc<ClientSourceMapsDisabledContentSource> {
+        ClientSourceMapsDisabledContentSource { inner }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for ClientSourceMapsDisabledContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::CatchAll, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for ClientSourceMapsDisabledContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> Vc<ContentSourceDataVary> {
+        ContentSourceDataVary {
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: String,
+        data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let ContentSourceData {
+            raw_query: Some(raw_query),
+            ..
+        } = &*data
+        else {
+            bail!("missing data for client source maps disabled source");
+        };
+
+        if is_client_frame(raw_query) {
+            return Ok(ContentSourceContent::HttpProxy(
+                ProxyResult {
+                    status: 204,
+                    headers: vec![(
+                        UNAVAILABLE_REASON_HEADER.to_string(),
+                        "Source maps are disabled for client code (experimental.turbo.sourceMaps: \
+                         'none')"
+                            .to_string(),
+                    )],
+                    body: "".into(),
+                }
+                .cell(),
+            )
+            .cell());
+        }
+        let path_and_query = if raw_query.is_empty() {
+            format!("/{path}")
+        } else {
+            format!("/{path}?{raw_query}")
+        };
+        let rewrite = RewriteBuilder::new_source_with_path_and_query(self.inner, path_and_query);
+        Ok(ContentSourceContent::Rewrite(rewrite.build()).cell())
+    }
+}
+
+/// Whether an original stack frame request is for a client frame, i.e.
+/// neither `isServer` nor `isEdgeServer` is `true`.
+fn is_client_frame(raw_query: &str) -> bool {
+    !raw_query.split('&').any(|pair| {
+        matches!(
+            pair.split_once('='),
+            Some(("isServer" | "isEdgeServer", "true"))
+        )
+    })
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for ClientSourceMapsDisabledContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<String> {
+        Vc::cell("client source maps disabled source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> Vc<String> {
+        Vc::cell("reports original client stack frames as unavailable".to_string())
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<Vc<IntrospectableChildren>> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = Vc::try_resolve_sidecast::<Box<dyn Introspectable>>(self.inner).await?
+        {
+            children.insert((Vc::cell("inner".to_string()), inner));
+        }
+        Ok(Vc::cell(children))
+    }
+}
+
+/// Reduces the source maps served by the `inner` source to line-only maps
+/// without the original sources (`experimental.turbo.sourceMaps: 'cheap'`).
+/// They are smaller and faster to parse in the browser, and stack frames are
+/// still mapped to the original line.
+#[turbo_tasks::value(shared)]
+pub struct CheapSourceMapsContentSource {
+    inner: Vc<Box<dyn ContentSource>>,
+}
+
+#[turbo_tasks::value_impl]
+impl CheapSourceMapsContentSource {
+    #[turbo_tasks::function]
+    pub fn new(inner: Vc<Box<dyn ContentSource>>) -> Vc<CheapSourceMapsContentSource> {
+        CheapSourceMapsContentSource { inner }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for CheapSourceMapsContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::CatchAll, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for CheapSourceMapsContentSource {
+    #[turbo_tasks::function]
+    async fn get(
+        self: Vc<Self>,
+        path: String,
+        _data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let this = self.await?;
+        let is_source_map = path.ends_with(".map");
+        let sources = this.inner.get_routes().get(path);
+        if !is_source_map {
+            return Ok(
+                ContentSourceContent::Rewrite(RewriteBuilder::new_sources(sources).build()).cell(),
+            );
+        }
+        let sources = sources
+            .await?
+            .iter()
+            .map(|s| {
+                Vc::upcast(WrappedGetContentSourceContent::new(
+                    *s,
+                    Vc::upcast(CheapSourceMapProcessor.cell()),
+                ))
+            })
+            .collect();
+        let sources = Vc::cell(sources);
+        Ok(ContentSourceContent::Rewrite(RewriteBuilder::new_sources(sources).build()).cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for CheapSourceMapsContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<String> {
+        Vc::cell("cheap source maps content source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> Vc<String> {
+        Vc::cell("serves line-only source maps without the original sources".to_string())
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<Vc<IntrospectableChildren>> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = Vc::try_resolve_sidecast::<Box<dyn Introspectable>>(self.inner).await?
+        {
+            children.insert((Vc::cell("inner".to_string()), inner));
+        }
+        Ok(Vc::cell(children))
+    }
+}
+
+#[turbo_tasks::value]
+struct CheapSourceMapProcessor;
+
+#[turbo_tasks::value_impl]
+impl ContentSourceProcessor for CheapSourceMapProcessor {
+    #[turbo_tasks::function]
+    async fn process(&self, content: Vc<ContentSourceContent>) -> Result<Vc<ContentSourceContent>> {
+        let ContentSourceContent::Static(static_content) = *content.await? else {
+            return Ok(content);
+        };
+        let static_content = static_content.await?;
+        if static_content.status_code != 200 {
+            return Ok(content);
+        }
+        let asset_content = static_content.content.content().await?;
+        let AssetContent::File(file_content) = *asset_content else {
+            return Ok(content);
+        };
+        let FileContent::Content(file) = &*file_content.await? else {
+            return Ok(content);
+        };
+        let Ok(mut map) = serde_json::from_str::<JsonValue>(&file.content().to_str()?) else {
+            return Ok(content);
+        };
+        cheapen_source_map(&mut map);
+        Ok(ContentSourceContent::static_content(
+            AssetContent::file(
+                File::from(serde_json::to_string(&map)?)
+                    .with_content_type(APPLICATION_JSON)
+                    .into(),
+            )
+            .versioned(),
+        ))
+    }
+}
+
+/// Drops the original sources, names and columns of a source map, keeping
+/// one mapping per generated line. Handles the sectioned maps of chunks.
+fn cheapen_source_map(map: &mut JsonValue) {
+    let JsonValue::Object(map) = map else {
+        return;
+    };
+    if let Some(JsonValue::Array(sections)) = map.get_mut("sections") {
+        for section in sections {
+            if let Some(section_map) = section.get_mut("map") {
+                cheapen_source_map(section_map);
+            }
+        }
+        return;
+    }
+    map.remove("sourcesContent");
+    if let Some(names) = map.get_mut("names") {
+        *names = JsonValue::Array(Vec::new());
+    }
+    if let Some(JsonValue::String(mappings)) = map.get_mut("mappings") {
+        if let Some(cheap) = line_only_mappings(mappings) {
+            *mappings = cheap;
+        }
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Keeps the first mapping of each generated line, pointing at column 0 of
+/// the original line. Returns `None` for malformed mappings.
+fn line_only_mappings(mappings: &str) -> Option<String> {
+    // Source index, line and column are relative to the previous segment,
+    // across lines, so every segment is decoded to keep track of them.
+    let mut source = 0;
+    let mut line = 0;
+    let mut written_source = 0;
+    let mut written_line = 0;
+    let mut out = String::with_capacity(mappings.len() / 4);
+    for (i, generated_line) in mappings.split(';').enumerate() {
+        if i > 0 {
+            out.push(';');
+        }
+        let mut written = false;
+        for segment in generated_line.split(',').filter(|s| !s.is_empty()) {
+            let fields = decode_vlq(segment)?;
+            if fields.len() < 4 {
+                continue;
+            }
+            source += fields[1];
+            line += fields[2];
+            if !written {
+                for field in [0, source - written_source, line - written_line, 0] {
+                    encode_vlq(&mut out, field);
+                }
+                written_source = source;
+                written_line = line;
+                written = true;
+            }
+        }
+    }
+    Some(out)
+}
+
+fn decode_vlq(segment: &str) -> Option<Vec<i64>> {
+    let mut fields = Vec::new();
+    let mut value = 0i64;
+    let mut shift = 0;
+    for byte in segment.bytes() {
+        let digit = BASE64.iter().position(|&b| b == byte)? as i64;
+        value += (digit & 0b11111) << shift;
+        if digit & 0b100000 != 0 {
+            shift += 5;
+            continue;
+        }
+        fields.push(if value & 1 == 1 {
+            -(value >> 1)
+        } else {
+            value >> 1
+        });
+        value = 0;
+        shift = 0;
+    }
+    (shift == 0).then_some(fields)
+}
+
+fn encode_vlq(out: &mut String, value: i64) {
+    let mut value = if value < 0 {
+        (-value << 1) | 1
+    } else {
+        value << 1
+    };
+    loop {
+        let mut digit = value & 0b11111;
+        value >>= 5;
+        if value > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit as usize] as char);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{cheapen_source_map, is_client_frame, line_only_mappings};
+
+    #[test]
+    fn client_frames() {
+        assert!(is_client_frame(
+            "isServer=false&isEdgeServer=false&file=_next%2Fstatic%2Fchunks%2Fa.js"
+        ));
+        assert!(is_client_frame(""));
+    }
+
+    #[test]
+    fn server_frames() {
+        assert!(!is_client_frame("isServer=true&isEdgeServer=false"));
+        assert!(!is_client_frame("isServer=false&isEdgeServer=true"));
+    }
+
+    #[test]
+    fn keeps_the_first_mapping_of_each_line() {
+        // Line 0 maps columns 0 and 4 to 0:2 and 0:6 of source 0, line 2 maps
+        // columns 3 and 4 to 1:0 and 1:1 of source 1.
+        assert_eq!(
+            line_only_mappings("AAAE,IAAI;;GCCA,CAAC").as_deref(),
+            Some("AAAA;;ACCA")
+        );
+        // Relative lines may be negative.
+        assert_eq!(
+            line_only_mappings("AAEA;AAFA").as_deref(),
+            Some("AAEA;AAFA")
+        );
+        assert_eq!(line_only_mappings("AA!A"), None);
+    }
+
+    #[test]
+    fn cheapens_sectioned_maps() {
+        let mut map = json!({
+            "version": 3,
+            "sections": [{
+                "offset": { "line": 0, "column": 0 },
+                "map": {
+                    "version": 3,
+                    "sources": ["pages/index.js"],
+                    "sourcesContent": ["export default 1"],
+                    "names": ["x"],
+                    "mappings": "AAAE,IAAIA",
+                },
+            }],
+        });
+        cheapen_source_map(&mut map);
+        assert_eq!(
+            map,
+            json!({
+                "version": 3,
+                "sections": [{
+                    "offset": { "line": 0, "column": 0 },
+                    "map": {
+                        "version": 3,
+                        "sources": ["pages/index.js"],
+                        "names": [],
+                        "mappings": "AAAA",
+                    },
+                }],
+            })
+        );
+    }
+}
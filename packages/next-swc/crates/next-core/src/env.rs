@@ -6,7 +6,7 @@ use turbopack_binding::{
     turbopack::env::EmbeddableProcessEnv,
 };
 
-use crate::next_config::NextConfig;
+use crate::next_config::{NextConfig, RuntimeConfigMode};
 
 /// Creates a Vc<Box<dyn ProcessEnv>> safe to use in JS, by stringifying and
 /// encoding as regular JS strings. Setting `client` to true will additionally
@@ -15,6 +15,11 @@ use crate::next_config::NextConfig;
 ///
 /// For now, it also injects overridden values as if they were real JS code, eg
 /// an Object and not a String.
+///
+/// Everything injected here is fixed at build time. With the `inline` runtime
+/// config mode, the server env also carries the runtime config as
+/// `__NEXT_RUNTIME_CONFIG`, otherwise it is sent with each render request (see
+/// [crate::util::render_data]).
 #[turbo_tasks::function]
 pub async fn env_for_js(
     env: Vc<Box<dyn ProcessEnv>>,
@@ -51,6 +56,7 @@ pub async fn env_for_js(
         "__NEXT_IMAGE_OPTS".to_string() => serde_json::to_string(&image_config)?,
     };
 
+    let runtime_config = next_config.runtime_config();
     let next_config = next_config.await?;
 
     if next_config.react_strict_mode.unwrap_or(false) {
@@ -65,5 +71,15 @@ pub async fn env_for_js(
         map.insert("__NEXT_TEST_MODE".to_string(), "true".to_string());
     }
 
+    if !client {
+        let runtime_config = runtime_config.await?;
+        if runtime_config.mode == RuntimeConfigMode::Inline {
+            map.insert(
+                "__NEXT_RUNTIME_CONFIG".to_string(),
+                serde_json::to_string(&*runtime_config)?,
+            );
+        }
+    }
+
     Ok(Vc::upcast(CustomProcessEnv::new(env, Vc::cell(map))))
 }
@@ -0,0 +1,223 @@
+use anyhow::Result;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::{tasks_fs::FileContent, tasks_hash::hash_xxh3_hash64},
+    turbopack::{
+        core::{asset::AssetContent, introspect::Introspectable},
+        dev_server::source::{
+            headers::HeaderValue,
+            route_tree::{RouteTree, RouteType},
+            wrapping_source::{ContentSourceProcessor, WrappedGetContentSourceContent},
+            ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataFilter,
+            ContentSourceDataVary, GetContentSourceContent, HeaderList, ProxyResult,
+            RewriteBuilder, StaticContent,
+        },
+    },
+};
+
+/// Adds an `ETag` with the hash of the served bytes to the successful static
+/// responses of the `inner` source, and answers requests whose
+/// `If-None-Match` matches it with `304 Not Modified`. Reloading a page then
+/// doesn't transfer unchanged chunks again. The hash is computed from the
+/// compiled output, so any change, e.g. an HMR update, changes the `ETag`.
+#[turbo_tasks::value(shared)]
+pub struct ETagContentSource {
+    inner: Vc<Box<dyn ContentSource>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ETagContentSource {
+    #[turbo_tasks::function]
+    pub fn new(inner: Vc<Box<dyn ContentSource>>) -> Vc<ETagContentSource> {
+        ETagContentSource { inner }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for ETagContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::CatchAll, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for ETagContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> Vc<ContentSourceDataVary> {
+        // Only the one header, so other headers don't fragment the cache.
+        ContentSourceDataVary {
+            headers: Some(ContentSourceDataFilter::Subset(
+                ["if-none-match".to_string()].into(),
+            )),
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        self: Vc<Self>,
+        path: String,
+        data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let this = self.await?;
+        let if_none_match = data
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get("if-none-match"))
+            .and_then(if_none_match);
+        let processor = ETagProcessor::new(if_none_match);
+        let sources = this.inner.get_routes().get(path).await?;
+        let sources = sources
+            .iter()
+            .map(|s| {
+                Vc::upcast(WrappedGetContentSourceContent::new(
+                    *s,
+                    Vc::upcast(processor),
+                ))
+            })
+            .collect();
+        let sources = Vc::cell(sources);
+        Ok(ContentSourceContent::Rewrite(RewriteBuilder::new_sources(sources).build()).cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for ETagContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<String> {
+        Vc::cell("etag content source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> Vc<String> {
+        Vc::cell("answers conditional requests for unchanged content with 304".to_string())
+    }
+}
+
+#[turbo_tasks::value]
+struct ETagProcessor {
+    if_none_match: Option<String>,
+}
+
+#[turbo_tasks::value_impl]
+impl ETagProcessor {
+    #[turbo_tasks::function]
+    pub fn new(if_none_match: Option<String>) -> Vc<ETagProcessor> {
+        ETagProcessor { if_none_match }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSourceProcessor for ETagProcessor {
+    #[turbo_tasks::function]
+    async fn process(&self, content: Vc<ContentSourceContent>) -> Result<Vc<ContentSourceContent>> {
+        let ContentSourceContent::Static(static_content) = *content.await? else {
+            return Ok(content);
+        };
+        let static_content = static_content.await?;
+        if static_content.status_code != 200 {
+            return Ok(content);
+        }
+        let asset_content = static_content.content.content().await?;
+        let AssetContent::File(file_content) = *asset_content else {
+            return Ok(content);
+        };
+        let FileContent::Content(file) = &*file_content.await? else {
+            return Ok(content);
+        };
+        let etag = etag(&file.content().to_bytes()?);
+
+        if matches!(&self.if_none_match, Some(if_none_match) if etag_matches(if_none_match, &etag))
+        {
+            return Ok(ContentSourceContent::HttpProxy(
+                ProxyResult {
+                    status: 304,
+                    headers: vec![("etag".to_string(), etag)],
+                    body: "".into(),
+                }
+                .cell(),
+            )
+            .cell());
+        }
+
+        let mut headers = static_content.headers.await?.clone_value();
+        headers.push(("etag".to_string(), etag));
+        Ok(ContentSourceContent::Static(
+            StaticContent {
+                content: static_content.content,
+                status_code: static_content.status_code,
+                headers: HeaderList::new(headers),
+            }
+            .cell(),
+        )
+        .cell())
+    }
+}
+
+/// The value of the `If-None-Match` request header. Repeated headers are
+/// combined into one list.
+fn if_none_match(value: &HeaderValue) -> Option<String> {
+    match value {
+        HeaderValue::SingleString(value) => Some(value.trim().to_string()),
+        HeaderValue::MultiStrings(values) => Some(values.join(", ")),
+        HeaderValue::SingleBytes(_) | HeaderValue::MultiBytes(_) => None,
+    }
+}
+
+/// A strong `ETag` for the served bytes.
+fn etag(bytes: &[u8]) -> String {
+    format!("\"{:016x}\"", hash_xxh3_hash64(bytes))
+}
+
+/// Whether an `If-None-Match` header, a comma-separated list of entity tags
+/// or `*`, matches `etag`. Uses the weak comparison, which ignores `W/`.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let strip_weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = strip_weak(etag);
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || strip_weak(tag) == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use turbopack_binding::turbopack::dev_server::source::headers::HeaderValue;
+
+    use super::{etag, etag_matches, if_none_match};
+
+    #[test]
+    fn etag_reflects_the_bytes() {
+        assert_eq!(etag(b"console.log(1)"), etag(b"console.log(1)"));
+        assert_ne!(etag(b"console.log(1)"), etag(b"console.log(2)"));
+        assert!(etag(b"").starts_with('"') && etag(b"").ends_with('"'));
+    }
+
+    #[test]
+    fn matches_if_none_match() {
+        let tag = etag(b"chunk");
+        assert!(etag_matches(&tag, &tag));
+        assert!(etag_matches(&format!("W/{tag}"), &tag));
+        assert!(etag_matches(&format!("\"other\", {tag}"), &tag));
+        assert!(etag_matches("*", &tag));
+        assert!(!etag_matches("\"other\"", &tag));
+        assert!(!etag_matches(&etag(b"changed chunk"), &tag));
+    }
+
+    #[test]
+    fn reads_if_none_match() {
+        assert_eq!(
+            if_none_match(&HeaderValue::SingleString(" \"abc\" ".to_string())),
+            Some("\"abc\"".to_string())
+        );
+        assert_eq!(
+            if_none_match(&HeaderValue::MultiStrings(vec![
+                "\"abc\"".to_string(),
+                "\"def\"".to_string()
+            ])),
+            Some("\"abc\", \"def\"".to_string())
+        );
+        assert_eq!(if_none_match(&HeaderValue::SingleBytes(vec![0xff])), None);
+    }
+}
@@ -1,16 +1,25 @@
 use std::collections::HashMap;
 
 use anyhow::{bail, Result};
+use serde_json::json;
 use turbo_tasks::{Value, Vc};
 use turbopack_binding::{
-    turbo::{tasks_env::ProcessEnv, tasks_fs::FileSystemPath},
+    turbo::{
+        tasks_env::ProcessEnv,
+        tasks_fs::{File, FileSystemPath},
+    },
     turbopack::{
         core::{
+            asset::AssetContent,
+            chunk::EvaluatableAssetExt,
             compile_time_info::CompileTimeInfo,
             context::AssetContext,
             resolve::{options::ImportMap, origin::PlainResolveOrigin},
+            source::Source,
+            virtual_source::VirtualSource,
         },
         dev_server::html::DevHtmlAsset,
+        ecmascript::chunk::EcmascriptChunkingContext,
         node::execution_context::ExecutionContext,
         turbopack::{ecmascript::EcmascriptModuleAsset, ModuleAssetContext},
     },
@@ -19,14 +28,37 @@ use turbopack_binding::{
 use crate::{
     mode::NextMode,
     next_client::context::{
-        get_client_chunking_context, get_client_module_options_context,
-        get_client_resolve_options_context, get_client_runtime_entries, ClientContextType,
+        get_client_module_options_context, get_client_resolve_options_context,
+        get_client_runtime_entries, ClientContextType,
     },
     next_config::NextConfig,
     next_import_map::insert_next_shared_aliases,
     runtime::resolve_runtime_request,
 };
 
+/// The label and favicon of the page shown while a page is compiling or
+/// failed to compile. Unset fields keep the default Next.js look.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Default)]
+pub struct FallbackBranding {
+    /// Shown in a header above the page content.
+    pub label: Option<String>,
+    /// The URL of the favicon, e.g. `/brand.ico`.
+    pub favicon: Option<String>,
+}
+
+/// The module that hands the branding to the fallback entry, which runs right
+/// after it.
+fn fallback_branding_code(branding: &FallbackBranding) -> String {
+    format!(
+        "globalThis.__NEXT_FALLBACK_BRANDING__ = {};",
+        json!({
+            "label": branding.label,
+            "favicon": branding.favicon,
+        })
+    )
+}
+
 #[turbo_tasks::function]
 pub async fn get_fallback_page(
     project_path: Vc<FileSystemPath>,
@@ -34,7 +66,9 @@ pub async fn get_fallback_page(
     dev_server_root: Vc<FileSystemPath>,
     env: Vc<Box<dyn ProcessEnv>>,
     client_compile_time_info: Vc<CompileTimeInfo>,
+    chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
     next_config: Vc<NextConfig>,
+    branding: Vc<FallbackBranding>,
 ) -> Result<Vc<DevHtmlAsset>> {
     let ty = Value::new(ClientContextType::Fallback);
     let mode = NextMode::DevServer;
@@ -48,12 +82,6 @@ pub async fn get_fallback_page(
         mode,
         next_config,
     );
-    let chunking_context = get_client_chunking_context(
-        project_path,
-        dev_server_root,
-        client_compile_time_info.environment(),
-        mode,
-    );
     let entries =
         get_client_runtime_entries(project_path, env, ty, mode, next_config, execution_context);
 
@@ -89,12 +117,41 @@ pub async fn get_fallback_page(
         bail!("fallback runtime entry is not an ecmascript module");
     };
 
+    let branding_source: Vc<Box<dyn Source>> = Vc::upcast(VirtualSource::new(
+        project_path.join("_next/fallback-branding.js".to_string()),
+        AssetContent::file(File::from(fallback_branding_code(&*branding.await?)).into()),
+    ));
+
     Ok(DevHtmlAsset::new(
         dev_server_root.join("fallback.html".to_string()),
         vec![(
             Vc::upcast(module),
             Vc::upcast(chunking_context),
-            Some(runtime_entries.with_entry(Vc::upcast(module))),
+            Some(
+                runtime_entries
+                    .with_entry(branding_source.to_evaluatable(context))
+                    .with_entry(Vc::upcast(module)),
+            ),
         )],
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fallback_branding_code, FallbackBranding};
+
+    #[test]
+    fn fallback_branding_code_contains_label() {
+        let code = fallback_branding_code(&FallbackBranding {
+            label: Some("Acme \"Dev\" Server".to_string()),
+            favicon: Some("/acme.ico".to_string()),
+        });
+        assert!(code.starts_with("globalThis.__NEXT_FALLBACK_BRANDING__ = {"));
+        assert!(code.contains(r#""label":"Acme \"Dev\" Server""#));
+        assert!(code.contains(r#""favicon":"/acme.ico""#));
+
+        let code = fallback_branding_code(&FallbackBranding::default());
+        assert!(code.contains(r#""label":null"#));
+        assert!(code.contains(r#""favicon":null"#));
+    }
+}
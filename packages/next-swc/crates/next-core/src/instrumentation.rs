@@ -0,0 +1,62 @@
+use anyhow::Result;
+use indoc::formatdoc;
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileSystemEntryType, FileSystemPath},
+    turbopack::core::{asset::AssetContent, source::Sources, virtual_source::VirtualSource},
+};
+
+use crate::next_config::NextConfig;
+
+/// Returns the server runtime entries which run the `register` export of the
+/// project's instrumentation hook, when `experimental.instrumentationHook` is
+/// set. The hook is looked up at `instrumentation.{ts,js}` and
+/// `src/instrumentation.{ts,js}`, like `next dev` does.
+///
+/// The entry is evaluated by the same Node.js processes which render pages,
+/// before the page module, so the hook can set up tracing for the rendering
+/// code.
+#[turbo_tasks::function]
+pub async fn instrumentation_runtime_entries(
+    project_path: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<Sources>> {
+    if !*next_config.instrumentation_hook().await? {
+        return Ok(Vc::cell(vec![]));
+    }
+    let Some(request) = find_instrumentation_file(project_path).await? else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let code = formatdoc! {
+        r#"
+            import * as instrumentation from "{request}";
+
+            if (typeof instrumentation.register === "function") {{
+                Promise.resolve(instrumentation.register()).catch((err) => {{
+                    console.error("Failed to run the instrumentation hook", err);
+                }});
+            }}
+        "#
+    };
+    let file = File::from(code);
+    let source = VirtualSource::new(
+        project_path.join("instrumentation-entry.js".to_string()),
+        AssetContent::file(file.into()),
+    );
+    Ok(Vc::cell(vec![Vc::upcast(source)]))
+}
+
+/// Returns the request for the instrumentation hook, relative to the project.
+async fn find_instrumentation_file(project_path: Vc<FileSystemPath>) -> Result<Option<String>> {
+    for dir in ["", "src/"] {
+        for extension in ["ts", "js"] {
+            let request = format!("./{dir}instrumentation.{extension}");
+            let path = project_path.join(request[2..].to_string());
+            if *path.get_type().await? == FileSystemEntryType::File {
+                return Ok(Some(request));
+            }
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use turbo_tasks::{RawVc, ReadRef, TransientInstance, TransientValue, Vc};
+use turbopack_binding::turbopack::core::issue::{CapturedIssues, IssueReporter, IssueSeverity};
+
+/// Wraps an [IssueReporter] and fails when an issue of one of the `error_on`
+/// categories is reported, whatever its severity. This lets CI builds treat
+/// e.g. unresolved imports as errors while other warnings stay warnings.
+#[turbo_tasks::value]
+pub struct ErrorOnIssueReporter {
+    reporter: Vc<Box<dyn IssueReporter>>,
+    error_on: Vc<Vec<String>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ErrorOnIssueReporter {
+    #[turbo_tasks::function]
+    pub fn new(reporter: Vc<Box<dyn IssueReporter>>, error_on: Vc<Vec<String>>) -> Vc<Self> {
+        ErrorOnIssueReporter { reporter, error_on }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl IssueReporter for ErrorOnIssueReporter {
+    #[turbo_tasks::function]
+    async fn report_issues(
+        &self,
+        issues: TransientInstance<ReadRef<CapturedIssues>>,
+        source: TransientValue<RawVc>,
+        min_failing_severity: Vc<IssueSeverity>,
+    ) -> Result<Vc<bool>> {
+        let has_fatal = *self
+            .reporter
+            .report_issues(issues.clone(), source, min_failing_severity)
+            .await?;
+        if has_fatal {
+            return Ok(Vc::cell(true));
+        }
+        let error_on = self.error_on.await?;
+        if !error_on.is_empty() {
+            for (issue, _) in issues.iter_with_shortest_path() {
+                if error_on.contains(&*issue.category().await?) {
+                    return Ok(Vc::cell(true));
+                }
+            }
+        }
+        Ok(Vc::cell(false))
+    }
+}
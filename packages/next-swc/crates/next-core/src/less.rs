@@ -0,0 +1,64 @@
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+use turbo_tasks::Vc;
+use turbopack_binding::turbopack::{
+    node::transforms::webpack::WebpackLoaderItem,
+    turbopack::module_options::{LoaderRuleItem, OptionWebpackRules, WebpackRules},
+};
+
+/// If the user has a `lessOptions` block in their `next.config.js`, adds
+/// `less-loader` for `.less` files. Projects without it are left untouched.
+#[turbo_tasks::function]
+pub async fn maybe_add_less_loader(
+    less_options: Vc<JsonValue>,
+    webpack_rules: Option<Vc<WebpackRules>>,
+) -> Result<Vc<OptionWebpackRules>> {
+    let less_options = less_options.await?;
+    let Some(less_options) = less_options.as_object() else {
+        return Ok(Vc::cell(webpack_rules));
+    };
+    let mut rules = if let Some(webpack_rules) = webpack_rules {
+        webpack_rules.await?.clone_value()
+    } else {
+        Default::default()
+    };
+    for (pattern, rename) in [("*.module.less", ".module.css"), ("*.less", ".css")] {
+        let rule = rules.get_mut(pattern);
+        let loader = WebpackLoaderItem {
+            loader: "less-loader".to_string(),
+            options: serde_json::json!({
+                "sourceMap": false,
+                // Includes `modifyVars` and `javascriptEnabled`, which are passed
+                // through to the less compiler as-is.
+                "lessOptions": less_options,
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        };
+
+        if let Some(rule) = rule {
+            // Without `as`, loader result would be JS code, so we don't want to apply
+            // less-loader on that.
+            let Some(rename_as) = rule.rename_as.as_ref() else {
+                continue;
+            };
+            if rename_as != "*" {
+                continue;
+            }
+            let mut loaders = rule.loaders.await?.clone_value();
+            loaders.push(loader);
+            rule.loaders = Vc::cell(loaders);
+        } else {
+            rules.insert(
+                pattern.to_string(),
+                LoaderRuleItem {
+                    loaders: Vc::cell(vec![loader]),
+                    rename_as: Some(format!("*{rename}")),
+                },
+            );
+        }
+    }
+
+    Ok(Vc::cell(Some(Vc::cell(rules))))
+}
@@ -12,11 +12,17 @@ mod app_source;
 pub mod app_structure;
 mod babel;
 mod bootstrap;
+pub mod client_source_maps;
+mod config_issue;
 pub mod dev_manifest;
 mod embed_js;
 mod emit;
 pub mod env;
+pub mod etag_source;
 mod fallback;
+mod instrumentation;
+pub mod issue_reporter;
+mod less;
 pub mod loader_tree;
 pub mod middleware;
 pub mod mode;
@@ -42,10 +48,15 @@ pub mod next_telemetry;
 mod page_loader;
 mod page_source;
 pub mod pages_structure;
+pub mod raw_asset_source;
+pub mod rewrites_source;
 pub mod router;
 pub mod router_source;
 mod runtime;
 mod sass;
+mod service_worker;
+pub mod source_root_remap;
+mod stylus;
 pub mod tracing_presets;
 mod transform_options;
 pub mod url_node;
@@ -59,11 +70,14 @@ pub use app_source::create_app_source;
 pub use emit::{
     all_assets_from_entries, all_server_paths, emit_all_assets, emit_assets, emit_client_assets,
 };
+pub use fallback::FallbackBranding;
 pub use next_edge::context::{
     get_edge_chunking_context, get_edge_compile_time_info, get_edge_resolve_options_context,
 };
+pub use next_import_map::get_next_package;
 pub use page_loader::{create_page_loader_entry_module, PageLoaderAsset};
 pub use page_source::create_page_source;
+pub use service_worker::create_service_worker_source;
 pub use turbopack_binding::{turbopack::node::source_map, *};
 pub use util::{get_asset_path_from_pathname, pathname_for_path, PathType};
 pub use web_entry_source::create_web_entry_source;
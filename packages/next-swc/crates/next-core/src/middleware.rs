@@ -1,13 +1,18 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use indexmap::indexmap;
-use turbo_tasks::{Value, Vc};
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, Value, Vc};
 use turbo_tasks_fs::{File, FileSystemPath};
 use turbopack_binding::turbopack::core::{
     asset::AssetContent, context::AssetContext, module::Module, reference_type::ReferenceType,
     virtual_source::VirtualSource,
 };
 
-use crate::util::{load_next_js_template, virtual_next_js_template_path};
+use crate::{
+    next_config::NextConfig,
+    next_route_matcher::path_to_regexp::path_to_regexp,
+    util::{load_next_js_template, virtual_next_js_template_path, NextSourceConfig},
+};
 
 #[turbo_tasks::function]
 pub async fn middleware_files(page_extensions: Vc<Vec<String>>) -> Result<Vc<Vec<String>>> {
@@ -51,3 +56,95 @@ pub async fn get_middleware_module(
 
     Ok(module)
 }
+
+/// A path pattern the middleware runs for, from its `config.matcher` export.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct MiddlewarePathMatcher {
+    /// The regular expression the runtime matches request paths against.
+    pub regexp: String,
+    /// The matcher as written in the middleware config.
+    pub original_source: String,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct MiddlewarePathMatchers(Vec<MiddlewarePathMatcher>);
+
+/// Resolves the matchers of the middleware the same way the runtime does
+/// (see `getMiddlewareMatchers` in `get-page-static-info.ts`), including the
+/// `/_next/data` routes, the locale prefix and the `basePath`. Without a
+/// `config.matcher` export, the middleware runs for every path.
+#[turbo_tasks::function]
+pub async fn middleware_matchers(
+    config: Vc<NextSourceConfig>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<MiddlewarePathMatchers>> {
+    let Some(matchers) = config.await?.matcher.clone() else {
+        return Ok(Vc::cell(vec![MiddlewarePathMatcher {
+            regexp: "^/.*$".to_string(),
+            original_source: "/:path*".to_string(),
+        }]));
+    };
+    let base_path = next_config.base_path().await?;
+    let has_i18n = *next_config.has_i18n().await?;
+    let matchers = matchers
+        .into_iter()
+        .map(|original_source| {
+            let source = matcher_source(&original_source, &base_path, has_i18n);
+            let regexp = path_to_regexp(&source)
+                .with_context(|| format!("Invalid middleware matcher source: {original_source}"))?;
+            Ok(MiddlewarePathMatcher {
+                regexp,
+                original_source,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(Vc::cell(matchers))
+}
+
+/// Expands a matcher to the source the runtime turns into a regular
+/// expression.
+fn matcher_source(source: &str, base_path: &str, has_i18n: bool) -> String {
+    let is_root = source == "/";
+    let mut source = source.to_string();
+    if has_i18n {
+        source = format!(
+            "/:nextInternalLocale((?!_next/)[^/.]{{1,}}){}",
+            if is_root { "" } else { &source }
+        );
+    }
+    let suffix = if !is_root {
+        "(.json)?"
+    } else if has_i18n {
+        "(|\\.json|/?index|/?index\\.json)?"
+    } else {
+        "(/?index|/?index\\.json)?"
+    };
+    format!("{base_path}/:nextData(_next/data/[^/]{{1,}})?{source}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matcher_source;
+
+    #[test]
+    fn matcher_source_adds_data_routes() {
+        assert_eq!(
+            matcher_source("/about", "", false),
+            "/:nextData(_next/data/[^/]{1,})?/about(.json)?"
+        );
+        assert_eq!(
+            matcher_source("/", "/docs", false),
+            "/docs/:nextData(_next/data/[^/]{1,})?/(/?index|/?index\\.json)?"
+        );
+    }
+
+    #[test]
+    fn matcher_source_adds_locale() {
+        assert_eq!(
+            matcher_source("/about", "", true),
+            "/:nextData(_next/data/[^/]{1,})?/:nextInternalLocale((?!_next/)[^/.]{1,})/about(.\
+             json)?"
+        );
+    }
+}
@@ -7,17 +7,16 @@ use turbopack_binding::{
     turbo::{tasks_env::ProcessEnv, tasks_fs::FileSystemPath},
     turbopack::{
         core::{
-            compile_time_defines,
             compile_time_info::{
                 CompileTimeDefines, CompileTimeInfo, FreeVarReference, FreeVarReferences,
             },
             environment::{BrowserEnvironment, Environment, ExecutionEnvironment},
             free_var_references,
-            resolve::{parse::Request, pattern::Pattern},
+            issue::{Issue, IssueExt, IssueSeverity},
+            resolve::{parse::Request, pattern::Pattern, resolve},
         },
         dev::{react_refresh::assert_can_resolve_react_refresh, DevChunkingContext},
         ecmascript::chunk::EcmascriptChunkingContext,
-        ecmascript_plugin::transform::directives::server::ServerDirectiveTransformer,
         env::ProcessEnvAsset,
         node::execution_context::ExecutionContext,
         turbopack::{
@@ -27,6 +26,7 @@ use turbopack_binding::{
                 JsxTransformOptions, MdxTransformModuleOptions, PostCssTransformOptions,
                 TypescriptTransformOptions, WebpackLoadersOptions,
             },
+            resolve_options,
             resolve_options_context::ResolveOptionsContext,
         },
     },
@@ -37,27 +37,33 @@ use crate::{
     babel::maybe_add_babel_loader,
     embed_js::next_js_fs,
     env::env_for_js,
+    less::maybe_add_less_loader,
     mode::NextMode,
     next_build::{get_external_next_compiled_package_mapping, get_postcss_package_mapping},
     next_client::runtime_entry::{RuntimeEntries, RuntimeEntry},
-    next_config::NextConfig,
+    next_config::{FreeVarConfigItem, NextConfig, RuntimeEntryPosition, SourceMapLevel},
     next_import_map::{
         get_next_client_fallback_import_map, get_next_client_import_map,
         get_next_client_resolved_map, mdx_import_source_file,
     },
     next_shared::{
+        defines::{defines as shared_defines, DefineEnv},
         resolve::{
             ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
             UnsupportedModulesResolvePlugin,
         },
         transforms::{
             emotion::get_emotion_transform_plugin, get_relay_transform_plugin,
+            global_css::get_global_css_check_plugin,
+            import_attributes::get_import_attributes_check_plugin,
+            node_polyfills::get_node_polyfills_warning_plugin,
             styled_components::get_styled_components_transform_plugin,
             styled_jsx::get_styled_jsx_transform_plugin,
             swc_ecma_transform_plugins::get_swc_ecma_transform_plugin,
         },
     },
     sass::maybe_add_sass_loader,
+    stylus::maybe_add_stylus_loader,
     transform_options::{
         get_decorators_transform_options, get_jsx_transform_options,
         get_typescript_transform_options,
@@ -65,27 +71,23 @@ use crate::{
     util::foreign_code_context_condition,
 };
 
-fn defines(mode: NextMode) -> CompileTimeDefines {
-    compile_time_defines!(
-        process.turbopack = true,
-        process.env.NODE_ENV = mode.node_env(),
-        process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
-        process.env.__NEXT_HAS_REWRITES = true,
-        process.env.__NEXT_I18N_SUPPORT = false,
-    )
-    // TODO(WEB-937) there are more defines needed, see
-    // packages/next/src/build/webpack-config.ts
+fn defines(node_env: &str) -> CompileTimeDefines {
+    shared_defines(DefineEnv::Client, node_env)
 }
 
 #[turbo_tasks::function]
-fn next_client_defines(mode: NextMode) -> Vc<CompileTimeDefines> {
-    defines(mode).cell()
+async fn next_client_defines(node_env: Vc<String>) -> Result<Vc<CompileTimeDefines>> {
+    Ok(defines(&node_env.await?).cell())
 }
 
 #[turbo_tasks::function]
-async fn next_client_free_vars(mode: NextMode) -> Result<Vc<FreeVarReferences>> {
-    Ok(free_var_references!(
-        ..defines(mode).into_iter(),
+async fn next_client_free_vars(
+    node_env: Vc<String>,
+    project_path: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<FreeVarReferences>> {
+    let mut free_vars = free_var_references!(
+        ..defines(&node_env.await?).into_iter(),
         Buffer = FreeVarReference::EcmaScriptModule {
             request: "node:buffer".to_string(),
             lookup_path: None,
@@ -96,27 +98,83 @@ async fn next_client_free_vars(mode: NextMode) -> Result<Vc<FreeVarReferences>>
             lookup_path: None,
             export: Some("default".to_string()),
         }
-    )
-    .cell())
+    );
+
+    for (name, item) in next_config.free_vars().await?.iter() {
+        let key: Vec<String> = name.split('.').map(|part| part.to_string()).collect();
+        if free_vars.0.contains_key(&key) {
+            FreeVarConflictIssue {
+                path: project_path,
+                name: name.clone(),
+            }
+            .cell()
+            .emit();
+            continue;
+        }
+        let (request, export) = free_var_request(item);
+        free_vars.0.insert(
+            key,
+            FreeVarReference::EcmaScriptModule {
+                request,
+                lookup_path: Some(project_path),
+                export,
+            },
+        );
+    }
+
+    Ok(free_vars.cell())
+}
+
+/// The module and export a configured free variable is replaced with.
+fn free_var_request(item: &FreeVarConfigItem) -> (String, Option<String>) {
+    match item {
+        FreeVarConfigItem::Module(request) => (request.clone(), None),
+        FreeVarConfigItem::Export(request, export) => (request.clone(), Some(export.clone())),
+        // The module is CommonJS and exports the global object itself, so
+        // its exports are the properties of the global object.
+        FreeVarConfigItem::Global { global } => (
+            "next/dist/build/polyfills/global-object".to_string(),
+            Some(global.clone()),
+        ),
+    }
 }
 
 #[turbo_tasks::function]
-pub fn get_client_compile_time_info(
+pub async fn get_client_compile_time_info(
     mode: NextMode,
     browserslist_query: String,
-) -> Vc<CompileTimeInfo> {
-    CompileTimeInfo::builder(Environment::new(Value::new(ExecutionEnvironment::Browser(
+    project_path: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<CompileTimeInfo>> {
+    let environment = Environment::new(Value::new(ExecutionEnvironment::Browser(
         BrowserEnvironment {
             dom: true,
             web_worker: false,
             service_worker: false,
-            browserslist_query: browserslist_query.to_owned(),
+            browserslist_query: browserslist_query.clone(),
         }
         .into(),
-    ))))
-    .defines(next_client_defines(mode))
-    .free_var_references(next_client_free_vars(mode))
-    .cell()
+    )));
+
+    // A query that matches no browsers is treated as "any target", so nothing
+    // is transpiled.
+    if environment.runtime_versions().await?.is_any_target() {
+        NoBrowserTargetsIssue {
+            path: project_path,
+            query: browserslist_query,
+        }
+        .cell()
+        .emit();
+    }
+
+    Ok(CompileTimeInfo::builder(environment)
+        .defines(next_client_defines(next_config.node_env(mode)))
+        .free_var_references(next_client_free_vars(
+            next_config.node_env(mode),
+            project_path,
+            next_config,
+        ))
+        .cell())
 }
 
 #[turbo_tasks::value(serialization = "auto_for_input")]
@@ -138,7 +196,7 @@ pub async fn get_client_resolve_options_context(
 ) -> Result<Vc<ResolveOptionsContext>> {
     let next_client_import_map =
         get_next_client_import_map(project_path, ty, mode, next_config, execution_context);
-    let next_client_fallback_import_map = get_next_client_fallback_import_map(ty);
+    let next_client_fallback_import_map = get_next_client_fallback_import_map(ty, next_config);
     let next_client_resolved_map = get_next_client_resolved_map(project_path, project_path, mode);
     let module_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
@@ -194,9 +252,17 @@ pub async fn get_client_module_options_context(
     };
     let jsx_runtime_options =
         get_jsx_transform_options(project_path, mode, Some(resolve_options_context));
+    let webpack_rules = *maybe_add_babel_loader(
+        project_path,
+        *next_config.webpack_rules().await?,
+        next_config.suppress_babel_warning(),
+    )
+    .await?;
     let webpack_rules =
-        *maybe_add_babel_loader(project_path, *next_config.webpack_rules().await?).await?;
-    let webpack_rules = maybe_add_sass_loader(next_config.sass_config(), webpack_rules).await?;
+        *maybe_add_sass_loader(project_path, next_config.sass_config(), webpack_rules).await?;
+    let webpack_rules = *maybe_add_less_loader(next_config.less_config(), webpack_rules).await?;
+    let webpack_rules =
+        maybe_add_stylus_loader(project_path, next_config.stylus_config(), webpack_rules).await?;
     let enable_webpack_loaders = webpack_rules.map(|rules| {
         WebpackLoadersOptions {
             rules,
@@ -207,25 +273,49 @@ pub async fn get_client_module_options_context(
         .cell()
     });
 
-    let source_transforms = vec![
+    let global_css_check = match ty.into_value() {
+        ClientContextType::Pages { pages_dir } => {
+            *get_global_css_check_plugin(
+                project_path,
+                pages_dir,
+                resolve_options_context,
+                next_config,
+            )
+            .await?
+        }
+        // The app directory allows global CSS imports from any module.
+        ClientContextType::App { .. } | ClientContextType::Fallback | ClientContextType::Other => {
+            None
+        }
+    };
+
+    let source_transforms: Vec<_> = vec![
         *get_swc_ecma_transform_plugin(project_path, next_config).await?,
         *get_relay_transform_plugin(next_config).await?,
         *get_emotion_transform_plugin(next_config).await?,
         *get_styled_components_transform_plugin(next_config).await?,
         *get_styled_jsx_transform_plugin().await?,
-        Some(Vc::cell(Box::new(ServerDirectiveTransformer::new(
-            // ServerDirective is not implemented yet and always reports an issue.
-            // We don't have to pass a valid transition name yet, but the API is prepared.
-            &Vc::cell("TODO".to_string()),
-        )) as _)),
+        *get_node_polyfills_warning_plugin(project_path, next_config).await?,
+        global_css_check,
     ]
     .into_iter()
     .flatten()
     .collect();
 
+    let foreign_ecma_transform_plugins = Some(CustomEcmascriptTransformPlugins::cell(
+        CustomEcmascriptTransformPlugins {
+            source_transforms: source_transforms.clone(),
+            output_transforms: vec![],
+        },
+    ));
+    // Import attributes are only checked in the project's own code, packages
+    // can't be fixed by the user.
     let custom_ecma_transform_plugins = Some(CustomEcmascriptTransformPlugins::cell(
         CustomEcmascriptTransformPlugins {
-            source_transforms,
+            source_transforms: source_transforms
+                .into_iter()
+                .chain(*get_import_attributes_check_plugin(project_path).await?)
+                .collect(),
             output_transforms: vec![],
         },
     ));
@@ -238,7 +328,7 @@ pub async fn get_client_module_options_context(
     let module_options_context = ModuleOptionsContext {
         preset_env_versions: Some(env),
         execution_context: Some(execution_context),
-        custom_ecma_transform_plugins,
+        custom_ecma_transform_plugins: foreign_ecma_transform_plugins,
         // NOTE(WEB-1016) PostCSS transforms should also apply to foreign code.
         enable_postcss_transform: postcss_transform_options.clone(),
         ..Default::default()
@@ -254,6 +344,7 @@ pub async fn get_client_module_options_context(
         enable_typescript_transform: Some(tsconfig),
         enable_mdx_rs,
         decorators: Some(decorators_options),
+        custom_ecma_transform_plugins,
         rules: vec![
             (
                 foreign_code_context_condition(next_config, project_path).await?,
@@ -280,12 +371,14 @@ pub async fn get_client_module_options_context(
 }
 
 #[turbo_tasks::function]
-pub fn get_client_chunking_context(
+pub async fn get_client_chunking_context(
     project_path: Vc<FileSystemPath>,
     client_root: Vc<FileSystemPath>,
     environment: Vc<Environment>,
     mode: NextMode,
-) -> Vc<Box<dyn EcmascriptChunkingContext>> {
+    enable_hmr: Option<bool>,
+    source_maps: Vc<SourceMapLevel>,
+) -> Result<Vc<Box<dyn EcmascriptChunkingContext>>> {
     let output_root = match mode {
         NextMode::DevServer => client_root,
         NextMode::Development | NextMode::Build => client_root.join("_next".to_string()),
@@ -296,17 +389,31 @@ pub fn get_client_chunking_context(
         client_root.join("_next/static/chunks".to_string()),
         get_client_assets_path(client_root),
         environment,
-    );
+    )
+    .reference_chunk_source_maps(*source_maps.await? != SourceMapLevel::None);
 
     let builder = match mode {
-        NextMode::DevServer => builder.hot_module_replacement(),
-        NextMode::Development => builder
-            .hot_module_replacement()
-            .chunk_base_path(Vc::cell(Some("_next/".to_string()))),
-        NextMode::Build => builder.chunk_base_path(Vc::cell(Some("_next/".to_string()))),
+        NextMode::DevServer => builder,
+        NextMode::Development | NextMode::Build => {
+            builder.chunk_base_path(Vc::cell(Some("_next/".to_string())))
+        }
+    };
+    let builder = if client_hmr_enabled(mode, enable_hmr) {
+        builder.hot_module_replacement()
+    } else {
+        builder
     };
 
-    Vc::upcast(builder.build())
+    Ok(Vc::upcast(builder.build()))
+}
+
+/// HMR is enabled in the development modes, unless `enable_hmr` overrides it,
+/// e.g. to measure its overhead.
+fn client_hmr_enabled(mode: NextMode, enable_hmr: Option<bool>) -> bool {
+    enable_hmr.unwrap_or(match mode {
+        NextMode::DevServer | NextMode::Development => true,
+        NextMode::Build => false,
+    })
 }
 
 #[turbo_tasks::function]
@@ -338,15 +445,21 @@ pub async fn get_client_runtime_entries(
         );
     }
 
+    let resolve_options_context =
+        get_client_resolve_options_context(project_root, ty, mode, next_config, execution_context);
+    let custom_entries =
+        custom_runtime_entries(project_root, resolve_options_context, next_config).await?;
+    let custom_entries_at = |position: RuntimeEntryPosition| {
+        custom_entries
+            .iter()
+            .filter(move |(p, _)| *p == position)
+            .map(|(_, entry)| *entry)
+    };
+
+    runtime_entries.extend(custom_entries_at(RuntimeEntryPosition::BeforeRefresh));
+
     match mode {
         NextMode::DevServer => {
-            let resolve_options_context = get_client_resolve_options_context(
-                project_root,
-                ty,
-                mode,
-                next_config,
-                execution_context,
-            );
             let enable_react_refresh =
                 assert_can_resolve_react_refresh(project_root, resolve_options_context)
                     .await?
@@ -359,15 +472,9 @@ pub async fn get_client_runtime_entries(
                 runtime_entries
                     .push(RuntimeEntry::Request(request, project_root.join("_".to_string())).cell())
             };
+            runtime_entries.extend(custom_entries_at(RuntimeEntryPosition::AfterRefresh));
         }
         NextMode::Development => {
-            let resolve_options_context = get_client_resolve_options_context(
-                project_root,
-                ty,
-                mode,
-                next_config,
-                execution_context,
-            );
             let enable_react_refresh =
                 assert_can_resolve_react_refresh(project_root, resolve_options_context)
                     .await?
@@ -380,6 +487,7 @@ pub async fn get_client_runtime_entries(
                 runtime_entries
                     .push(RuntimeEntry::Request(request, project_root.join("_".to_string())).cell())
             };
+            runtime_entries.extend(custom_entries_at(RuntimeEntryPosition::AfterRefresh));
 
             if matches!(*ty, ClientContextType::App { .. },) {
                 runtime_entries.push(
@@ -393,32 +501,232 @@ pub async fn get_client_runtime_entries(
                 );
             }
         }
-        NextMode::Build => match *ty {
-            ClientContextType::App { .. } => {
-                runtime_entries.push(
-                    RuntimeEntry::Request(
-                        Request::parse(Value::new(Pattern::Constant(
-                            "./build/client/app-bootstrap.ts".to_string(),
-                        ))),
-                        next_js_fs().root().join("_".to_string()),
-                    )
-                    .cell(),
-                );
-            }
-            ClientContextType::Pages { .. } => {
-                runtime_entries.push(
-                    RuntimeEntry::Request(
-                        Request::parse(Value::new(Pattern::Constant(
-                            "./build/client/bootstrap.ts".to_string(),
-                        ))),
-                        next_js_fs().root().join("_".to_string()),
-                    )
-                    .cell(),
-                );
+        NextMode::Build => {
+            runtime_entries.extend(custom_entries_at(RuntimeEntryPosition::AfterRefresh));
+            match *ty {
+                ClientContextType::App { .. } => {
+                    runtime_entries.push(
+                        RuntimeEntry::Request(
+                            Request::parse(Value::new(Pattern::Constant(
+                                "./build/client/app-bootstrap.ts".to_string(),
+                            ))),
+                            next_js_fs().root().join("_".to_string()),
+                        )
+                        .cell(),
+                    );
+                }
+                ClientContextType::Pages { .. } => {
+                    runtime_entries.push(
+                        RuntimeEntry::Request(
+                            Request::parse(Value::new(Pattern::Constant(
+                                "./build/client/bootstrap.ts".to_string(),
+                            ))),
+                            next_js_fs().root().join("_".to_string()),
+                        )
+                        .cell(),
+                    );
+                }
+                _ => {}
             }
-            _ => {}
-        },
+        }
     }
 
+    runtime_entries.extend(custom_entries_at(RuntimeEntryPosition::Last));
+
     Ok(Vc::cell(runtime_entries))
 }
+
+/// The `experimental.turbo.runtimeEntries` with their positions. Requests
+/// which can't be resolved are reported and skipped.
+async fn custom_runtime_entries(
+    project_root: Vc<FileSystemPath>,
+    resolve_options_context: Vc<ResolveOptionsContext>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vec<(RuntimeEntryPosition, Vc<RuntimeEntry>)>> {
+    let mut entries = vec![];
+    for entry in next_config.runtime_entries().await?.iter() {
+        let request = Request::parse(Value::new(Pattern::Constant(entry.request.clone())));
+        let sources = resolve(
+            project_root,
+            request,
+            resolve_options(project_root, resolve_options_context),
+        )
+        .primary_sources()
+        .await?;
+        if sources.is_empty() {
+            UnresolvableRuntimeEntryIssue {
+                path: project_root,
+                request: entry.request.clone(),
+            }
+            .cell()
+            .emit();
+            continue;
+        }
+        entries.push((
+            entry.position,
+            RuntimeEntry::Request(request, project_root.join("_".to_string())).cell(),
+        ));
+    }
+    Ok(entries)
+}
+
+#[turbo_tasks::value(shared)]
+struct FreeVarConflictIssue {
+    path: Vc<FileSystemPath>,
+    name: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for FreeVarConflictIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The free variable \"{}\" in experimental.turbo.freeVars is ignored",
+            self.name
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "Next.js already provides \"{}\" in client code, which takes precedence over the \
+             configured module.",
+            self.name
+        ))
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct UnresolvableRuntimeEntryIssue {
+    path: Vc<FileSystemPath>,
+    request: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnresolvableRuntimeEntryIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The runtime entry \"{}\" in experimental.turbo.runtimeEntries can't be resolved",
+            self.request
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(
+            "Runtime entries are resolved from the project directory. The entry is skipped."
+                .to_string(),
+        )
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct NoBrowserTargetsIssue {
+    path: Vc<FileSystemPath>,
+    query: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for NoBrowserTargetsIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The browserslist query \"{}\" matches no browsers",
+            self.query
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(
+            "Client code is not transpiled for any browser, so it may not run in the browsers you \
+             support. Broaden the query to match the browsers you target."
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{client_hmr_enabled, free_var_request};
+    use crate::{mode::NextMode, next_config::FreeVarConfigItem};
+
+    #[test]
+    fn hmr_follows_mode_by_default() {
+        assert!(client_hmr_enabled(NextMode::DevServer, None));
+        assert!(client_hmr_enabled(NextMode::Development, None));
+        assert!(!client_hmr_enabled(NextMode::Build, None));
+    }
+
+    #[test]
+    fn hmr_can_be_disabled_in_dev() {
+        assert!(!client_hmr_enabled(NextMode::DevServer, Some(false)));
+        assert!(!client_hmr_enabled(NextMode::Development, Some(false)));
+        assert!(client_hmr_enabled(NextMode::Build, Some(true)));
+    }
+
+    #[test]
+    fn free_vars_are_parsed_from_each_form() {
+        let free_vars: Vec<FreeVarConfigItem> =
+            serde_json::from_str(r#"["buffer", ["buffer", "Buffer"], { "global": "globalThis" }]"#)
+                .unwrap();
+        let requests: Vec<_> = free_vars.iter().map(free_var_request).collect();
+        assert_eq!(
+            requests,
+            vec![
+                ("buffer".to_string(), None),
+                ("buffer".to_string(), Some("Buffer".to_string())),
+                (
+                    "next/dist/build/polyfills/global-object".to_string(),
+                    Some("globalThis".to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
     next_shared::transforms::{
         get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
         get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+        get_server_actions_in_pages_rule, get_server_actions_transform_rule,
     },
 };
 
@@ -34,11 +35,14 @@ pub async fn get_next_client_transforms_rules(
             rules.push(
                 get_next_pages_transforms_rule(pages_dir, ExportFilter::StripDataExports).await?,
             );
+            rules.push(get_server_actions_in_pages_rule(pages_dir));
             Some(pages_dir)
         }
-        ClientContextType::App { .. } | ClientContextType::Fallback | ClientContextType::Other => {
+        ClientContextType::App { .. } => {
+            rules.push(get_server_actions_transform_rule(false));
             None
         }
+        ClientContextType::Fallback | ClientContextType::Other => None,
     };
 
     rules.push(get_next_dynamic_transform_rule(false, false, pages_dir, mode).await?);
@@ -1,16 +1,20 @@
 use anyhow::{Context, Result};
-use indexmap::IndexMap;
+use indexmap::{indexmap, IndexMap};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use turbo_tasks::{trace::TraceRawVcs, Completion, Value, Vc};
 use turbo_tasks_fs::json::parse_json_with_source_context;
 use turbopack_binding::{
-    turbo::{tasks_env::EnvMap, tasks_fs::FileSystemPath},
+    turbo::{
+        tasks_env::EnvMap,
+        tasks_fs::{FileSystemEntryType, FileSystemPath},
+    },
     turbopack::{
         core::{
             changed::any_content_changed_of_module,
             chunk::ChunkingContext,
             context::AssetContext,
+            error::PrettyPrintError,
             file_source::FileSource,
             ident::AssetIdent,
             issue::{Issue, IssueDescriptionExt, IssueExt, IssueSeverity},
@@ -39,7 +43,10 @@ use turbopack_binding::{
     },
 };
 
-use crate::{embed_js::next_asset, next_shared::transforms::ModularizeImportPackageConfig};
+use crate::{
+    embed_js::next_asset, mode::NextMode, next_shared::transforms::ModularizeImportPackageConfig,
+    util::DEFAULT_DIST_DIR,
+};
 
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -84,6 +91,8 @@ pub struct NextConfig {
     pub transpile_packages: Option<Vec<String>>,
     pub modularize_imports: Option<IndexMap<String, ModularizeImportPackageConfig>>,
     sass_options: Option<serde_json::Value>,
+    less_options: Option<serde_json::Value>,
+    stylus_options: Option<serde_json::Value>,
     trailing_slash: bool,
 
     // Partially supported
@@ -380,8 +389,112 @@ pub struct ExperimentalTurboConfig {
     pub loaders: Option<JsonValue>,
     pub rules: Option<IndexMap<String, RuleConfigItem>>,
     pub resolve_alias: Option<IndexMap<String, JsonValue>>,
+    pub suppress_babel_warning: Option<bool>,
+    pub warn_on_node_polyfills: Option<bool>,
+    pub allow_global_css_anywhere: Option<bool>,
+    pub report_node_builtins: Option<bool>,
+    pub source_maps: Option<SourceMapLevel>,
+    pub free_vars: Option<IndexMap<String, FreeVarConfigItem>>,
+    pub runtime_config: Option<RuntimeConfigMode>,
+    pub runtime_entries: Option<Vec<RuntimeEntryConfig>>,
+    pub node_env: Option<String>,
 }
 
+/// How `publicRuntimeConfig` and `serverRuntimeConfig` reach the rendering
+/// process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuntimeConfigMode {
+    /// The values are sent along with every render request and end up in
+    /// `__NEXT_DATA__.runtimeConfig`, so they can change without a rebuild.
+    #[default]
+    Request,
+    /// The values are compiled into the server bundles, like `env`.
+    Inline,
+}
+
+/// The runtime config values of `next.config.js`.
+#[turbo_tasks::value(serialization = "custom", eq = "manual")]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeConfig {
+    #[serde(skip)]
+    pub mode: RuntimeConfigMode,
+    pub public_runtime_config: IndexMap<String, JsonValue>,
+    pub server_runtime_config: IndexMap<String, JsonValue>,
+}
+
+/// A module which replaces a free variable in client code, like webpack's
+/// `ProvidePlugin`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(untagged)]
+pub enum FreeVarConfigItem {
+    /// The namespace of the module.
+    Module(String),
+    /// An export of the module, as `[module, export]`.
+    Export(String, String),
+    /// A property of the global object, as `{ global: "name" }`, e.g. to make
+    /// `global` refer to `globalThis`.
+    Global { global: String },
+}
+
+/// A module which the client evaluates on startup, next to the Next.js
+/// bootstrap, e.g. to initialize error monitoring or to always include a
+/// conditionally loaded polyfill. Runtime entries are part of every page's
+/// bootstrap chunks, so they add to the size of every page.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeEntryConfig {
+    pub request: String,
+    #[serde(default)]
+    pub position: RuntimeEntryPosition,
+}
+
+/// Where a [RuntimeEntryConfig] is evaluated relative to the runtime entries
+/// Next.js adds itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuntimeEntryPosition {
+    /// Before React Refresh, i.e. before any other module.
+    BeforeRefresh,
+    /// After React Refresh, but before the Next.js bootstrap.
+    AfterRefresh,
+    /// After the Next.js bootstrap.
+    #[default]
+    Last,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct RuntimeEntriesConfig(Vec<RuntimeEntryConfig>);
+
+/// `experimental.turbo.sourceMaps`, whether client chunks have source maps.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default, Copy, Clone, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SourceMapLevel {
+    /// Client chunks don't reference source maps, so none are generated and
+    /// original stack frames of client errors are unavailable. This makes
+    /// compilation faster.
+    None,
+    /// The dev server serves line-only source maps without the original
+    /// sources, which are smaller and faster for the browser to load. Original
+    /// stack frames in the error overlay are still traced with full maps.
+    Cheap,
+    /// Source maps with columns and the original sources.
+    #[default]
+    Full,
+}
+
+/// `experimental.turbo.freeVars`, mapping a free variable to the module that
+/// provides it.
+#[turbo_tasks::value(transparent)]
+pub struct FreeVarsConfig(IndexMap<String, FreeVarConfigItem>);
+
+/// Loader rules which are not part of `next.config.js`, mapping a glob to the
+/// names of the loaders applied to matching files.
+#[turbo_tasks::value(transparent)]
+pub struct ExtraLoaderRules(IndexMap<String, Vec<String>>);
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum RuleConfigItem {
@@ -409,6 +522,7 @@ pub struct ExperimentalConfig {
     pub fetch_cache_key_prefix: Option<String>,
     pub isr_memory_cache_size: Option<f64>,
     pub isr_flush_to_disk: Option<bool>,
+    pub instrumentation_hook: Option<bool>,
     mdx_rs: Option<bool>,
     pub swc_plugins: Option<Vec<(String, serde_json::Value)>>,
 
@@ -495,6 +609,45 @@ pub enum RemoveConsoleConfig {
     Config { exclude: Option<Vec<String>> },
 }
 
+impl NextConfig {
+    /// The config Next.js uses when a project has no config file, see
+    /// `defaultConfig` in packages/next/src/server/config-shared.ts.
+    fn next_default() -> Self {
+        NextConfig {
+            config_file_name: "next.config.js".to_string(),
+            page_extensions: ["tsx", "ts", "jsx", "js"]
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+            clean_dist_dir: true,
+            compress: true,
+            dev_indicators: DevIndicatorsConfig {
+                build_activity: true,
+                build_activity_position: BuildActivityPositions::BottomRight,
+            },
+            dist_dir: DEFAULT_DIST_DIR.to_string(),
+            exclude_default_moment_locales: true,
+            generate_etags: true,
+            http_agent_options: HttpAgentConfig { keep_alive: true },
+            on_demand_entries: OnDemandEntriesConfig {
+                max_inactive_age: 60.0 * 1000.0,
+                pages_buffer_length: 5.0,
+            },
+            optimize_fonts: true,
+            output_file_tracing: true,
+            powered_by_header: true,
+            static_page_generation_timeout: 60.0,
+            swc_minify: Some(true),
+            typescript: TypeScriptConfig {
+                ignore_build_errors: Some(false),
+                ts_config_path: Some("tsconfig.json".to_string()),
+            },
+            use_file_system_public_routes: true,
+            ..Default::default()
+        }
+    }
+}
+
 #[turbo_tasks::value_impl]
 impl NextConfig {
     #[turbo_tasks::function]
@@ -547,6 +700,22 @@ impl NextConfig {
         Ok(self.await?.images.clone().cell())
     }
 
+    #[turbo_tasks::function]
+    pub async fn runtime_config(self: Vc<Self>) -> Result<Vc<RuntimeConfig>> {
+        let this = self.await?;
+        Ok(RuntimeConfig {
+            mode: this
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.runtime_config)
+                .unwrap_or_default(),
+            public_runtime_config: this.public_runtime_config.clone(),
+            server_runtime_config: this.server_runtime_config.clone(),
+        }
+        .cell())
+    }
+
     #[turbo_tasks::function]
     pub async fn page_extensions(self: Vc<Self>) -> Result<Vc<Vec<String>>> {
         Ok(Vc::cell(self.await?.page_extensions.clone()))
@@ -605,6 +774,147 @@ impl NextConfig {
         Ok(Vc::cell(Some(Vc::cell(rules))))
     }
 
+    #[turbo_tasks::function]
+    pub async fn suppress_babel_warning(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.suppress_babel_warning)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn runtime_entries(self: Vc<Self>) -> Result<Vc<RuntimeEntriesConfig>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.runtime_entries.clone())
+                .unwrap_or_default(),
+        ))
+    }
+
+    /// The value `process.env.NODE_ENV` is replaced with, which defaults to
+    /// the one of `mode`. Resolve conditions and the React JSX runtime still
+    /// follow `mode`.
+    #[turbo_tasks::function]
+    pub async fn node_env(self: Vc<Self>, mode: NextMode) -> Result<Vc<String>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.node_env.clone())
+                .unwrap_or_else(|| mode.node_env().to_string()),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn free_vars(self: Vc<Self>) -> Result<Vc<FreeVarsConfig>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.free_vars.clone())
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn warn_on_node_polyfills(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.warn_on_node_polyfills)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn allow_global_css_anywhere(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.allow_global_css_anywhere)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn client_source_maps(self: Vc<Self>) -> Result<Vc<SourceMapLevel>> {
+        Ok(self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|t| t.source_maps)
+            .unwrap_or_default()
+            .cell())
+    }
+
+    #[turbo_tasks::function]
+    pub async fn instrumentation_hook(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .instrumentation_hook
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn report_node_builtins(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.report_node_builtins)
+                .unwrap_or(false),
+        ))
+    }
+
+    /// Returns a config with `rules` added to `experimental.turbo.rules`. A
+    /// rule replaces the configured rule for the same glob.
+    #[turbo_tasks::function]
+    pub async fn with_extra_loader_rules(
+        self: Vc<Self>,
+        rules: Vc<ExtraLoaderRules>,
+    ) -> Result<Vc<NextConfig>> {
+        let rules = rules.await?;
+        if rules.is_empty() {
+            return Ok(self);
+        }
+        let mut this = self.await?.clone_value();
+        let turbo_rules = this
+            .experimental
+            .turbo
+            .get_or_insert_with(Default::default)
+            .rules
+            .get_or_insert_with(Default::default);
+        for (glob, loaders) in rules.iter() {
+            turbo_rules.insert(
+                glob.clone(),
+                RuleConfigItem::Loaders(
+                    loaders
+                        .iter()
+                        .map(|loader| LoaderItem::LoaderName(loader.clone()))
+                        .collect(),
+                ),
+            );
+        }
+        Ok(this.cell())
+    }
+
     #[turbo_tasks::function]
     pub async fn resolve_alias_options(self: Vc<Self>) -> Result<Vc<ResolveAliasMap>> {
         let this = self.await?;
@@ -620,6 +930,47 @@ impl NextConfig {
         Ok(alias_map.cell())
     }
 
+    /// The configured `distDir`, relative to the project directory.
+    #[turbo_tasks::function]
+    pub async fn dist_dir(self: Vc<Self>) -> Result<Vc<String>> {
+        let dist_dir = &self.await?.dist_dir;
+        Ok(Vc::cell(if dist_dir.is_empty() {
+            DEFAULT_DIST_DIR.to_string()
+        } else {
+            dist_dir.clone()
+        }))
+    }
+
+    /// The configured `basePath`, empty when the app is served from the root.
+    #[turbo_tasks::function]
+    pub async fn base_path(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(Vc::cell(self.await?.base_path.clone()))
+    }
+
+    /// The configured `assetPrefix`, empty when the assets are served from the
+    /// root.
+    #[turbo_tasks::function]
+    pub async fn asset_prefix(self: Vc<Self>) -> Result<Vc<String>> {
+        Ok(Vc::cell(self.await?.asset_prefix.clone()))
+    }
+
+    /// Returns a config for an app which is served under `/{prefix}`. Both the
+    /// `basePath` and the `assetPrefix` are replaced, so the pages link their
+    /// scripts under the prefix.
+    #[turbo_tasks::function]
+    pub async fn with_mount_prefix(self: Vc<Self>, prefix: String) -> Result<Vc<NextConfig>> {
+        let mut this = self.await?.clone_value();
+        this.base_path = format!("/{prefix}");
+        this.asset_prefix = this.base_path.clone();
+        Ok(this.cell())
+    }
+
+    /// Whether `i18n` routing is configured.
+    #[turbo_tasks::function]
+    pub async fn has_i18n(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(self.await?.i18n.is_some()))
+    }
+
     #[turbo_tasks::function]
     pub async fn mdx_rs(self: Vc<Self>) -> Result<Vc<bool>> {
         Ok(Vc::cell(self.await?.experimental.mdx_rs.unwrap_or(false)))
@@ -632,6 +983,20 @@ impl NextConfig {
         ))
     }
 
+    #[turbo_tasks::function]
+    pub async fn less_config(self: Vc<Self>) -> Result<Vc<JsonValue>> {
+        Ok(Vc::cell(
+            self.await?.less_options.clone().unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn stylus_config(self: Vc<Self>) -> Result<Vc<JsonValue>> {
+        Ok(Vc::cell(
+            self.await?.stylus_options.clone().unwrap_or_default(),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn swc_minify(self: Vc<Self>) -> Result<Vc<bool>> {
         Ok(Vc::cell(self.await?.swc_minify.unwrap_or(false)))
@@ -652,9 +1017,14 @@ impl NextConfig {
     }
 }
 
-fn next_configs() -> Vc<Vec<String>> {
+/// The config file names, in order of precedence when several exist. This
+/// matches the order in which Next.js looks for `next.config.js` and
+/// `next.config.mjs`. `next.config.ts` isn't supported by Next.js itself and is
+/// compiled by Turbopack instead.
+#[turbo_tasks::function]
+pub(crate) fn next_configs() -> Vc<Vec<String>> {
     Vc::cell(
-        ["next.config.mjs", "next.config.js"]
+        ["next.config.js", "next.config.mjs", "next.config.ts"]
             .into_iter()
             .map(ToOwned::to_owned)
             .collect(),
@@ -677,6 +1047,63 @@ pub async fn load_rewrites(execution_context: Vc<ExecutionContext>) -> Result<Vc
         .rewrites)
 }
 
+/// Like [load_next_config], but when the config fails to load, the error is
+/// reported as an issue and the default config is used instead. This allows
+/// the dev server to start and show the error in the overlay.
+#[turbo_tasks::function]
+pub async fn load_next_config_or_default(
+    execution_context: Vc<ExecutionContext>,
+) -> Result<Vc<NextConfig>> {
+    Ok(load_config_and_custom_routes_or_default(execution_context)
+        .await?
+        .config)
+}
+
+/// Like [load_rewrites], but falls back to no rewrites when the config fails
+/// to load. See [load_next_config_or_default].
+#[turbo_tasks::function]
+pub async fn load_rewrites_or_default(
+    execution_context: Vc<ExecutionContext>,
+) -> Result<Vc<Rewrites>> {
+    Ok(load_config_and_custom_routes_or_default(execution_context)
+        .await?
+        .custom_routes
+        .await?
+        .rewrites)
+}
+
+#[turbo_tasks::function]
+async fn load_config_and_custom_routes_or_default(
+    execution_context: Vc<ExecutionContext>,
+) -> Result<Vc<NextConfigAndCustomRoutes>> {
+    let config_and_custom_routes = load_config_and_custom_routes(execution_context);
+    let err = match config_and_custom_routes.await {
+        Ok(_) => return Ok(config_and_custom_routes),
+        Err(err) => err,
+    };
+
+    let ExecutionContext { project_path, .. } = *execution_context.await?;
+    let path = match &*find_context_file(project_path, next_configs()).await? {
+        FindContextFileResult::Found(config_path, _) => *config_path,
+        FindContextFileResult::NotFound(_) => project_path,
+    };
+    NextConfigLoadIssue {
+        path,
+        description: PrettyPrintError(&err).to_string(),
+    }
+    .cell()
+    .emit();
+
+    Ok(NextConfigAndCustomRoutes {
+        config: NextConfig::next_default().cell(),
+        custom_routes: CustomRoutes {
+            rewrites: Rewrites::default().cell(),
+        }
+        .cell(),
+    }
+    .cell())
+}
+
 #[turbo_tasks::function]
 async fn load_config_and_custom_routes(
     execution_context: Vc<ExecutionContext>,
@@ -687,12 +1114,41 @@ async fn load_config_and_custom_routes(
         FindContextFileResult::Found(config_path, _) => Some(*config_path),
         FindContextFileResult::NotFound(_) => None,
     };
+    if let Some(config_file) = config_file {
+        warn_about_other_configs(config_file).await?;
+    }
 
     load_next_config_and_custom_routes_internal(execution_context, config_file)
         .issue_file_path(config_file, "Loading Next.js config")
         .await
 }
 
+/// Warns when the directory of the used config file contains other config
+/// files, which are ignored.
+async fn warn_about_other_configs(config_file: Vc<FileSystemPath>) -> Result<()> {
+    let used = config_file.await?;
+    let dir = config_file.parent();
+    let mut ignored = Vec::new();
+    for name in next_configs().await?.iter() {
+        if *name == used.file_name() {
+            continue;
+        }
+        if let FileSystemEntryType::File = &*dir.join(name.clone()).get_type().await? {
+            ignored.push(name.clone());
+        }
+    }
+    if !ignored.is_empty() {
+        AmbiguousNextConfigIssue {
+            path: config_file,
+            used: used.file_name().to_string(),
+            ignored,
+        }
+        .cell()
+        .emit();
+    }
+    Ok(())
+}
+
 #[turbo_tasks::function]
 async fn load_next_config_and_custom_routes_internal(
     execution_context: Vc<ExecutionContext>,
@@ -712,20 +1168,36 @@ async fn load_next_config_and_custom_routes_internal(
 
     let context = node_evaluate_asset_context(execution_context, Some(import_map.cell()), None);
     let config_asset = config_file.map(FileSource::new);
-
-    let config_changed = config_asset.map_or_else(Completion::immutable, |config_asset| {
-        // This invalidates the execution when anything referenced by the config file
-        // changes
-        let config_asset = context.process(
+    let config_module = config_asset.map(|config_asset| {
+        context.process(
             Vc::upcast(config_asset),
             Value::new(ReferenceType::Internal(InnerAssets::empty())),
-        );
-        any_content_changed_of_module(config_asset)
+        )
+    });
+
+    // This invalidates the execution when anything referenced by the config file
+    // changes
+    let config_changed = config_module.map_or_else(Completion::immutable, |config_module| {
+        any_content_changed_of_module(config_module)
     });
-    let load_next_config_asset = context.process(
-        next_asset("entry/config/next.js".to_string()),
-        Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
-    );
+    let is_ts_config = match config_file {
+        Some(config_file) => config_file.await?.path.ends_with(".ts"),
+        None => false,
+    };
+    let load_next_config_asset = match config_module {
+        // Next.js can't load TypeScript configs, so the compiled config is passed
+        // to it instead.
+        Some(config_module) if is_ts_config => context.process(
+            next_asset("entry/config/next-compiled.js".to_string()),
+            Value::new(ReferenceType::Internal(Vc::cell(indexmap! {
+                "CONFIG".to_string() => config_module,
+            }))),
+        ),
+        _ => context.process(
+            next_asset("entry/config/next.js".to_string()),
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
+        ),
+    };
     let config_value = evaluate(
         load_next_config_asset,
         project_path,
@@ -862,3 +1334,85 @@ impl Issue for OutdatedConfigIssue {
         Vc::cell(self.description.to_string())
     }
 }
+
+#[turbo_tasks::value]
+struct AmbiguousNextConfigIssue {
+    path: Vc<FileSystemPath>,
+    used: String,
+    ignored: Vec<String>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for AmbiguousNextConfigIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "Found multiple Next.js config files, using \"{}\"",
+            self.used
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "Ignored {}. When several config files exist, next.config.js is used first, then \
+             next.config.mjs, then next.config.ts. Remove the unused files to avoid confusion.",
+            self.ignored
+                .iter()
+                .map(|name| format!("\"{name}\""))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
+
+#[turbo_tasks::value]
+struct NextConfigLoadIssue {
+    path: Vc<FileSystemPath>,
+    description: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for NextConfigLoadIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(
+            "Failed to load the Next.js config, falling back to the default config".to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(self.description.clone())
+    }
+}
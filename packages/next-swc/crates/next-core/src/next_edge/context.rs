@@ -4,7 +4,6 @@ use turbopack_binding::{
     turbo::tasks_fs::FileSystemPath,
     turbopack::{
         core::{
-            compile_time_defines,
             compile_time_info::{
                 CompileTimeDefines, CompileTimeInfo, FreeVarReference, FreeVarReferences,
             },
@@ -24,33 +23,32 @@ use crate::{
     next_config::NextConfig,
     next_import_map::get_next_edge_import_map,
     next_server::context::ServerContextType,
-    next_shared::resolve::{
-        ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
-        UnsupportedModulesResolvePlugin,
+    next_shared::{
+        defines::{defines as shared_defines, DefineEnv},
+        resolve::{
+            ModuleFeatureReportResolvePlugin, NextSharedRuntimeResolvePlugin,
+            UnsupportedModulesResolvePlugin,
+        },
     },
     util::foreign_code_context_condition,
 };
 
-fn defines() -> CompileTimeDefines {
-    compile_time_defines!(
-        process.turbopack = true,
-        process.env.NODE_ENV = "development",
-        process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
-        process.env.NEXT_RUNTIME = "edge"
-    )
-    // TODO(WEB-937) there are more defines needed, see
-    // packages/next/src/build/webpack-config.ts
+fn defines(node_env: &str) -> CompileTimeDefines {
+    shared_defines(DefineEnv::Edge, node_env)
 }
 
 #[turbo_tasks::function]
-fn next_edge_defines() -> Vc<CompileTimeDefines> {
-    defines().cell()
+async fn next_edge_defines(node_env: Vc<String>) -> Result<Vc<CompileTimeDefines>> {
+    Ok(defines(&node_env.await?).cell())
 }
 
 #[turbo_tasks::function]
-fn next_edge_free_vars(project_path: Vc<FileSystemPath>) -> Vc<FreeVarReferences> {
-    free_var_references!(
-        ..defines().into_iter(),
+async fn next_edge_free_vars(
+    node_env: Vc<String>,
+    project_path: Vc<FileSystemPath>,
+) -> Result<Vc<FreeVarReferences>> {
+    Ok(free_var_references!(
+        ..defines(&node_env.await?).into_iter(),
         Buffer = FreeVarReference::EcmaScriptModule {
             request: "next/dist/compiled/buffer".to_string(),
             lookup_path: Some(project_path),
@@ -62,19 +60,22 @@ fn next_edge_free_vars(project_path: Vc<FileSystemPath>) -> Vc<FreeVarReferences
             export: Some("default".to_string()),
         },
     )
-    .cell()
+    .cell())
 }
 
+/// `node_env` is the value of `process.env.NODE_ENV`, usually
+/// [NextConfig::node_env].
 #[turbo_tasks::function]
 pub fn get_edge_compile_time_info(
     project_path: Vc<FileSystemPath>,
     server_addr: Vc<ServerAddr>,
+    node_env: Vc<String>,
 ) -> Vc<CompileTimeInfo> {
     CompileTimeInfo::builder(Environment::new(Value::new(
         ExecutionEnvironment::EdgeWorker(EdgeWorkerEnvironment { server_addr }.into()),
     )))
-    .defines(next_edge_defines())
-    .free_var_references(next_edge_free_vars(project_path))
+    .defines(next_edge_defines(node_env))
+    .free_var_references(next_edge_free_vars(node_env, project_path))
     .cell()
 }
 
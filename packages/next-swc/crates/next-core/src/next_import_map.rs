@@ -29,6 +29,7 @@ use crate::{
         local::{NextFontLocalCssModuleReplacer, NextFontLocalReplacer},
     },
     next_server::context::ServerContextType,
+    next_shared::resolve::{UnsupportedNodeBuiltinReplacer, NODE_ONLY_BUILTINS},
     util::NextRuntime,
 };
 
@@ -117,6 +118,7 @@ pub async fn get_next_client_import_map(
                 "next/dynamic",
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
+            insert_server_actions_aliases(&mut import_map, project_path);
         }
         ClientContextType::Fallback => {}
         ClientContextType::Other => {}
@@ -163,9 +165,14 @@ pub fn get_next_build_import_map() -> Vc<ImportMap> {
 }
 
 /// Computes the Next-specific client fallback import map, which provides
-/// polyfills to Node.js externals.
+/// polyfills to Node.js externals. With `experimental.turbo.reportNodeBuiltins`
+/// the Node.js builtins without a polyfill are reported as unsupported
+/// modules.
 #[turbo_tasks::function]
-pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Vc<ImportMap> {
+pub async fn get_next_client_fallback_import_map(
+    ty: Value<ClientContextType>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<ImportMap>> {
     let mut import_map = ImportMap::empty();
 
     match ty.into_value() {
@@ -184,9 +191,18 @@ pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Vc<I
         ClientContextType::Other => {}
     }
 
+    if *next_config.report_node_builtins().await? {
+        let replacer: Vc<ImportMapping> =
+            ImportMapping::Dynamic(Vc::upcast(UnsupportedNodeBuiltinReplacer::new())).into();
+        for builtin in NODE_ONLY_BUILTINS {
+            import_map.insert_exact_alias(*builtin, replacer);
+            import_map.insert_exact_alias(format!("node:{builtin}"), replacer);
+        }
+    }
+
     insert_turbopack_dev_alias(&mut import_map);
 
-    import_map.cell()
+    Ok(import_map.cell())
 }
 
 /// Computes the Next-specific server-side import map.
@@ -261,6 +277,7 @@ pub async fn get_next_server_import_map(
                 "next/dynamic",
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
+            insert_server_actions_aliases(&mut import_map, project_path);
         }
         ServerContextType::Middleware => {}
     }
@@ -314,6 +331,7 @@ pub async fn get_next_edge_import_map(
                 "next/dynamic",
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
+            insert_server_actions_aliases(&mut import_map, project_path);
         }
         ServerContextType::Middleware => {}
     }
@@ -885,6 +903,27 @@ fn insert_turbopack_dev_alias(import_map: &mut ImportMap) {
 
 /// Creates a direct import mapping to the result of resolving a request
 /// in a context.
+/// Maps the modules which the server actions transform imports to their
+/// Next.js implementations.
+fn insert_server_actions_aliases(import_map: &mut ImportMap, project_path: Vc<FileSystemPath>) {
+    for (alias, module) in [
+        (
+            "private-next-rsc-action-client-wrapper",
+            "action-client-wrapper",
+        ),
+        ("private-next-rsc-action-proxy", "action-proxy"),
+        ("private-next-rsc-action-validate", "action-validate"),
+    ] {
+        import_map.insert_exact_alias(
+            alias,
+            request_to_import_mapping(
+                project_path,
+                &format!("next/dist/build/webpack/loaders/next-flight-loader/{module}"),
+            ),
+        );
+    }
+}
+
 fn request_to_import_mapping(context_path: Vc<FileSystemPath>, request: &str) -> Vc<ImportMapping> {
     ImportMapping::PrimaryAlternative(request.to_string(), Some(context_path)).cell()
 }
@@ -10,6 +10,7 @@ use self::{
 
 mod all;
 mod path_regex;
+pub(crate) mod path_to_regexp;
 mod prefix_suffix;
 
 /// A route matcher that matches a path against an exact route.
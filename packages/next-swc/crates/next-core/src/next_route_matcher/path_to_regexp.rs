@@ -0,0 +1,383 @@
+//! A port of the parts of `path-to-regexp@6.1.0` that Next.js uses to turn
+//! route sources like `/blog/:slug*` into regular expressions, so the
+//! expressions match the ones the runtime computes.
+
+use anyhow::{bail, Result};
+
+const DEFAULT_DELIMITER: &str = "/#?";
+const DEFAULT_PREFIXES: &str = "./";
+
+#[derive(Debug, PartialEq, Eq)]
+enum LexToken {
+    Open,
+    Close,
+    Pattern(String),
+    Name(String),
+    Char(char),
+    EscapedChar(char),
+    Modifier(char),
+}
+
+fn lexer(source: &str) -> Result<Vec<LexToken>> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let char = chars[i];
+        match char {
+            '*' | '+' | '?' => {
+                tokens.push(LexToken::Modifier(char));
+                i += 1;
+            }
+            '\\' => {
+                let Some(&escaped) = chars.get(i + 1) else {
+                    bail!("Unexpected end of source after \"\\\" at {i}");
+                };
+                tokens.push(LexToken::EscapedChar(escaped));
+                i += 2;
+            }
+            '{' => {
+                tokens.push(LexToken::Open);
+                i += 1;
+            }
+            '}' => {
+                tokens.push(LexToken::Close);
+                i += 1;
+            }
+            ':' => {
+                let mut j = i + 1;
+                let mut name = String::new();
+                while let Some(&c) = chars.get(j) {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    name.push(c);
+                    j += 1;
+                }
+                if name.is_empty() {
+                    bail!("Missing parameter name at {i}");
+                }
+                tokens.push(LexToken::Name(name));
+                i = j;
+            }
+            '(' => {
+                let mut count = 1;
+                let mut pattern = String::new();
+                let mut j = i + 1;
+                if chars.get(j) == Some(&'?') {
+                    bail!("Pattern cannot start with \"?\" at {j}");
+                }
+                while j < chars.len() {
+                    if chars[j] == '\\' {
+                        pattern.extend(chars[j..].iter().take(2));
+                        j += 2;
+                        continue;
+                    }
+                    if chars[j] == ')' {
+                        count -= 1;
+                        if count == 0 {
+                            j += 1;
+                            break;
+                        }
+                    } else if chars[j] == '(' {
+                        count += 1;
+                        if chars.get(j + 1) != Some(&'?') {
+                            bail!("Capturing groups are not allowed at {j}");
+                        }
+                    }
+                    pattern.push(chars[j]);
+                    j += 1;
+                }
+                if count != 0 {
+                    bail!("Unbalanced pattern at {i}");
+                }
+                if pattern.is_empty() {
+                    bail!("Missing pattern at {i}");
+                }
+                tokens.push(LexToken::Pattern(pattern));
+                i = j;
+            }
+            _ => {
+                tokens.push(LexToken::Char(char));
+                i += 1;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Path(String),
+    Key {
+        prefix: String,
+        suffix: String,
+        pattern: String,
+        modifier: String,
+    },
+}
+
+struct Parser {
+    tokens: Vec<LexToken>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&LexToken> {
+        self.tokens.get(self.index)
+    }
+
+    fn try_char(&mut self) -> Option<char> {
+        let Some(LexToken::Char(c)) = self.peek() else {
+            return None;
+        };
+        let c = *c;
+        self.index += 1;
+        Some(c)
+    }
+
+    fn try_escaped_char(&mut self) -> Option<char> {
+        let Some(LexToken::EscapedChar(c)) = self.peek() else {
+            return None;
+        };
+        let c = *c;
+        self.index += 1;
+        Some(c)
+    }
+
+    fn try_name(&mut self) -> Option<String> {
+        let Some(LexToken::Name(name)) = self.peek() else {
+            return None;
+        };
+        let name = name.clone();
+        self.index += 1;
+        Some(name)
+    }
+
+    fn try_pattern(&mut self) -> Option<String> {
+        let Some(LexToken::Pattern(pattern)) = self.peek() else {
+            return None;
+        };
+        let pattern = pattern.clone();
+        self.index += 1;
+        Some(pattern)
+    }
+
+    fn try_modifier(&mut self) -> String {
+        let Some(LexToken::Modifier(modifier)) = self.peek() else {
+            return String::new();
+        };
+        let modifier = modifier.to_string();
+        self.index += 1;
+        modifier
+    }
+
+    fn consume_text(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.try_char().or_else(|| self.try_escaped_char()) {
+            text.push(c);
+        }
+        text
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Token>> {
+    let default_pattern = format!("[^{}]+?", escape_string(DEFAULT_DELIMITER));
+    let mut parser = Parser {
+        tokens: lexer(source)?,
+        index: 0,
+    };
+    let mut result = Vec::new();
+    let mut path = String::new();
+
+    loop {
+        let char = parser.try_char();
+        let name = parser.try_name();
+        let pattern = parser.try_pattern();
+
+        if name.is_some() || pattern.is_some() {
+            let mut prefix = char.map(String::from).unwrap_or_default();
+            if !DEFAULT_PREFIXES.contains(prefix.as_str()) {
+                path.push_str(&prefix);
+                prefix = String::new();
+            }
+            if !path.is_empty() {
+                result.push(Token::Path(std::mem::take(&mut path)));
+            }
+            result.push(Token::Key {
+                prefix,
+                suffix: String::new(),
+                pattern: pattern.unwrap_or_else(|| default_pattern.clone()),
+                modifier: parser.try_modifier(),
+            });
+            continue;
+        }
+
+        if let Some(value) = char.or_else(|| parser.try_escaped_char()) {
+            path.push(value);
+            continue;
+        }
+
+        if !path.is_empty() {
+            result.push(Token::Path(std::mem::take(&mut path)));
+        }
+
+        if parser.peek() == Some(&LexToken::Open) {
+            parser.index += 1;
+            let prefix = parser.consume_text();
+            let name = parser.try_name();
+            let pattern = parser.try_pattern();
+            let suffix = parser.consume_text();
+            if parser.peek() != Some(&LexToken::Close) {
+                bail!("Expected \"}}\" at {}", parser.index);
+            }
+            parser.index += 1;
+            let pattern = match (name, pattern) {
+                (_, Some(pattern)) => pattern,
+                (Some(_), None) => default_pattern.clone(),
+                (None, None) => String::new(),
+            };
+            result.push(Token::Key {
+                prefix,
+                suffix,
+                pattern,
+                modifier: parser.try_modifier(),
+            });
+            continue;
+        }
+
+        if let Some(token) = parser.peek() {
+            bail!("Unexpected {token:?} at {}", parser.index);
+        }
+        return Ok(result);
+    }
+}
+
+fn escape_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        if ".+*?=^!:${}()[]|/\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Converts a `path-to-regexp` route source to the source of the regular
+/// expression the runtime matches paths against, using the default options
+/// (not strict, matching the whole path).
+pub(crate) fn path_to_regexp(source: &str) -> Result<String> {
+    let mut route = "^".to_string();
+    for token in parse(source)? {
+        match token {
+            Token::Path(path) => route.push_str(&escape_string(&path)),
+            Token::Key {
+                prefix,
+                suffix,
+                pattern,
+                modifier,
+            } => {
+                let prefix = escape_string(&prefix);
+                let suffix = escape_string(&suffix);
+                if pattern.is_empty() {
+                    route.push_str(&format!("(?:{prefix}{suffix}){modifier}"));
+                } else if prefix.is_empty() && suffix.is_empty() {
+                    route.push_str(&format!("({pattern}){modifier}"));
+                } else if modifier == "+" || modifier == "*" {
+                    let optional = if modifier == "*" { "?" } else { "" };
+                    route.push_str(&format!(
+                        "(?:{prefix}((?:{pattern})(?:{suffix}{prefix}(?:{pattern}))*\
+                         ){suffix}){optional}"
+                    ));
+                } else {
+                    route.push_str(&format!("(?:{prefix}({pattern}){suffix}){modifier}"));
+                }
+            }
+        }
+    }
+    route.push_str(&format!("[{}]?$", escape_string(DEFAULT_DELIMITER)));
+    Ok(regexp_source(&route))
+}
+
+/// Returns what `new RegExp(pattern).source` returns in V8: unescaped `/`
+/// outside of character classes is escaped, so custom parameter patterns like
+/// `(_next/data/[^/]{1,})` come out as `(_next\/data\/[^/]{1,})`. Line
+/// terminators are escaped as well.
+fn regexp_source(pattern: &str) -> String {
+    let mut source = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                source.push(c);
+                if let Some(escaped) = chars.next() {
+                    source.push(escaped);
+                }
+            }
+            '[' => {
+                in_class = true;
+                source.push(c);
+            }
+            ']' => {
+                in_class = false;
+                source.push(c);
+            }
+            '/' if !in_class => source.push_str("\\/"),
+            '\n' => source.push_str("\\n"),
+            '\r' => source.push_str("\\r"),
+            '\u{2028}' => source.push_str("\\u2028"),
+            '\u{2029}' => source.push_str("\\u2029"),
+            _ => source.push(c),
+        }
+    }
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{path_to_regexp, regexp_source};
+
+    #[test]
+    fn static_path() {
+        assert_eq!(path_to_regexp("/about").unwrap(), r"^\/about[\/#\?]?$");
+    }
+
+    #[test]
+    fn named_params() {
+        assert_eq!(
+            path_to_regexp("/blog/:slug").unwrap(),
+            r"^\/blog(?:\/([^\/#\?]+?))[\/#\?]?$"
+        );
+        assert_eq!(
+            path_to_regexp("/blog/:path*").unwrap(),
+            r"^\/blog(?:\/((?:[^\/#\?]+?)(?:\/(?:[^\/#\?]+?))*))?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn middleware_data_route() {
+        assert_eq!(
+            path_to_regexp("/:nextData(_next/data/[^/]{1,})?/about(.json)?").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?\/about(.json)?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn escapes_slashes_like_regexp_source() {
+        assert_eq!(regexp_source(r"a/b\/c[/]"), r"a\/b\/c[/]");
+        assert_eq!(regexp_source(r"[\]/]/"), r"[\]/]\/");
+        assert_eq!(
+            path_to_regexp("/:path(api/.*)").unwrap(),
+            r"^(?:\/(api\/.*))[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn invalid_sources() {
+        assert!(path_to_regexp("/:").is_err());
+        assert!(path_to_regexp("/(foo(bar))").is_err());
+        assert!(path_to_regexp("/(foo").is_err());
+    }
+}
@@ -6,16 +6,13 @@ use turbopack_binding::{
     turbopack::{
         build::{BuildChunkingContext, MinifyType},
         core::{
-            compile_time_defines,
             compile_time_info::{CompileTimeDefines, CompileTimeInfo, FreeVarReferences},
             environment::{Environment, ExecutionEnvironment, NodeJsEnvironment, ServerAddr},
             free_var_references,
             resolve::{parse::Request, pattern::Pattern},
         },
         ecmascript::TransformPlugin,
-        ecmascript_plugin::transform::directives::{
-            client::ClientDirectiveTransformer, server::ServerDirectiveTransformer,
-        },
+        ecmascript_plugin::transform::directives::client::ClientDirectiveTransformer,
         env::ProcessEnvAsset,
         node::execution_context::ExecutionContext,
         turbopack::{
@@ -39,6 +36,7 @@ use crate::{
     babel::maybe_add_babel_loader,
     embed_js::next_js_fs,
     env::env_for_js,
+    less::maybe_add_less_loader,
     mode::NextMode,
     next_build::{get_external_next_compiled_package_mapping, get_postcss_package_mapping},
     next_client::{RuntimeEntries, RuntimeEntry},
@@ -46,6 +44,7 @@ use crate::{
     next_import_map::{get_next_server_import_map, mdx_import_source_file},
     next_server::resolve::ExternalPredicate,
     next_shared::{
+        defines::{defines as shared_defines, DefineEnv},
         resolve::{
             ModuleFeatureReportResolvePlugin, NextExternalResolvePlugin,
             NextNodeSharedRuntimeResolvePlugin, UnsupportedModulesResolvePlugin,
@@ -58,6 +57,7 @@ use crate::{
         },
     },
     sass::maybe_add_sass_loader,
+    stylus::maybe_add_stylus_loader,
     transform_options::{
         get_decorators_transform_options, get_jsx_transform_options,
         get_typescript_transform_options,
@@ -174,26 +174,18 @@ pub async fn get_server_resolve_options_context(
     .cell())
 }
 
-fn defines(mode: NextMode) -> CompileTimeDefines {
-    compile_time_defines!(
-        process.turbopack = true,
-        process.env.NODE_ENV = mode.node_env(),
-        process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
-        process.env.NEXT_RUNTIME = "nodejs",
-        process.env.__NEXT_EXPERIMENTAL_REACT = false,
-    )
-    // TODO(WEB-937) there are more defines needed, see
-    // packages/next/src/build/webpack-config.ts
+fn defines(node_env: &str) -> CompileTimeDefines {
+    shared_defines(DefineEnv::Server, node_env)
 }
 
 #[turbo_tasks::function]
-fn next_server_defines(mode: NextMode) -> Vc<CompileTimeDefines> {
-    defines(mode).cell()
+async fn next_server_defines(node_env: Vc<String>) -> Result<Vc<CompileTimeDefines>> {
+    Ok(defines(&node_env.await?).cell())
 }
 
 #[turbo_tasks::function]
-async fn next_server_free_vars(mode: NextMode) -> Result<Vc<FreeVarReferences>> {
-    Ok(free_var_references!(..defines(mode).into_iter()).cell())
+async fn next_server_free_vars(node_env: Vc<String>) -> Result<Vc<FreeVarReferences>> {
+    Ok(free_var_references!(..defines(&node_env.await?).into_iter()).cell())
 }
 
 #[turbo_tasks::function]
@@ -201,12 +193,13 @@ pub fn get_server_compile_time_info(
     mode: NextMode,
     process_env: Vc<Box<dyn ProcessEnv>>,
     server_addr: Vc<ServerAddr>,
+    next_config: Vc<NextConfig>,
 ) -> Vc<CompileTimeInfo> {
     CompileTimeInfo::builder(Environment::new(Value::new(
         ExecutionEnvironment::NodeJsLambda(NodeJsEnvironment::current(process_env, server_addr)),
     )))
-    .defines(next_server_defines(mode))
-    .free_var_references(next_server_free_vars(mode))
+    .defines(next_server_defines(next_config.node_env(mode)))
+    .free_var_references(next_server_free_vars(next_config.node_env(mode)))
     .cell()
 }
 
@@ -228,9 +221,17 @@ pub async fn get_server_module_options_context(
         ..Default::default()
     });
 
+    let webpack_rules = *maybe_add_babel_loader(
+        project_path,
+        *next_config.webpack_rules().await?,
+        next_config.suppress_babel_warning(),
+    )
+    .await?;
+    let webpack_rules =
+        *maybe_add_sass_loader(project_path, next_config.sass_config(), webpack_rules).await?;
+    let webpack_rules = *maybe_add_less_loader(next_config.less_config(), webpack_rules).await?;
     let webpack_rules =
-        *maybe_add_babel_loader(project_path, *next_config.webpack_rules().await?).await?;
-    let webpack_rules = maybe_add_sass_loader(next_config.sass_config(), webpack_rules).await?;
+        maybe_add_stylus_loader(project_path, next_config.stylus_config(), webpack_rules).await?;
     let enable_webpack_loaders = webpack_rules.map(|rules| {
         WebpackLoadersOptions {
             rules,
@@ -245,12 +246,6 @@ pub async fn get_server_module_options_context(
     let styled_components_transform_plugin =
         *get_styled_components_transform_plugin(next_config).await?;
     let styled_jsx_transform_plugin = *get_styled_jsx_transform_plugin().await?;
-    let server_directive_transform_plugin =
-        Some(Vc::cell(Box::new(ServerDirectiveTransformer::new(
-            // ServerDirective is not implemented yet and always reports an issue.
-            // We don't have to pass a valid transition name yet, but the API is prepared.
-            &Vc::cell("TODO".to_string()),
-        )) as _));
 
     // ModuleOptionsContext related options
     let tsconfig = get_typescript_transform_options(project_path);
@@ -342,7 +337,6 @@ pub async fn get_server_module_options_context(
             let mut base_source_transforms: Vec<Vc<TransformPlugin>> = vec![
                 styled_components_transform_plugin,
                 styled_jsx_transform_plugin,
-                server_directive_transform_plugin,
             ]
             .into_iter()
             .flatten()
@@ -401,13 +395,11 @@ pub async fn get_server_module_options_context(
             ecmascript_client_reference_transition_name,
             ..
         } => {
-            let mut base_source_transforms: Vec<Vc<TransformPlugin>> = vec![
-                styled_components_transform_plugin,
-                server_directive_transform_plugin,
-            ]
-            .into_iter()
-            .flatten()
-            .collect();
+            let mut base_source_transforms: Vec<Vc<TransformPlugin>> =
+                vec![styled_components_transform_plugin]
+                    .into_iter()
+                    .flatten()
+                    .collect();
 
             if let Some(ecmascript_client_reference_transition_name) =
                 ecmascript_client_reference_transition_name
@@ -603,6 +595,7 @@ pub fn get_server_chunking_context(
     // anyway?
     client_root: Vc<FileSystemPath>,
     environment: Vc<Environment>,
+    minify: bool,
 ) -> Vc<BuildChunkingContext> {
     // TODO(alexkirsz) This should return a trait that can be implemented by the
     // different server chunking contexts. OR the build chunking context should
@@ -614,6 +607,10 @@ pub fn get_server_chunking_context(
         client_root.join("_next/static/media".to_string()),
         environment,
     )
-    .minify_type(MinifyType::NoMinify)
+    .minify_type(if minify {
+        MinifyType::Minify
+    } else {
+        MinifyType::NoMinify
+    })
     .build()
 }
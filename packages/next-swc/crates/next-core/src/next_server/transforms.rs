@@ -11,6 +11,7 @@ use crate::{
     next_shared::transforms::{
         get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
         get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+        get_server_actions_transform_rule,
     },
 };
 
@@ -37,7 +38,12 @@ pub async fn get_next_server_transforms_rules(
             );
             (false, Some(pages_dir))
         }
-        ServerContextType::AppSSR { .. } => (false, None),
+        ServerContextType::AppSSR { .. } => {
+            // Client components are rendered on the server too, where they
+            // must reference the actions like in the browser.
+            rules.push(get_server_actions_transform_rule(false));
+            (false, None)
+        }
         ServerContextType::AppRSC {
             client_transition, ..
         } => {
@@ -46,6 +52,7 @@ pub async fn get_next_server_transforms_rules(
                     client_transition,
                 ));
             }
+            rules.push(get_server_actions_transform_rule(true));
             (true, None)
         }
         ServerContextType::AppRoute { .. } => (false, None),
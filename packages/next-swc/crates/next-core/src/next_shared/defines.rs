@@ -0,0 +1,87 @@
+use turbopack_binding::turbopack::core::{
+    compile_time_defines, compile_time_info::CompileTimeDefines,
+};
+
+/// The environment code is compiled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DefineEnv {
+    Client,
+    Server,
+    Edge,
+}
+
+/// The compile time defines for code compiled for `env`, so that isomorphic
+/// code only keeps the branches for that environment.
+pub(crate) fn defines(env: DefineEnv, node_env: &str) -> CompileTimeDefines {
+    match env {
+        DefineEnv::Client => compile_time_defines!(
+            process.turbopack = true,
+            process.browser = true,
+            process.env.NODE_ENV = node_env,
+            process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
+            process.env.__NEXT_HAS_REWRITES = true,
+            process.env.__NEXT_I18N_SUPPORT = false,
+        ),
+        DefineEnv::Server => compile_time_defines!(
+            process.turbopack = true,
+            process.browser = false,
+            process.env.NODE_ENV = node_env,
+            process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
+            process.env.NEXT_RUNTIME = "nodejs",
+            process.env.__NEXT_EXPERIMENTAL_REACT = false,
+        ),
+        DefineEnv::Edge => compile_time_defines!(
+            process.turbopack = true,
+            process.browser = false,
+            process.env.NODE_ENV = node_env,
+            process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
+            process.env.NEXT_RUNTIME = "edge",
+        ),
+    }
+    // TODO(WEB-937) there are more defines needed, see
+    // packages/next/src/build/webpack-config.ts
+}
+
+#[cfg(test)]
+mod tests {
+    use turbopack_binding::turbopack::core::compile_time_info::CompileTimeDefineValue;
+
+    use super::{defines, DefineEnv};
+
+    fn define(env: DefineEnv, path: &[&str]) -> Option<CompileTimeDefineValue> {
+        let path = path.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        defines(env, "development").0.get(&path).cloned()
+    }
+
+    #[test]
+    fn process_browser_follows_env() {
+        assert_eq!(
+            define(DefineEnv::Client, &["process", "browser"]),
+            Some(CompileTimeDefineValue::Bool(true))
+        );
+        assert_eq!(
+            define(DefineEnv::Server, &["process", "browser"]),
+            Some(CompileTimeDefineValue::Bool(false))
+        );
+        assert_eq!(
+            define(DefineEnv::Edge, &["process", "browser"]),
+            Some(CompileTimeDefineValue::Bool(false))
+        );
+    }
+
+    #[test]
+    fn next_runtime_is_server_only() {
+        assert_eq!(
+            define(DefineEnv::Client, &["process", "env", "NEXT_RUNTIME"]),
+            None
+        );
+        assert_eq!(
+            define(DefineEnv::Server, &["process", "env", "NEXT_RUNTIME"]),
+            Some(CompileTimeDefineValue::String("nodejs".to_string()))
+        );
+        assert_eq!(
+            define(DefineEnv::Edge, &["process", "env", "NEXT_RUNTIME"]),
+            Some(CompileTimeDefineValue::String("edge".to_string()))
+        );
+    }
+}
@@ -1,2 +1,3 @@
+pub(crate) mod defines;
 pub(crate) mod resolve;
 pub(crate) mod transforms;
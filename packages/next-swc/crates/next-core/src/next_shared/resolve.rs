@@ -11,6 +11,7 @@ use turbopack_binding::{
         file_source::FileSource,
         issue::{unsupported_module::UnsupportedModuleIssue, IssueExt},
         resolve::{
+            options::{ImportMapResult, ImportMapping, ImportMappingReplacement},
             parse::Request,
             pattern::Pattern,
             plugin::{ResolvePlugin, ResolvePluginCondition},
@@ -103,6 +104,81 @@ impl ResolvePlugin for UnsupportedModulesResolvePlugin {
     }
 }
 
+/// Node.js builtins which have no browser polyfill, see `NEXT_ALIASES` in
+/// `next_import_map.rs` for the polyfilled ones.
+pub(crate) const NODE_ONLY_BUILTINS: &[&str] = &[
+    "async_hooks",
+    "child_process",
+    "cluster",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "fs",
+    "http2",
+    "inspector",
+    "module",
+    "net",
+    "perf_hooks",
+    "readline",
+    "repl",
+    "tls",
+    "trace_events",
+    "v8",
+    "wasi",
+    "worker_threads",
+];
+
+/// Mapped in the client fallback import map for the [NODE_ONLY_BUILTINS] when
+/// `experimental.turbo.reportNodeBuiltins` is set. Requests which reach it
+/// found no module, and are reported as unsupported modules like the ones of
+/// [UnsupportedModulesResolvePlugin], so they show up in the aggregated list.
+/// Resolving still fails as before. Requests from code in `node_modules`
+/// aren't reported, since packages often guard Node.js-only requires.
+#[turbo_tasks::value]
+pub(crate) struct UnsupportedNodeBuiltinReplacer;
+
+#[turbo_tasks::value_impl]
+impl UnsupportedNodeBuiltinReplacer {
+    #[turbo_tasks::function]
+    pub fn new() -> Vc<Self> {
+        UnsupportedNodeBuiltinReplacer.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for UnsupportedNodeBuiltinReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: String) -> Vc<ImportMapping> {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: Vc<FileSystemPath>,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let Request::Module { module, .. } = &*request.await? else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+        let is_foreign = context
+            .await?
+            .path
+            .split('/')
+            .any(|segment| segment == "node_modules");
+        if !is_foreign {
+            UnsupportedModuleIssue {
+                file_path: context,
+                package: module.strip_prefix("node:").unwrap_or(module).into(),
+                package_path: None,
+            }
+            .cell()
+            .emit();
+        }
+        Ok(ImportMapResult::NoEntry.into())
+    }
+}
+
 #[turbo_tasks::value]
 pub(crate) struct NextExternalResolvePlugin {
     root: Vc<FileSystemPath>,
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use swc_core::ecma::{
+    ast::{ImportDecl, Program},
+    visit::{Visit, VisitWith},
+};
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::{
+        core::{
+            issue::{Issue, IssueExt, IssueSeverity},
+            resolve::{parse::Request, pattern::Pattern, resolve},
+            source::Source,
+        },
+        ecmascript::{CustomTransformer, OptionTransformPlugin, TransformContext},
+        turbopack::{resolve_options, resolve_options_context::ResolveOptionsContext},
+    },
+};
+
+use crate::next_config::NextConfig;
+
+/// Returns a plugin which reports imports of global (non-module) CSS from
+/// files other than the custom `App` in `pages_dir`, unless
+/// `experimental.turbo.allowGlobalCssAnywhere` is set. Global CSS applies to
+/// every page, so importing it from a component makes the styles depend on
+/// which pages happened to be loaded. Stylesheets from packages can be
+/// imported anywhere. Imports are resolved with `resolve_options_context`, so
+/// aliases like `@/styles/globals.css` are checked by the file they point to.
+#[turbo_tasks::function]
+pub async fn get_global_css_check_plugin(
+    project_path: Vc<FileSystemPath>,
+    pages_dir: Vc<FileSystemPath>,
+    resolve_options_context: Vc<ResolveOptionsContext>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<OptionTransformPlugin>> {
+    if *next_config.allow_global_css_anywhere().await? {
+        return Ok(Vc::cell(None));
+    }
+    let app_path = format!("{}/_app", pages_dir.await?.path);
+    Ok(Vc::cell(Some(Vc::cell(Box::new(GlobalCssCheck {
+        project_path,
+        resolve_options_context,
+        app_path,
+    }) as _))))
+}
+
+#[derive(Debug)]
+struct GlobalCssCheck {
+    project_path: Vc<FileSystemPath>,
+    resolve_options_context: Vc<ResolveOptionsContext>,
+    /// The path of the custom `App` without its extension, relative to the
+    /// root of the file system.
+    app_path: String,
+}
+
+#[async_trait]
+impl CustomTransformer for GlobalCssCheck {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        if !global_css_restricted(ctx.file_path_str, &self.app_path) {
+            return Ok(());
+        }
+        let mut visitor = StylesheetImportVisitor { imports: vec![] };
+        program.visit_with(&mut visitor);
+        if visitor.imports.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.project_path.root().join(ctx.file_path_str.to_string());
+        let origin = path.parent();
+        let resolve_options = resolve_options(origin, self.resolve_options_context);
+        for specifier in visitor.imports {
+            let request = Request::parse(Value::new(Pattern::Constant(specifier.clone())));
+            let sources = resolve(origin, request, resolve_options)
+                .primary_sources()
+                .await?;
+            for source in sources.iter() {
+                if is_global_css(&source.ident().path().await?.path) {
+                    GlobalCssImportIssue {
+                        path,
+                        specifier: specifier.clone(),
+                    }
+                    .cell()
+                    .emit();
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the specifiers of imports which look like stylesheets, so only
+/// those have to be resolved.
+struct StylesheetImportVisitor {
+    imports: Vec<String>,
+}
+
+impl Visit for StylesheetImportVisitor {
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        if is_stylesheet(strip_query(&import.src.value)) {
+            self.imports.push(import.src.value.to_string());
+        }
+    }
+}
+
+/// Whether the module at `file_path` may not import global CSS. Only the
+/// custom `App` at `app_path` and code in `node_modules` may.
+fn global_css_restricted(file_path: &str, app_path: &str) -> bool {
+    if file_path
+        .split('/')
+        .any(|segment| segment == "node_modules")
+    {
+        return false;
+    }
+    let without_extension = file_path
+        .rsplit_once('.')
+        .map_or(file_path, |(path, _)| path);
+    without_extension != app_path
+}
+
+fn strip_query(specifier: &str) -> &str {
+    specifier
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or(specifier)
+}
+
+fn is_stylesheet(path: &str) -> bool {
+    [".css", ".scss", ".sass", ".less", ".styl"]
+        .iter()
+        .any(|extension| path.ends_with(extension))
+}
+
+/// Whether the resolved file at `path` is a global stylesheet of the project.
+/// Stylesheets of packages aren't restricted, and CSS modules are scoped to
+/// the importer.
+fn is_global_css(path: &str) -> bool {
+    is_stylesheet(path)
+        && !path.contains(".module.")
+        && !path.split('/').any(|segment| segment == "node_modules")
+}
+
+#[turbo_tasks::value(shared)]
+struct GlobalCssImportIssue {
+    path: Vc<FileSystemPath>,
+    specifier: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for GlobalCssImportIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("code generation".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "Global CSS \"{}\" cannot be imported from files other than your Custom <App>",
+            self.specifier
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(
+            "Due to the global nature of stylesheets, and to avoid conflicts, please move all \
+             first-party global CSS imports to pages/_app.js, or use CSS modules (*.module.css) \
+             for component styles. To allow global CSS imports from any module, set \
+             `experimental.turbo.allowGlobalCssAnywhere` in next.config.js."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> Vc<String> {
+        Vc::cell("https://nextjs.org/docs/messages/css-global".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{global_css_restricted, is_global_css, is_stylesheet, strip_query};
+
+    #[test]
+    fn stylesheet_specifiers() {
+        assert!(is_stylesheet(strip_query("@/styles/a.css")));
+        assert!(is_stylesheet(strip_query("../styles/theme.scss?inline")));
+        assert!(is_stylesheet(strip_query("./theme.less")));
+        assert!(is_stylesheet(strip_query("./theme.styl#hash")));
+        assert!(!is_stylesheet(strip_query("./data.json")));
+    }
+
+    #[test]
+    fn global_css_paths() {
+        assert!(is_global_css("project/styles/globals.css"));
+        assert!(is_global_css("project/styles/theme.scss"));
+        assert!(is_global_css("project/styles/theme.less"));
+        assert!(is_global_css("project/styles/theme.styl"));
+        assert!(!is_global_css("project/components/button.module.less"));
+        assert!(!is_global_css("project/components/button.module.css"));
+        assert!(!is_global_css(
+            "project/node_modules/some-package/dist/styles.css"
+        ));
+        assert!(!is_global_css("project/data.json"));
+    }
+
+    #[test]
+    fn only_the_custom_app_may_import_global_css() {
+        let app_path = "project/pages/_app";
+        assert!(!global_css_restricted("project/pages/_app.tsx", app_path));
+        assert!(global_css_restricted("project/pages/index.tsx", app_path));
+        assert!(global_css_restricted("project/components/nav.js", app_path));
+        assert!(!global_css_restricted(
+            "project/node_modules/ui/dist/index.js",
+            app_path
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use swc_core::ecma::{
+    ast::{
+        ExportAll, Expr, ImportDecl, Lit, NamedExport, ObjectLit, Program, Prop, PropName,
+        PropOrSpread,
+    },
+    visit::{Visit, VisitWith},
+};
+use turbo_tasks::{trace::TraceRawVcs, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::{
+        core::issue::{Issue, IssueExt, IssueSeverity},
+        ecmascript::{CustomTransformer, OptionTransformPlugin, TransformContext},
+    },
+};
+
+/// Returns a plugin which checks the `type` import attribute of static
+/// imports and re-exports, e.g. `import data from './x.json' with { type:
+/// 'json' }`, against the extension of the imported file. Modules are typed
+/// by their extension, so an attribute that doesn't match it would be
+/// silently ignored. Only applied to the project's own code, not to packages.
+#[turbo_tasks::function]
+pub fn get_import_attributes_check_plugin(
+    project_path: Vc<FileSystemPath>,
+) -> Vc<OptionTransformPlugin> {
+    Vc::cell(Some(Vc::cell(
+        Box::new(ImportAttributesCheck { project_path }) as _,
+    )))
+}
+
+#[derive(Debug)]
+struct ImportAttributesCheck {
+    project_path: Vc<FileSystemPath>,
+}
+
+#[async_trait]
+impl CustomTransformer for ImportAttributesCheck {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let mut visitor = ImportAttributesVisitor { imports: vec![] };
+        program.visit_with(&mut visitor);
+
+        for (specifier, ty) in visitor.imports {
+            let Some(problem) = attribute_problem(&specifier, &ty) else {
+                continue;
+            };
+            ImportAttributeIssue {
+                path: self.project_path.root().join(ctx.file_path_str.to_string()),
+                specifier,
+                ty,
+                problem,
+            }
+            .cell()
+            .emit();
+        }
+        Ok(())
+    }
+}
+
+/// Collects the specifiers of imports and re-exports with a `type` import
+/// attribute, together with the asserted type.
+struct ImportAttributesVisitor {
+    imports: Vec<(String, String)>,
+}
+
+impl ImportAttributesVisitor {
+    fn add(&mut self, specifier: &str, with: &Option<Box<ObjectLit>>) {
+        if let Some(ty) = with.as_deref().and_then(attribute_type) {
+            self.imports.push((specifier.to_string(), ty));
+        }
+    }
+}
+
+impl Visit for ImportAttributesVisitor {
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        self.add(&import.src.value, &import.with);
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if let Some(src) = &export.src {
+            self.add(&src.value, &export.with);
+        }
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        self.add(&export.src.value, &export.with);
+    }
+}
+
+/// The value of the `type` key of an import attributes object.
+fn attribute_type(with: &ObjectLit) -> Option<String> {
+    with.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        let Prop::KeyValue(key_value) = &**prop else {
+            return None;
+        };
+        let key = match &key_value.key {
+            PropName::Ident(ident) => &*ident.sym,
+            PropName::Str(str) => &*str.value,
+            _ => return None,
+        };
+        if key != "type" {
+            return None;
+        }
+        match &*key_value.value {
+            Expr::Lit(Lit::Str(value)) => Some(value.value.to_string()),
+            _ => None,
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+enum AttributeProblem {
+    /// The attribute asserts a type the imported file doesn't have.
+    Mismatch,
+    /// The attribute asserts a type that isn't supported.
+    UnsupportedType,
+}
+
+/// Checks the asserted `ty` against the extension of `specifier`. Package
+/// imports and paths without an extension are resolved later and can't be
+/// checked here.
+fn attribute_problem(specifier: &str, ty: &str) -> Option<AttributeProblem> {
+    let extensions: &[&str] = match ty {
+        "json" => &["json"],
+        "css" => &["css"],
+        _ => return Some(AttributeProblem::UnsupportedType),
+    };
+    if !specifier.starts_with('.') && !specifier.starts_with('/') {
+        return None;
+    }
+    let path = specifier
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or(specifier);
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let (_, extension) = file_name.rsplit_once('.')?;
+    if extensions.contains(&extension) {
+        None
+    } else {
+        Some(AttributeProblem::Mismatch)
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct ImportAttributeIssue {
+    path: Vc<FileSystemPath>,
+    specifier: String,
+    ty: String,
+    problem: AttributeProblem,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ImportAttributeIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        match self.problem {
+            AttributeProblem::Mismatch => IssueSeverity::Error.into(),
+            AttributeProblem::UnsupportedType => IssueSeverity::Warning.into(),
+        }
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("code generation".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(match self.problem {
+            AttributeProblem::Mismatch => format!(
+                "The import of \"{}\" asserts type \"{}\", which doesn't match the file",
+                self.specifier, self.ty
+            ),
+            AttributeProblem::UnsupportedType => format!(
+                "The import of \"{}\" asserts the unsupported type \"{}\"",
+                self.specifier, self.ty
+            ),
+        })
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(match self.problem {
+            AttributeProblem::Mismatch => format!(
+                "Modules are typed by their file extension, so \"{}\" isn't imported as {}. \
+                 Remove the `with {{ type: \"{}\" }}` attribute or import a .{} file.",
+                self.specifier, self.ty, self.ty, self.ty
+            ),
+            AttributeProblem::UnsupportedType => {
+                "Only the \"json\" and \"css\" import attribute types are supported.".to_string()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attribute_problem, AttributeProblem};
+
+    #[test]
+    fn matching_attributes() {
+        assert_eq!(attribute_problem("./data.json", "json"), None);
+        assert_eq!(attribute_problem("./data.json?raw", "json"), None);
+        assert_eq!(attribute_problem("../styles/a.css", "css"), None);
+        assert_eq!(attribute_problem("some-package", "json"), None);
+        assert_eq!(attribute_problem("lodash.merge", "json"), None);
+        assert_eq!(attribute_problem("./data", "json"), None);
+    }
+
+    #[test]
+    fn mismatched_attributes() {
+        assert_eq!(
+            attribute_problem("./data.js", "json"),
+            Some(AttributeProblem::Mismatch)
+        );
+        assert_eq!(
+            attribute_problem("./styles.module.scss", "css"),
+            Some(AttributeProblem::Mismatch)
+        );
+        assert_eq!(
+            attribute_problem("./data.json", "javascript"),
+            Some(AttributeProblem::UnsupportedType)
+        );
+    }
+}
@@ -1,9 +1,13 @@
 pub(crate) mod emotion;
+pub(crate) mod global_css;
+pub(crate) mod import_attributes;
 pub(crate) mod modularize_imports;
 pub(crate) mod next_dynamic;
 pub(crate) mod next_font;
 pub(crate) mod next_strip_page_exports;
+pub(crate) mod node_polyfills;
 pub(crate) mod relay;
+pub(crate) mod server_actions;
 pub(crate) mod styled_components;
 pub(crate) mod styled_jsx;
 pub(crate) mod swc_ecma_transform_plugins;
@@ -13,6 +17,7 @@ pub use next_dynamic::get_next_dynamic_transform_rule;
 pub use next_font::get_next_font_transform_rule;
 pub use next_strip_page_exports::get_next_pages_transforms_rule;
 pub use relay::get_relay_transform_plugin;
+pub use server_actions::{get_server_actions_in_pages_rule, get_server_actions_transform_rule};
 use turbo_tasks::{Value, Vc};
 use turbopack_binding::turbopack::{
     core::reference_type::{ReferenceType, UrlReferenceSubType},
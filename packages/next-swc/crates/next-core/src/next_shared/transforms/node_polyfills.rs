@@ -0,0 +1,139 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use swc_core::{
+    common::Mark,
+    ecma::{
+        ast::{Expr, Ident, MemberExpr, MemberProp, Program},
+        visit::{Visit, VisitWith},
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::{
+        core::issue::{Issue, IssueExt, IssueSeverity},
+        ecmascript::{CustomTransformer, OptionTransformPlugin, TransformContext},
+    },
+};
+
+use crate::next_config::NextConfig;
+
+/// Returns a plugin which reports every client module that references the
+/// `process` or `Buffer` globals, which pull in the Node.js polyfills. Only
+/// enabled with `experimental.turbo.warnOnNodePolyfills`.
+#[turbo_tasks::function]
+pub async fn get_node_polyfills_warning_plugin(
+    project_path: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<OptionTransformPlugin>> {
+    if !*next_config.warn_on_node_polyfills().await? {
+        return Ok(Vc::cell(None));
+    }
+    Ok(Vc::cell(Some(Vc::cell(
+        Box::new(NodePolyfillsWarning { project_path }) as _,
+    ))))
+}
+
+#[derive(Debug)]
+struct NodePolyfillsWarning {
+    project_path: Vc<FileSystemPath>,
+}
+
+#[async_trait]
+impl CustomTransformer for NodePolyfillsWarning {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let mut visitor = NodeGlobalsVisitor {
+            unresolved_mark: ctx.unresolved_mark,
+            globals: BTreeSet::new(),
+        };
+        program.visit_with(&mut visitor);
+
+        for global in visitor.globals {
+            NodePolyfillIssue {
+                path: self.project_path.root().join(ctx.file_path_str.to_string()),
+                global: global.to_string(),
+            }
+            .cell()
+            .emit();
+        }
+        Ok(())
+    }
+}
+
+/// Collects the polyfilled Node.js globals a module references.
+struct NodeGlobalsVisitor {
+    unresolved_mark: Mark,
+    globals: BTreeSet<&'static str>,
+}
+
+impl Visit for NodeGlobalsVisitor {
+    fn visit_member_expr(&mut self, expr: &MemberExpr) {
+        // `process.env.*` and `process.turbopack` are replaced at compile time
+        // and don't need the polyfill.
+        if let (Expr::Ident(obj), MemberProp::Ident(prop)) = (&*expr.obj, &expr.prop) {
+            if obj.sym == *"process" && (prop.sym == *"env" || prop.sym == *"turbopack") {
+                return;
+            }
+        }
+        expr.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        if ident.span.ctxt.outer() != self.unresolved_mark {
+            return;
+        }
+        match &*ident.sym {
+            "process" => {
+                self.globals.insert("process");
+            }
+            "Buffer" => {
+                self.globals.insert("Buffer");
+            }
+            _ => {}
+        }
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct NodePolyfillIssue {
+    path: Vc<FileSystemPath>,
+    global: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for NodePolyfillIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("code generation".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "The Node.js `{}` global is polyfilled in client code",
+            self.global
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "This module references `{}`, which adds the `node:{}` polyfill to the client bundle. \
+             Remove the reference if the module doesn't need to run in Node.js.",
+            self.global,
+            self.global.to_lowercase()
+        ))
+    }
+}
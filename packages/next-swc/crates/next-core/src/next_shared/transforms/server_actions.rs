@@ -0,0 +1,187 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use next_swc::server_actions::{server_actions, Config};
+use swc_core::{
+    common::{util::take::Take, FileName},
+    ecma::{
+        ast::{Expr, ExprStmt, Lit, Module, Program, Stmt},
+        visit::FoldWith,
+    },
+};
+use turbo_tasks::Vc;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::{
+        core::issue::{Issue, IssueExt, IssueSeverity},
+        ecmascript::{CustomTransformer, EcmascriptInputTransform, TransformContext},
+        turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+    },
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which applies the Next.js server actions transform. On the
+/// client, the exports of `"use server"` modules are replaced with references
+/// to the actions, so their implementation stays out of the client bundles.
+/// On the server, the actions are registered with their ids.
+pub fn get_server_actions_transform_rule(is_server: bool) -> ModuleRule {
+    let transformer =
+        EcmascriptInputTransform::Plugin(Vc::cell(Box::new(NextServerActions { is_server }) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct NextServerActions {
+    is_server: bool,
+}
+
+#[async_trait]
+impl CustomTransformer for NextServerActions {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        let p = std::mem::replace(program, Program::Module(Module::dummy()));
+        *program = p.fold_with(&mut server_actions(
+            &FileName::Real(ctx.file_path_str.into()),
+            Config {
+                is_server: self.is_server,
+                enabled: true,
+            },
+            ctx.comments,
+        ));
+
+        Ok(())
+    }
+}
+
+/// Returns a rule which reports `"use server"` modules imported from pages.
+/// Server actions are called through the app router, so the pages client
+/// can't call them.
+pub fn get_server_actions_in_pages_rule(pages_dir: Vc<FileSystemPath>) -> ModuleRule {
+    let transformer = EcmascriptInputTransform::Plugin(Vc::cell(Box::new(
+        ServerActionsInPagesCheck { pages_dir },
+    ) as _));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(Vc::cell(vec![
+            transformer,
+        ]))],
+    )
+}
+
+#[derive(Debug)]
+struct ServerActionsInPagesCheck {
+    pages_dir: Vc<FileSystemPath>,
+}
+
+#[async_trait]
+impl CustomTransformer for ServerActionsInPagesCheck {
+    async fn transform(&self, program: &mut Program, ctx: &TransformContext<'_>) -> Result<()> {
+        if has_use_server_directive(program) {
+            ServerActionsInPagesIssue {
+                path: self.pages_dir.root().join(ctx.file_path_str.to_string()),
+            }
+            .cell()
+            .emit();
+        }
+        Ok(())
+    }
+}
+
+/// Whether the directives at the start of the module include `"use server"`.
+fn has_use_server_directive(program: &Program) -> bool {
+    let stmts: Box<dyn Iterator<Item = &Stmt>> = match program {
+        Program::Module(module) => Box::new(module.body.iter().map_while(|item| item.as_stmt())),
+        Program::Script(script) => Box::new(script.body.iter()),
+    };
+    stmts
+        .map_while(|stmt| match stmt {
+            Stmt::Expr(ExprStmt { expr, .. }) => match &**expr {
+                Expr::Lit(Lit::Str(directive)) => Some(&*directive.value),
+                _ => None,
+            },
+            _ => None,
+        })
+        .any(|directive| directive == "use server")
+}
+
+#[turbo_tasks::value(shared)]
+struct ServerActionsInPagesIssue {
+    path: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ServerActionsInPagesIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("unsupported".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell("Server actions (\"use server\") can't be used in the pages directory".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(
+            "Server actions are called through the app router. Import this module from a \
+             component in the app directory, or call an API route from the page instead."
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::DUMMY_SP,
+        ecma::ast::{Expr, ExprStmt, Lit, Module, ModuleItem, Program, Stmt, Str},
+    };
+
+    use super::has_use_server_directive;
+
+    fn module(directives: &[&str]) -> Program {
+        Program::Module(Module {
+            span: DUMMY_SP,
+            body: directives
+                .iter()
+                .map(|directive| {
+                    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                        span: DUMMY_SP,
+                        expr: Box::new(Expr::Lit(Lit::Str(Str {
+                            span: DUMMY_SP,
+                            value: (*directive).into(),
+                            raw: None,
+                        }))),
+                    }))
+                })
+                .collect(),
+            shebang: None,
+        })
+    }
+
+    #[test]
+    fn detects_use_server_directive() {
+        assert!(has_use_server_directive(&module(&["use server"])));
+        assert!(has_use_server_directive(&module(&[
+            "use strict",
+            "use server"
+        ])));
+        assert!(!has_use_server_directive(&module(&["use client"])));
+        assert!(!has_use_server_directive(&module(&[])));
+    }
+}
@@ -49,11 +49,12 @@ use turbopack_binding::{
 use crate::{
     embed_js::next_asset,
     env::env_for_js,
-    fallback::get_fallback_page,
+    fallback::{get_fallback_page, FallbackBranding},
+    instrumentation::instrumentation_runtime_entries,
     mode::NextMode,
     next_client::{
         context::{
-            get_client_assets_path, get_client_chunking_context, get_client_module_options_context,
+            get_client_assets_path, get_client_module_options_context,
             get_client_resolve_options_context, get_client_runtime_entries, ClientContextType,
         },
         transition::NextClientTransition,
@@ -90,6 +91,7 @@ pub async fn create_page_source(
     client_compile_time_info: Vc<CompileTimeInfo>,
     next_config: Vc<NextConfig>,
     server_addr: Vc<ServerAddr>,
+    fallback_branding: Vc<FallbackBranding>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let pages_dir = if let Some(pages) = pages_structure.await?.pages {
         pages.project_path().resolve().await?
@@ -139,7 +141,8 @@ pub async fn create_page_source(
         .cell(),
     );
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_root, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(project_root, server_addr, next_config.node_env(mode));
 
     let edge_chunking_context = Vc::upcast(
         DevChunkingContext::builder(
@@ -174,7 +177,8 @@ pub async fn create_page_source(
         .cell(),
     );
 
-    let server_compile_time_info = get_server_compile_time_info(mode, env, server_addr);
+    let server_compile_time_info =
+        get_server_compile_time_info(mode, env, server_addr, next_config);
     let server_resolve_options_context = get_server_resolve_options_context(
         project_root,
         server_ty,
@@ -242,10 +246,15 @@ pub async fn create_page_source(
     let injected_env = env_for_js(Vc::upcast(EnvMap::empty()), false, next_config);
     let env = Vc::upcast(CustomProcessEnv::new(env, next_config.env()));
 
-    let server_runtime_entries = Vc::cell(vec![Vc::upcast(ProcessEnvAsset::new(
-        project_root,
-        injected_env,
-    ))]);
+    let mut server_runtime_entries =
+        vec![Vc::upcast(ProcessEnvAsset::new(project_root, injected_env))];
+    server_runtime_entries.extend(
+        instrumentation_runtime_entries(project_root, next_config)
+            .await?
+            .iter()
+            .copied(),
+    );
+    let server_runtime_entries = Vc::cell(server_runtime_entries);
     let fallback_runtime_entries = Vc::cell(vec![]);
 
     let fallback_page = get_fallback_page(
@@ -254,7 +263,9 @@ pub async fn create_page_source(
         client_root,
         env,
         client_compile_time_info,
+        client_chunking_context,
         next_config,
+        fallback_branding,
     );
 
     let render_data = render_data(next_config, server_addr);
@@ -293,6 +304,7 @@ pub async fn create_page_source(
             client_root,
             node_root,
             render_data,
+            client_chunking_context,
         ),
         Vc::upcast::<Box<dyn ContentSource>>(AssetGraphContentSource::new_eager(
             client_root,
@@ -344,9 +356,8 @@ async fn create_page_source_for_file(
     node_path: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     render_data: Vc<JsonValue>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
-    let mode = NextMode::DevServer;
-
     let server_chunking_context = Vc::upcast(
         DevChunkingContext::builder(
             project_path,
@@ -373,13 +384,6 @@ async fn create_page_source_for_file(
         .build(),
     );
 
-    let client_chunking_context = get_client_chunking_context(
-        project_path,
-        client_root,
-        client_context.compile_time_info().environment(),
-        mode,
-    );
-
     let pathname = pathname_for_path(client_root, client_path, PathType::PagesPage);
     let route_matcher = NextParamsMatcher::new(pathname);
 
@@ -606,6 +610,7 @@ async fn create_page_source_for_root_directory(
     client_root: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     render_data: Vc<JsonValue>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let PagesStructure {
         app: _,
@@ -631,6 +636,7 @@ async fn create_page_source_for_root_directory(
             false,
             node_root,
             render_data,
+            client_chunking_context,
         ));
     }
 
@@ -649,6 +655,7 @@ async fn create_page_source_for_root_directory(
             true,
             node_root,
             render_data,
+            client_chunking_context,
         ));
     }
 
@@ -673,6 +680,7 @@ async fn create_page_source_for_directory(
     is_api_path: bool,
     node_root: Vc<FileSystemPath>,
     render_data: Vc<JsonValue>,
+    client_chunking_context: Vc<Box<dyn EcmascriptChunkingContext>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let PagesDirectoryStructure {
         ref items,
@@ -703,6 +711,7 @@ async fn create_page_source_for_directory(
             node_root,
             node_root,
             render_data,
+            client_chunking_context,
         )
         .issue_file_path(
             project_path,
@@ -731,6 +740,7 @@ async fn create_page_source_for_directory(
             is_api_path,
             node_root,
             render_data,
+            client_chunking_context,
         ))
     }
 
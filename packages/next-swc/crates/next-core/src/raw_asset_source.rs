@@ -0,0 +1,152 @@
+use anyhow::{bail, Result};
+use indexmap::IndexSet;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::turbopack::{
+    core::introspect::{Introspectable, IntrospectableChildren},
+    dev_server::source::{
+        headers::HeaderValue,
+        route_tree::{RouteTree, RouteType},
+        ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataFilter,
+        ContentSourceDataVary, GetContentSourceContent, RewriteBuilder,
+    },
+};
+
+/// The request header that makes [RawAssetContentSource] skip the router.
+pub const RAW_ASSET_HEADER: &str = "x-nextjs-raw";
+
+/// Wraps the Next.js router and sends requests with the
+/// `x-nextjs-raw: 1` header to the `raw` source directly, skipping rewrites
+/// and middleware. This makes it possible to fetch a compiled chunk while
+/// debugging rewrite issues. Only used in development when enabled.
+#[turbo_tasks::value(shared)]
+pub struct RawAssetContentSource {
+    router: Vc<Box<dyn ContentSource>>,
+    raw: Vc<Box<dyn ContentSource>>,
+}
+
+#[turbo_tasks::value_impl]
+impl RawAssetContentSource {
+    #[turbo_tasks::function]
+    pub fn new(
+        router: Vc<Box<dyn ContentSource>>,
+        raw: Vc<Box<dyn ContentSource>>,
+    ) -> Vc<RawAssetContentSource> {
+        RawAssetContentSource { router, raw }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for RawAssetContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::CatchAll, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for RawAssetContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> Vc<ContentSourceDataVary> {
+        ContentSourceDataVary {
+            headers: Some(ContentSourceDataFilter::Subset(
+                [RAW_ASSET_HEADER.to_string()].into(),
+            )),
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: String,
+        data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let ContentSourceData {
+            headers,
+            raw_query: Some(raw_query),
+            ..
+        } = &*data
+        else {
+            bail!("missing data for raw asset source");
+        };
+
+        let raw_header = headers
+            .as_ref()
+            .and_then(|headers| headers.get(RAW_ASSET_HEADER));
+        let source = if is_raw_request(raw_header) {
+            self.raw
+        } else {
+            self.router
+        };
+        let path_and_query = if raw_query.is_empty() {
+            format!("/{path}")
+        } else {
+            format!("/{path}?{raw_query}")
+        };
+        let rewrite = RewriteBuilder::new_source_with_path_and_query(source, path_and_query);
+        Ok(ContentSourceContent::Rewrite(rewrite.build()).cell())
+    }
+}
+
+/// Whether the request asks to skip the router with `x-nextjs-raw: 1`, given
+/// the value of that header.
+fn is_raw_request(raw_header: Option<&HeaderValue>) -> bool {
+    matches!(raw_header, Some(HeaderValue::SingleString(value)) if value.trim() == "1")
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for RawAssetContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<String> {
+        Vc::cell("raw asset content source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "skips the router for requests with the {RAW_ASSET_HEADER}: 1 header"
+        ))
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<Vc<IntrospectableChildren>> {
+        let mut children = IndexSet::new();
+        for (key, source) in [("router", self.router), ("raw", self.raw)] {
+            if let Some(source) =
+                Vc::try_resolve_sidecast::<Box<dyn Introspectable>>(source).await?
+            {
+                children.insert((Vc::cell(key.to_string()), source));
+            }
+        }
+        Ok(Vc::cell(children))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use turbopack_binding::turbopack::dev_server::source::headers::HeaderValue;
+
+    use super::is_raw_request;
+
+    fn header(value: &str) -> HeaderValue {
+        HeaderValue::SingleString(value.to_string())
+    }
+
+    #[test]
+    fn raw_header_bypasses_the_router() {
+        assert!(is_raw_request(Some(&header("1"))));
+        assert!(is_raw_request(Some(&header(" 1 "))));
+    }
+
+    #[test]
+    fn other_requests_are_routed() {
+        assert!(!is_raw_request(None));
+        assert!(!is_raw_request(Some(&header("0"))));
+        assert!(!is_raw_request(Some(&HeaderValue::MultiStrings(vec![
+            "1".to_string(),
+            "1".to_string()
+        ]))));
+    }
+}
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use mime::APPLICATION_JSON;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::File,
+    turbopack::{
+        core::{asset::AssetContent, introspect::Introspectable, version::VersionedContentExt},
+        dev_server::source::{
+            route_tree::{RouteTree, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceData, GetContentSourceContent,
+        },
+    },
+};
+
+use crate::next_config::Rewrites;
+
+/// Serves the rewrites loaded from next.config.js as JSON, split into
+/// `beforeFiles`, `afterFiles` and `fallback` with the `source`,
+/// `destination`, `has` and `missing` of each rule. This allows tooling to
+/// inspect the rules the router applies without evaluating the config.
+#[turbo_tasks::value(shared)]
+pub struct RewritesContentSource {
+    rewrites: Vc<Rewrites>,
+}
+
+#[turbo_tasks::value_impl]
+impl RewritesContentSource {
+    #[turbo_tasks::function]
+    pub fn new(rewrites: Vc<Rewrites>) -> Vc<RewritesContentSource> {
+        RewritesContentSource { rewrites }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for RewritesContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::Exact, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for RewritesContentSource {
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        _path: String,
+        _data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let json = rewrites_json(&*self.rewrites.await?)?;
+        Ok(ContentSourceContent::static_content(
+            AssetContent::file(File::from(json).with_content_type(APPLICATION_JSON).into())
+                .versioned(),
+        ))
+    }
+}
+
+fn rewrites_json(rewrites: &Rewrites) -> Result<String> {
+    Ok(serde_json::to_string_pretty(rewrites)?)
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for RewritesContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<String> {
+        Vc::cell("rewrites source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> Vc<String> {
+        Vc::cell("provides the rewrites from next.config.js as JSON".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rewrites_json;
+    use crate::next_config::{Rewrite, Rewrites, RouteHas};
+
+    #[test]
+    fn serializes_rewrites() {
+        let rewrites = Rewrites {
+            before_files: vec![Rewrite {
+                source: "/docs/:path*".to_string(),
+                destination: "https://docs.example.com/:path*".to_string(),
+                base_path: None,
+                locale: None,
+                has: Some(vec![RouteHas::Header {
+                    key: "x-docs".to_string(),
+                    value: None,
+                }]),
+                missing: None,
+            }],
+            ..Default::default()
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&rewrites_json(&rewrites).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "beforeFiles": [{
+                    "source": "/docs/:path*",
+                    "destination": "https://docs.example.com/:path*",
+                    "has": [{ "type": "header", "key": "x-docs" }],
+                }],
+                "afterFiles": [],
+                "fallback": [],
+            })
+        );
+    }
+}
@@ -40,7 +40,7 @@ use crate::{
     embed_js::next_asset,
     middleware::middleware_files,
     mode::NextMode,
-    next_config::NextConfig,
+    next_config::{next_configs, NextConfig},
     next_edge::{
         context::{get_edge_compile_time_info, get_edge_resolve_options_context},
         route_transition::NextEdgeRouteTransition,
@@ -50,16 +50,6 @@ use crate::{
     util::parse_config_from_source,
 };
 
-#[turbo_tasks::function]
-fn next_configs() -> Vc<Vec<String>> {
-    Vc::cell(
-        ["next.config.mjs", "next.config.js"]
-            .into_iter()
-            .map(ToOwned::to_owned)
-            .collect(),
-    )
-}
-
 #[turbo_tasks::value(shared)]
 #[derive(Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -220,7 +210,8 @@ fn edge_transition_map(
 ) -> Vc<TransitionsByName> {
     let mode = NextMode::DevServer;
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(project_path, server_addr, next_config.node_env(mode));
 
     let edge_chunking_context = Vc::upcast(
         DevChunkingContext::builder(
@@ -1,13 +1,20 @@
 use anyhow::{bail, Result};
 use serde_json::Value as JsonValue;
 use turbo_tasks::Vc;
-use turbopack_binding::turbopack::{
-    node::transforms::webpack::WebpackLoaderItem,
-    turbopack::module_options::{LoaderRuleItem, OptionWebpackRules, WebpackRules},
+use turbopack_binding::{
+    turbo::tasks_fs::{FileSystemEntryType, FileSystemPath},
+    turbopack::{
+        core::issue::{IssueExt, IssueSeverity},
+        node::transforms::webpack::WebpackLoaderItem,
+        turbopack::module_options::{LoaderRuleItem, OptionWebpackRules, WebpackRules},
+    },
 };
 
+use crate::config_issue::ConfigIssue;
+
 #[turbo_tasks::function]
 pub async fn maybe_add_sass_loader(
+    project_path: Vc<FileSystemPath>,
     sass_options: Vc<JsonValue>,
     webpack_rules: Option<Vc<WebpackRules>>,
 ) -> Result<Vc<OptionWebpackRules>> {
@@ -15,6 +22,44 @@ pub async fn maybe_add_sass_loader(
     let Some(sass_options) = sass_options.as_object() else {
         bail!("sass_options must be an object");
     };
+    let mut sass_options = sass_options.clone();
+    // Matches the webpack config: `prependData` and `additionalData` are options
+    // of sass-loader itself rather than of the sass compiler.
+    let additional_data = sass_options
+        .remove("additionalData")
+        .or_else(|| sass_options.remove("prependData"));
+    if let Some(JsonValue::Array(include_paths)) = sass_options.get("includePaths") {
+        for include_path in include_paths {
+            let Some(include_path) = include_path.as_str() else {
+                continue;
+            };
+            // Loaders are evaluated with the project directory as the working
+            // directory, so relative include paths are resolved against it.
+            if include_path.starts_with('/') {
+                continue;
+            }
+            let path = project_path.join(include_path.to_string());
+            if !matches!(
+                *path.get_type().await?,
+                FileSystemEntryType::Directory | FileSystemEntryType::Symlink
+            ) {
+                ConfigIssue {
+                    path: project_path,
+                    title: Vc::cell(format!(
+                        "Sass include path \"{include_path}\" does not exist"
+                    )),
+                    description: Vc::cell(
+                        "Each entry of sassOptions.includePaths in next.config.js should be a \
+                         directory, relative to the project directory."
+                            .to_string(),
+                    ),
+                    severity: IssueSeverity::Warning.cell(),
+                }
+                .cell()
+                .emit();
+            }
+        }
+    }
     let mut rules = if let Some(webpack_rules) = webpack_rules {
         webpack_rules.await?.clone_value()
     } else {
@@ -27,16 +72,20 @@ pub async fn maybe_add_sass_loader(
         ("*.sass", ".css"),
     ] {
         let rule = rules.get_mut(pattern);
+        let mut options = serde_json::json!({
+            //https://github.com/vercel/turbo/blob/d527eb54be384a4658243304cecd547d09c05c6b/crates/turbopack-node/src/transforms/webpack.rs#L191
+            "sourceMap": false,
+            "sassOptions": sass_options,
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        if let Some(additional_data) = &additional_data {
+            options.insert("additionalData".to_string(), additional_data.clone());
+        }
         let loader = WebpackLoaderItem {
             loader: "next/dist/compiled/sass-loader".to_string(),
-            options: serde_json::json!({
-                //https://github.com/vercel/turbo/blob/d527eb54be384a4658243304cecd547d09c05c6b/crates/turbopack-node/src/transforms/webpack.rs#L191
-                "sourceMap": false,
-                "sassOptions": sass_options,
-            })
-            .as_object()
-            .unwrap()
-            .clone(),
+            options,
         };
 
         if let Some(rule) = rule {
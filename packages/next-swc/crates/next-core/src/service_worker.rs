@@ -0,0 +1,185 @@
+use anyhow::{bail, Result};
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileSystemPath},
+    turbopack::{
+        core::{
+            asset::{Asset, AssetContent},
+            chunk::{ChunkingContext, EvaluatableAsset, EvaluatableAssets},
+            compile_time_defines,
+            compile_time_info::{CompileTimeDefines, CompileTimeInfo, FreeVarReferences},
+            context::AssetContext,
+            environment::{BrowserEnvironment, Environment, ExecutionEnvironment},
+            file_source::FileSource,
+            free_var_references,
+            ident::AssetIdent,
+            output::{OutputAsset, OutputAssets},
+            reference_type::{EntryReferenceSubType, ReferenceType},
+        },
+        dev::DevChunkingContext,
+        dev_server::source::{asset_graph::AssetGraphContentSource, ContentSource},
+        ecmascript::utils::StringifyJs,
+        node::execution_context::ExecutionContext,
+    },
+};
+
+use crate::{
+    mode::NextMode, next_client::context::ClientContextType, next_config::NextConfig,
+    web_entry_source::get_web_client_asset_context,
+};
+
+fn defines(node_env: &str) -> CompileTimeDefines {
+    compile_time_defines!(
+        process.turbopack = true,
+        process.env.NODE_ENV = node_env.to_string(),
+    )
+}
+
+#[turbo_tasks::function]
+async fn service_worker_defines(node_env: Vc<String>) -> Result<Vc<CompileTimeDefines>> {
+    Ok(defines(&node_env.await?).cell())
+}
+
+#[turbo_tasks::function]
+async fn service_worker_free_vars(node_env: Vc<String>) -> Result<Vc<FreeVarReferences>> {
+    Ok(free_var_references!(..defines(&node_env.await?).into_iter()).cell())
+}
+
+/// Like the client compile time info, but without the DOM.
+#[turbo_tasks::function]
+pub fn get_service_worker_compile_time_info(
+    browserslist_query: String,
+    node_env: Vc<String>,
+) -> Vc<CompileTimeInfo> {
+    CompileTimeInfo::builder(Environment::new(Value::new(ExecutionEnvironment::Browser(
+        BrowserEnvironment {
+            dom: false,
+            web_worker: false,
+            service_worker: true,
+            browserslist_query: browserslist_query.to_owned(),
+        }
+        .into(),
+    ))))
+    .defines(service_worker_defines(node_env))
+    .free_var_references(service_worker_free_vars(node_env))
+    .cell()
+}
+
+/// Compiles `entry`, relative to the project, as a service worker and serves
+/// it at `pathname`. The chunks are loaded with `importScripts`, so the worker
+/// doesn't need the module runtime to fetch them.
+#[turbo_tasks::function]
+pub async fn create_service_worker_source(
+    project_path: Vc<FileSystemPath>,
+    execution_context: Vc<ExecutionContext>,
+    server_root: Vc<FileSystemPath>,
+    entry: String,
+    pathname: String,
+    browserslist_query: String,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<Box<dyn ContentSource>>> {
+    let mode = NextMode::DevServer;
+    let compile_time_info =
+        get_service_worker_compile_time_info(browserslist_query, next_config.node_env(mode));
+    let context = get_web_client_asset_context(
+        project_path,
+        execution_context,
+        compile_time_info,
+        Value::new(ClientContextType::Other),
+        mode,
+        next_config,
+    );
+    // Service workers are replaced as a whole by the browser, so there's no
+    // HMR.
+    let chunk_root = server_root.join("_next/service-worker".to_string());
+    let chunking_context = Vc::upcast(
+        DevChunkingContext::builder(
+            project_path,
+            server_root,
+            chunk_root.join("chunks".to_string()),
+            chunk_root.join("media".to_string()),
+            compile_time_info.environment(),
+        )
+        .build(),
+    );
+
+    let module = context.process(
+        Vc::upcast(FileSource::new(project_path.join(entry.clone()))),
+        Value::new(ReferenceType::Entry(EntryReferenceSubType::Web)),
+    );
+    let Some(module) = Vc::try_resolve_sidecast::<Box<dyn EvaluatableAsset>>(module).await? else {
+        bail!("the service worker entry {entry} must be an ECMAScript module");
+    };
+
+    let asset = ServiceWorkerAsset {
+        server_root,
+        pathname,
+        chunking_context,
+        module,
+    }
+    .cell();
+
+    Ok(Vc::upcast(AssetGraphContentSource::new_lazy(
+        server_root,
+        Vc::upcast(asset),
+    )))
+}
+
+/// The script registered as the service worker, which imports its chunks.
+#[turbo_tasks::value]
+struct ServiceWorkerAsset {
+    server_root: Vc<FileSystemPath>,
+    pathname: String,
+    chunking_context: Vc<Box<dyn ChunkingContext>>,
+    module: Vc<Box<dyn EvaluatableAsset>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ServiceWorkerAsset {
+    #[turbo_tasks::function]
+    fn chunks(&self) -> Vc<OutputAssets> {
+        self.chunking_context.evaluated_chunk_group(
+            self.module.as_root_chunk(self.chunking_context),
+            EvaluatableAssets::one(self.module),
+        )
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for ServiceWorkerAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        AssetIdent::from_path(
+            self.server_root
+                .join(self.pathname.trim_start_matches('/').to_string()),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn references(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.chunks()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for ServiceWorkerAsset {
+    #[turbo_tasks::function]
+    async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        let this = self.await?;
+        let server_root = this.server_root.await?;
+
+        let mut scripts = vec![];
+        for chunk in self.chunks().await?.iter() {
+            let chunk_path = chunk.ident().path().await?;
+            if chunk_path.extension_ref() != Some("js") {
+                continue;
+            }
+            if let Some(path) = server_root.get_path_to(&chunk_path) {
+                scripts.push(format!("/{path}"));
+            }
+        }
+
+        let content = format!("importScripts(...{});\n", StringifyJs(&scripts));
+        Ok(AssetContent::file(File::from(content).into()))
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use mime::APPLICATION_JSON;
+use serde_json::Value as JsonValue;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::{File, FileContent},
+    turbopack::{
+        core::{asset::AssetContent, introspect::Introspectable, version::VersionedContentExt},
+        dev_server::source::{
+            route_tree::{RouteTree, RouteType},
+            wrapping_source::{ContentSourceProcessor, WrappedGetContentSourceContent},
+            ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataVary,
+            GetContentSourceContent, RewriteBuilder,
+        },
+    },
+};
+
+/// Wraps the stack frame tracing source and replaces the `from` prefix of the
+/// traced original paths with `to`. This is needed when the project is built
+/// in a different directory than the one the editor has open, e.g. behind a
+/// symlink.
+#[turbo_tasks::value(shared)]
+pub struct SourceRootRemapContentSource {
+    inner: Vc<Box<dyn ContentSource>>,
+    from: String,
+    to: String,
+}
+
+#[turbo_tasks::value_impl]
+impl SourceRootRemapContentSource {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: Vc<Box<dyn ContentSource>>,
+        from: String,
+        to: String,
+    ) -> Vc<SourceRootRemapContentSource> {
+        SourceRootRemapContentSource { inner, from, to }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for SourceRootRemapContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::CatchAll, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for SourceRootRemapContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> Vc<ContentSourceDataVary> {
+        ContentSourceDataVary {
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        self: Vc<Self>,
+        path: String,
+        _data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let this = self.await?;
+        let sources = this.inner.get_routes().get(path).await?;
+        let sources = sources
+            .iter()
+            .map(|s| {
+                Vc::upcast(WrappedGetContentSourceContent::new(
+                    *s,
+                    Vc::upcast(SourceRootRemapProcessor::new(
+                        this.from.clone(),
+                        this.to.clone(),
+                    )),
+                ))
+            })
+            .collect();
+        let sources = Vc::cell(sources);
+        Ok(ContentSourceContent::Rewrite(RewriteBuilder::new_sources(sources).build()).cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for SourceRootRemapContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<String> {
+        Vc::cell("source root remap content source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "replaces {} with {} in traced stack frames",
+            self.from, self.to
+        ))
+    }
+}
+
+#[turbo_tasks::value]
+struct SourceRootRemapProcessor {
+    from: String,
+    to: String,
+}
+
+#[turbo_tasks::value_impl]
+impl SourceRootRemapProcessor {
+    #[turbo_tasks::function]
+    pub fn new(from: String, to: String) -> Vc<SourceRootRemapProcessor> {
+        SourceRootRemapProcessor { from, to }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSourceProcessor for SourceRootRemapProcessor {
+    #[turbo_tasks::function]
+    async fn process(&self, content: Vc<ContentSourceContent>) -> Result<Vc<ContentSourceContent>> {
+        let ContentSourceContent::Static(static_content) = *content.await? else {
+            return Ok(content);
+        };
+        let static_content = static_content.await?;
+        let asset_content = static_content.content.content().await?;
+        let AssetContent::File(file_content) = *asset_content else {
+            return Ok(content);
+        };
+        let FileContent::Content(file) = &*file_content.await? else {
+            return Ok(content);
+        };
+        let Ok(mut frame) = serde_json::from_str::<JsonValue>(&file.content().to_str()?) else {
+            return Ok(content);
+        };
+        remap_source_root(&mut frame, &self.from, &self.to);
+        Ok(ContentSourceContent::static_content(
+            AssetContent::file(
+                File::from(serde_json::to_string(&frame)?)
+                    .with_content_type(APPLICATION_JSON)
+                    .into(),
+            )
+            .versioned(),
+        ))
+    }
+}
+
+/// Replaces the `from` prefix of every path in a traced stack frame with `to`.
+fn remap_source_root(value: &mut JsonValue, from: &str, to: &str) {
+    match value {
+        JsonValue::String(path) => {
+            if let Some(rest) = path.strip_prefix(from) {
+                // Only match whole path segments.
+                if rest.is_empty() || rest.starts_with('/') || from.ends_with('/') {
+                    *path = format!("{to}{rest}");
+                }
+            }
+        }
+        JsonValue::Array(values) => {
+            for value in values {
+                remap_source_root(value, from, to);
+            }
+        }
+        JsonValue::Object(map) => {
+            for value in map.values_mut() {
+                remap_source_root(value, from, to);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::remap_source_root;
+
+    #[test]
+    fn remaps_original_path() {
+        let mut frame = json!({
+            "file": "/private/var/build/app/app/page.tsx",
+            "lineNumber": 4,
+            "column": 10,
+            "methodName": "Page",
+        });
+        remap_source_root(&mut frame, "/private/var/build/app", "/Users/me/app");
+        assert_eq!(
+            frame,
+            json!({
+                "file": "/Users/me/app/app/page.tsx",
+                "lineNumber": 4,
+                "column": 10,
+                "methodName": "Page",
+            })
+        );
+    }
+
+    #[test]
+    fn keeps_paths_outside_of_root() {
+        let mut frame = json!({ "file": "/private/var/build/app-other/page.tsx" });
+        remap_source_root(&mut frame, "/private/var/build/app", "/Users/me/app");
+        assert_eq!(
+            frame,
+            json!({ "file": "/private/var/build/app-other/page.tsx" })
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPath,
+    turbopack::{
+        core::{
+            issue::{IssueExt, IssueSeverity},
+            resolve::{parse::Request, pattern::Pattern, resolve},
+        },
+        node::transforms::webpack::WebpackLoaderItem,
+        turbopack::{
+            module_options::{LoaderRuleItem, OptionWebpackRules, WebpackRules},
+            resolve_options,
+            resolve_options_context::ResolveOptionsContext,
+        },
+    },
+};
+
+use crate::config_issue::ConfigIssue;
+
+/// If the user has a `stylusOptions` block in their `next.config.js`, adds
+/// `stylus-loader` for `.styl` files. `use` plugins and `import` paths are
+/// passed through to the stylus compiler.
+#[turbo_tasks::function]
+pub async fn maybe_add_stylus_loader(
+    project_path: Vc<FileSystemPath>,
+    stylus_options: Vc<JsonValue>,
+    webpack_rules: Option<Vc<WebpackRules>>,
+) -> Result<Vc<OptionWebpackRules>> {
+    let stylus_options = stylus_options.await?;
+    let Some(stylus_options) = stylus_options.as_object() else {
+        return Ok(Vc::cell(webpack_rules));
+    };
+
+    for package in ["stylus", "stylus-loader"] {
+        if !*is_package_available(project_path, package.to_string()).await? {
+            ConfigIssue {
+                path: project_path,
+                title: Vc::cell(format!(
+                    "Unable to resolve {package}, but stylusOptions are configured"
+                )),
+                description: Vc::cell(
+                    "Make sure stylus and stylus-loader are installed via your package manager."
+                        .to_string(),
+                ),
+                severity: IssueSeverity::Error.cell(),
+            }
+            .cell()
+            .emit();
+        }
+    }
+
+    let mut rules = if let Some(webpack_rules) = webpack_rules {
+        webpack_rules.await?.clone_value()
+    } else {
+        Default::default()
+    };
+    for (pattern, rename) in [("*.module.styl", ".module.css"), ("*.styl", ".css")] {
+        let rule = rules.get_mut(pattern);
+        let loader = WebpackLoaderItem {
+            loader: "stylus-loader".to_string(),
+            options: serde_json::json!({
+                "sourceMap": false,
+                "stylusOptions": stylus_options,
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        };
+
+        if let Some(rule) = rule {
+            // Without `as`, loader result would be JS code, so we don't want to apply
+            // stylus-loader on that.
+            let Some(rename_as) = rule.rename_as.as_ref() else {
+                continue;
+            };
+            if rename_as != "*" {
+                continue;
+            }
+            let mut loaders = rule.loaders.await?.clone_value();
+            loaders.push(loader);
+            rule.loaders = Vc::cell(loaders);
+        } else {
+            rules.insert(
+                pattern.to_string(),
+                LoaderRuleItem {
+                    loaders: Vc::cell(vec![loader]),
+                    rename_as: Some(format!("*{rename}")),
+                },
+            );
+        }
+    }
+
+    Ok(Vc::cell(Some(Vc::cell(rules))))
+}
+
+#[turbo_tasks::function]
+async fn is_package_available(
+    project_path: Vc<FileSystemPath>,
+    package: String,
+) -> Result<Vc<bool>> {
+    let result = resolve(
+        project_path,
+        Request::parse(Value::new(Pattern::Constant(format!(
+            "{package}/package.json"
+        )))),
+        resolve_options(
+            project_path,
+            ResolveOptionsContext {
+                enable_node_modules: Some(project_path.root().resolve().await?),
+                enable_node_native_modules: true,
+                custom_conditions: vec!["development".to_string()],
+                ..Default::default()
+            }
+            .cell(),
+        ),
+    );
+    let assets = result.primary_sources().await?;
+    Ok(Vc::cell(!assets.is_empty()))
+}
@@ -1,8 +1,10 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::{bail, Result};
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value as JsonValue;
 use swc_core::ecma::ast::Program;
-use turbo_tasks::{trace::TraceRawVcs, TaskInput, ValueDefault, ValueToString, Vc};
+use turbo_tasks::{TaskInput, ValueDefault, ValueToString, Vc};
 use turbo_tasks_fs::rope::Rope;
 use turbopack_binding::{
     turbo::tasks_fs::{json::parse_json_rope_with_source_context, FileContent, FileSystemPath},
@@ -23,12 +25,25 @@ use turbopack_binding::{
 };
 
 use crate::{
-    next_config::{NextConfig, OutputType},
+    next_config::{NextConfig, OutputType, RuntimeConfig, RuntimeConfigMode},
     next_import_map::get_next_package,
 };
 
 const NEXT_TEMPLATE_PATH: &str = "dist/esm/build/templates";
 
+/// The `distDir` when none is configured.
+pub const DEFAULT_DIST_DIR: &str = ".next";
+
+/// The directory Next.js writes its internal files to, e.g. the trace file:
+/// the configured `distDir`, relative to the project directory.
+pub fn internal_dir(project_dir: &Path, dist_dir: Option<&str>) -> PathBuf {
+    project_dir.join(
+        dist_dir
+            .filter(|dist_dir| !dist_dir.is_empty())
+            .unwrap_or(DEFAULT_DIST_DIR),
+    )
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, TaskInput)]
 pub enum PathType {
     PagesPage,
@@ -116,21 +131,8 @@ pub async fn foreign_code_context_condition(
     Ok(result)
 }
 
-#[derive(
-    Default,
-    PartialEq,
-    Eq,
-    Clone,
-    Copy,
-    Debug,
-    TraceRawVcs,
-    Serialize,
-    Deserialize,
-    Hash,
-    PartialOrd,
-    Ord,
-    TaskInput,
-)]
+#[turbo_tasks::value(shared)]
+#[derive(Default, Clone, Copy, Debug, Hash, PartialOrd, Ord, TaskInput)]
 #[serde(rename_all = "lowercase")]
 pub enum NextRuntime {
     #[default]
@@ -139,6 +141,18 @@ pub enum NextRuntime {
     Edge,
 }
 
+impl NextRuntime {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NextRuntime::NodeJs => "nodejs",
+            NextRuntime::Edge => "edge",
+        }
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionNextRuntime(Option<NextRuntime>);
+
 #[turbo_tasks::value]
 #[derive(Default, Clone)]
 pub struct NextSourceConfig {
@@ -403,11 +417,15 @@ pub async fn render_data(
         fetch_cache_key_prefix: Option<String>,
         isr_memory_cache_size: Option<f64>,
         isr_flush_to_disk: Option<bool>,
+        runtime_config: Option<RuntimeConfig>,
+        base_path: String,
+        asset_prefix: String,
     }
 
     let config = next_config.await?;
     let server_info = ServerInfo::try_from(&*server_addr.await?);
 
+    let runtime_config = next_config.runtime_config().await?;
     let experimental = &config.experimental;
 
     let value = serde_json::to_value(Data {
@@ -417,6 +435,32 @@ pub async fn render_data(
         fetch_cache_key_prefix: experimental.fetch_cache_key_prefix.clone(),
         isr_memory_cache_size: experimental.isr_memory_cache_size,
         isr_flush_to_disk: experimental.isr_flush_to_disk,
+        runtime_config: (runtime_config.mode == RuntimeConfigMode::Request)
+            .then(|| runtime_config.clone_value()),
+        base_path: next_config.base_path().await?.clone_value(),
+        asset_prefix: next_config.asset_prefix().await?.clone_value(),
     })?;
     Ok(Vc::cell(value))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::internal_dir;
+
+    #[test]
+    fn internal_dir_follows_dist_dir() {
+        let project = Path::new("/project");
+        assert_eq!(internal_dir(project, None), Path::new("/project/.next"));
+        assert_eq!(internal_dir(project, Some("")), Path::new("/project/.next"));
+        assert_eq!(
+            internal_dir(project, Some("build")),
+            Path::new("/project/build")
+        );
+        assert_eq!(
+            internal_dir(project, Some("out/next")),
+            Path::new("/project/out/next")
+        );
+    }
+}
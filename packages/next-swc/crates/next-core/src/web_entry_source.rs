@@ -42,25 +42,28 @@ use crate::{
     next_config::NextConfig,
 };
 
-fn defines() -> CompileTimeDefines {
+fn defines(node_env: &str) -> CompileTimeDefines {
     compile_time_defines!(
         process.turbopack = true,
-        process.env.NODE_ENV = "development",
+        process.env.NODE_ENV = node_env.to_string(),
     )
 }
 
 #[turbo_tasks::function]
-fn web_defines() -> Vc<CompileTimeDefines> {
-    defines().cell()
+async fn web_defines(node_env: Vc<String>) -> Result<Vc<CompileTimeDefines>> {
+    Ok(defines(&node_env.await?).cell())
 }
 
 #[turbo_tasks::function]
-async fn web_free_vars() -> Result<Vc<FreeVarReferences>> {
-    Ok(free_var_references!(..defines().into_iter()).cell())
+async fn web_free_vars(node_env: Vc<String>) -> Result<Vc<FreeVarReferences>> {
+    Ok(free_var_references!(..defines(&node_env.await?).into_iter()).cell())
 }
 
 #[turbo_tasks::function]
-pub fn get_compile_time_info(browserslist_query: String) -> Vc<CompileTimeInfo> {
+pub fn get_compile_time_info(
+    browserslist_query: String,
+    node_env: Vc<String>,
+) -> Vc<CompileTimeInfo> {
     CompileTimeInfo::builder(Environment::new(Value::new(ExecutionEnvironment::Browser(
         BrowserEnvironment {
             dom: true,
@@ -70,8 +73,8 @@ pub fn get_compile_time_info(browserslist_query: String) -> Vc<CompileTimeInfo>
         }
         .into(),
     ))))
-    .defines(web_defines())
-    .free_var_references(web_free_vars())
+    .defines(web_defines(node_env))
+    .free_var_references(web_free_vars(node_env))
     .cell()
 }
 
@@ -135,7 +138,7 @@ fn get_web_client_chunking_context(
 }
 
 #[turbo_tasks::function]
-fn get_web_client_asset_context(
+pub(crate) fn get_web_client_asset_context(
     project_path: Vc<FileSystemPath>,
     execution_context: Vc<ExecutionContext>,
     compile_time_info: Vc<CompileTimeInfo>,
@@ -176,7 +179,7 @@ pub async fn create_web_entry_source(
 ) -> Result<Vc<Box<dyn ContentSource>>> {
     let ty = Value::new(ClientContextType::Other);
     let mode = NextMode::DevServer;
-    let compile_time_info = get_compile_time_info(browserslist_query);
+    let compile_time_info = get_compile_time_info(browserslist_query, next_config.node_env(mode));
     let context = get_web_client_asset_context(
         project_root,
         execution_context,
@@ -3,13 +3,14 @@
 #![cfg(test)]
 
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     env,
     fmt::Write,
     future::{pending, Future},
     net::{IpAddr, Ipv4Addr, SocketAddr},
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 
@@ -29,11 +30,14 @@ use chromiumoxide::{
 use dunce::canonicalize;
 use futures::StreamExt;
 use lazy_static::lazy_static;
-use next_core::turbopack::{
-    cli_utils::issue::{format_issue, LogOptions},
-    core::issue::IssueSeverity,
+use next_core::{
+    turbopack::{
+        cli_utils::issue::{format_issue, LogOptions},
+        core::issue::IssueSeverity,
+    },
+    FallbackBranding,
 };
-use next_dev::{EntryRequest, NextDevServerBuilder};
+use next_dev::{internal_routes::parse_header, EntryRequest, NextDevServerBuilder};
 use owo_colors::OwoColorize;
 use parking_lot::Mutex;
 use regex::{Captures, Regex, Replacer};
@@ -232,38 +236,116 @@ async fn run_test(resource: PathBuf) -> JsResult {
         get_free_local_addr().unwrap()
     };
 
+    // Fixtures can override the default browserslist query.
+    let browserslist_query = std::fs::read_to_string(project_dir.join(".browserslistrc"))
+        .ok()
+        .map(|query| query.lines().map(str::trim).collect::<Vec<_>>().join(", "));
+    // Fixtures can label the fallback page.
+    let fallback_label = std::fs::read_to_string(project_dir.join(".fallback-label"))
+        .ok()
+        .map(|label| label.trim().to_string());
+    // Fixtures can compile a service worker, `ENTRY PATHNAME` with the entry
+    // relative to the input directory.
+    let service_worker = std::fs::read_to_string(project_dir.join(".service-worker"))
+        .ok()
+        .and_then(|service_worker| {
+            let (entry, pathname) = service_worker.trim().split_once(' ')?;
+            Some((entry.to_string(), pathname.trim().to_string()))
+        });
+    // Fixtures can add headers to the internal routes, one `Name: value` per
+    // line.
+    let internal_route_headers =
+        std::fs::read_to_string(project_dir.join(".internal-route-headers"))
+            .map(|headers| {
+                headers
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| parse_header(line).expect("invalid internal route header"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+    // Fixtures can enable the `x-nextjs-raw` header with a marker file.
+    let raw_asset_header = project_dir.join(".raw-asset-header").exists();
+    // Fixtures can disable the dev manifest source with a marker file.
+    let enable_manifest_source = !project_dir.join(".no-manifest-source").exists();
+    // Fixtures can mount more projects, one `PREFIX=DIR` per line with the
+    // directory relative to the input directory.
+    let mounted_projects = std::fs::read_to_string(project_dir.join(".mounted-projects"))
+        .map(|mounts| {
+            mounts
+                .lines()
+                .filter_map(|line| line.trim().split_once('='))
+                .map(|(prefix, dir)| {
+                    let dir = canonicalize(project_dir.join(dir))
+                        .expect("mounted project directory must exist");
+                    (prefix.to_string(), dir.to_string_lossy().to_string())
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
     let mock_dir = resource_temp.join("__httpmock__");
     let mock_server_future = get_mock_server_future(&mock_dir);
 
     let (issue_tx, mut issue_rx) = unbounded_channel();
     let issue_tx = TransientInstance::new(issue_tx);
+    let listened_issues = Arc::new(Mutex::new(vec![]));
 
     let result;
 
     {
         let tt = TurboTasks::new(MemoryBackend::default());
-        let server = NextDevServerBuilder::new(
+        let mut server = NextDevServerBuilder::new(
             tt.clone(),
             project_dir.to_string_lossy().to_string(),
             workspace_root.to_string_lossy().to_string(),
-        )
-        .entry_request(EntryRequest::Module(
-            "@turbo/pack-test-harness".to_string(),
-            "/harness".to_string(),
-        ))
-        .entry_request(EntryRequest::Relative("index.js".to_owned()))
-        .eager_compile(false)
-        .hostname(requested_addr.ip())
-        .port(requested_addr.port())
-        .log_level(turbopack_binding::turbopack::core::issue::IssueSeverity::Warning)
-        .log_detail(true)
-        .issue_reporter(Box::new(move || {
-            Vc::upcast(TestIssueReporter::new(issue_tx.clone()))
-        }))
-        .show_all(true)
-        .build()
-        .await
-        .unwrap();
+        );
+        if let Some(browserslist_query) = browserslist_query {
+            server = server.browserslist_query(browserslist_query);
+        }
+        for (prefix, project_dir) in mounted_projects {
+            server = server.mount_project(prefix, project_dir);
+        }
+        for (name, value) in internal_route_headers {
+            server = server.internal_route_header(name, value);
+        }
+        if let Some((entry, pathname)) = service_worker {
+            server = server.service_worker(entry, pathname);
+        }
+        if let Some(label) = fallback_label {
+            server = server.fallback_branding(FallbackBranding {
+                label: Some(label),
+                favicon: None,
+            });
+        }
+        let server = server
+            .entry_request(EntryRequest::Module(
+                "@turbo/pack-test-harness".to_string(),
+                "/harness".to_string(),
+            ))
+            .entry_request(EntryRequest::Relative("index.js".to_owned()))
+            .eager_compile(false)
+            .enable_manifest_source(enable_manifest_source)
+            .raw_asset_header(raw_asset_header)
+            .hostname(requested_addr.ip())
+            .port(requested_addr.port())
+            .log_level(turbopack_binding::turbopack::core::issue::IssueSeverity::Warning)
+            .log_detail(true)
+            .issue_reporter(Box::new(move || {
+                Vc::upcast(TestIssueReporter::new(issue_tx.clone()))
+            }))
+            .issue_listener({
+                let listened_issues = listened_issues.clone();
+                Arc::new(move |issue: ReadRef<PlainIssue>| {
+                    listened_issues
+                        .lock()
+                        .push((issue.file_path.clone(), issue.title.clone()));
+                })
+            })
+            .show_all(true)
+            .build()
+            .await
+            .unwrap();
 
         let local_addr =
             SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), server.addr.port());
@@ -306,6 +388,16 @@ async fn run_test(resource: PathBuf) -> JsResult {
                 issues.push(issue);
             }
 
+            let reported: HashSet<_> = issues
+                .iter()
+                .map(|issue| (issue.file_path.clone(), issue.title.clone()))
+                .collect();
+            let listened: HashSet<_> = listened_issues.lock().iter().cloned().collect();
+            assert_eq!(
+                listened, reported,
+                "the issue listener should receive every reported issue"
+            );
+
             snapshot_issues(
                 issues.iter().cloned(),
                 issues_fs.root(),
@@ -0,0 +1,16 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+function test() {
+  it('should discover app pages with a configured page extension', async () => {
+    const res = await fetch('/docs')
+    expect(res.status).toEqual(200)
+    const html = await res.text()
+    expect(html).toContain('Hello from MDX')
+  }, 20000)
+}
+
+export default function Test() {
+  useTestHarness(() => test())
+}
@@ -0,0 +1,3 @@
+export function useMDXComponents(components: any) {
+  return components
+}
@@ -0,0 +1,6 @@
+module.exports = {
+  pageExtensions: ['tsx', 'mdx'],
+  experimental: {
+    mdxRs: true,
+  },
+}
@@ -1,15 +1,10 @@
 import Test from './test'
+import action from './action'
 
 export default async function Page() {
-  let action
-  try {
-    await import('./action')
-  } catch (e) {
-    action = e.toString()
-  }
   return (
     <div>
-      <Test action={action} />
+      <Test serverResult={await action()} serverId={(action as any).$$id} />
     </div>
   )
 }
@@ -1,20 +1,34 @@
 'use client'
 
 import { useTestHarness } from '@turbo/pack-test-harness'
+import action from './action'
 
-export default function Test({ action }) {
+export default function Test({ serverResult, serverId }) {
   useTestHarness(() => {
     it('should run', () => {})
-    it('should throw an error when importing server action in client component', async () => {
-      await expect(import('./action')).rejects.toMatchObject({
-        message:
-          /Server actions \("use server"\) are not yet supported in Turbopack/,
-      })
+    it('should run the action in a server component', () => {
+      expect(serverResult).toBe(42)
     })
-    it('should throw an error when importing server action in server component', () => {
-      expect(action).toMatch(
-        /Server actions \("use server"\) are not yet supported in Turbopack/
-      )
+    it('should call the server with the id of the action in a client component', async () => {
+      const requests = []
+      const fetch = window.fetch
+      // The response never arrives, only the request is checked.
+      window.fetch = (_input, init) => {
+        requests.push(init)
+        return new Promise(() => {})
+      }
+      try {
+        action()
+        for (let i = 0; i < 50 && requests.length === 0; i++) {
+          await new Promise((resolve) => setTimeout(resolve, 20))
+        }
+      } finally {
+        window.fetch = fetch
+      }
+      expect(serverId).toEqual(expect.any(String))
+      expect(requests).toHaveLength(1)
+      expect(requests[0].method).toBe('POST')
+      expect(requests[0].headers['Next-Action']).toBe(serverId)
     })
   })
 }
@@ -0,0 +1,9 @@
+module.exports = {
+  experimental: {
+    turbo: {
+      // The pages and dynamically imported modules import global CSS to test
+      // that shared stylesheets are only loaded once.
+      allowGlobalCssAnywhere: true,
+    },
+  },
+}
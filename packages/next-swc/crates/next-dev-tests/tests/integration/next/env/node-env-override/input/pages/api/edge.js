@@ -0,0 +1,7 @@
+export const config = {
+  runtime: 'edge',
+}
+
+export default function Edge() {
+  return Response.json({ NODE_ENV: process.env.NODE_ENV })
+}
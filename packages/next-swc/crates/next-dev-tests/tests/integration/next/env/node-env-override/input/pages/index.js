@@ -0,0 +1,16 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should replace NODE_ENV with the configured value', () => {
+    expect(process.env.NODE_ENV).toBe('test')
+  })
+
+  it('should replace NODE_ENV with the configured value on the edge', async () => {
+    const res = await fetch('/api/edge')
+    expect(await res.json()).toEqual({ NODE_ENV: 'test' })
+  })
+}
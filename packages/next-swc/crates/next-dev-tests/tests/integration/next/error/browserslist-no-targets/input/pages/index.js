@@ -0,0 +1,12 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should run when no browser is targeted', () => {
+    const spread = { ...{ a: 1 } }
+    expect(spread).toEqual({ a: 1 })
+  })
+}
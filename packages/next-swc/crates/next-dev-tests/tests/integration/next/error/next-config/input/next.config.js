@@ -0,0 +1 @@
+throw new Error('Broken config (expected error)')
@@ -0,0 +1,40 @@
+import { useRef } from 'react'
+import { Harness, useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Index() {
+  const iframeRef = useRef<HTMLIFrameElement | null>(null)
+
+  useTestHarness((harness) => runTests(harness, iframeRef.current!))
+
+  return (
+    <iframe style={{ width: 800, height: 600 }} src="/page" ref={iframeRef} />
+  )
+}
+
+function runTests(harness: Harness, iframe: HTMLIFrameElement) {
+  const TIMEOUT = 40000
+
+  it(
+    'serves pages when the config fails to load',
+    async () => {
+      const res = await fetch('/page')
+      expect(res.status).toBe(200)
+    },
+    TIMEOUT
+  )
+
+  it(
+    'should show error overlay for the broken config',
+    async () => {
+      await harness.waitForLoaded(iframe)
+      const errorOverlay = await harness.waitForErrorOverlay(iframe)
+      const issues = await harness.waitForSelector(
+        errorOverlay,
+        '#turbopack-issues'
+      )
+      expect(issues.innerHTML).toContain('Failed to load the Next.js config')
+      expect(issues.innerHTML).toContain('Broken config (expected error)')
+    },
+    TIMEOUT
+  )
+}
@@ -0,0 +1,42 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests(harness) {
+  it('should answer a repeated request with 304 Not Modified', async () => {
+    const res = await fetch('/data.txt', { cache: 'no-store' })
+    expect(res.status).toBe(200)
+    const etag = res.headers.get('etag')
+    expect(etag).toMatch(/^".+"$/)
+
+    const again = await fetch('/data.txt', {
+      cache: 'no-store',
+      headers: { 'If-None-Match': etag },
+    })
+    expect(again.status).toBe(304)
+    expect(again.headers.get('etag')).toBe(etag)
+  })
+
+  it('should change the ETag when the file changes', async () => {
+    const before = await fetch('/data.txt', { cache: 'no-store' })
+    const etag = before.headers.get('etag')
+
+    await harness.changeFile('public/data.txt', 'before', 'after')
+
+    // The watcher picks up the change asynchronously.
+    let after
+    for (let i = 0; i < 50; i++) {
+      after = await fetch('/data.txt', {
+        cache: 'no-store',
+        headers: { 'If-None-Match': etag },
+      })
+      if (after.status === 200) break
+      await new Promise((resolve) => setTimeout(resolve, 100))
+    }
+    expect(after.status).toBe(200)
+    expect(await after.text()).toBe('after\n')
+    expect(after.headers.get('etag')).not.toBe(etag)
+  })
+}
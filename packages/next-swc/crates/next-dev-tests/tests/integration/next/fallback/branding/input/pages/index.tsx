@@ -0,0 +1,33 @@
+import { useRef } from 'react'
+import { Harness, useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Index() {
+  const iframeRef = useRef<HTMLIFrameElement | null>(null)
+
+  useTestHarness((harness) => runTests(harness, iframeRef.current!))
+
+  return (
+    <iframe
+      style={{ width: 800, height: 600 }}
+      src="/fallback.html"
+      ref={iframeRef}
+    />
+  )
+}
+
+function runTests(harness: Harness, iframe: HTMLIFrameElement) {
+  const TIMEOUT = 40000
+
+  it(
+    'should show the configured label on the fallback page',
+    async () => {
+      await harness.waitForLoaded(iframe)
+      const label = await harness.waitForSelector(
+        iframe,
+        '[data-nextjs-fallback-label]'
+      )
+      expect(label.textContent).toBe('Acme Dev Server')
+    },
+    TIMEOUT
+  )
+}
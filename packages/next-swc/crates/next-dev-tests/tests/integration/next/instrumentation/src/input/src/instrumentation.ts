@@ -0,0 +1,3 @@
+export function register() {
+  globalThis.INSTRUMENTATION_REGISTERED = true
+}
@@ -0,0 +1,19 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page({ registered }: { registered: boolean }) {
+  useTestHarness(() => runTests(registered))
+}
+
+export function getServerSideProps() {
+  return {
+    props: {
+      registered: globalThis.INSTRUMENTATION_REGISTERED === true,
+    },
+  }
+}
+
+function runTests(registered: boolean) {
+  it('should run src/instrumentation.ts in the rendering process', () => {
+    expect(registered).toBe(true)
+  })
+}
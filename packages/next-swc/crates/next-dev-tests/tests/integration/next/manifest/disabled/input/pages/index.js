@@ -0,0 +1,30 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <div id="test">Hello World</div>
+}
+
+function runTests() {
+  it('should render the page', () => {
+    expect(document.getElementById('test').textContent).toBe('Hello World')
+  })
+
+  it('should not serve the manifests', async () => {
+    for (const path of [
+      '/_next/static/development/_devPagesManifest.json',
+      '/_next/static/development/_buildManifest.js',
+      '/_next/static/development/_devMiddlewareManifest.json',
+    ]) {
+      const res = await fetch(path)
+      expect(res.status).toBe(404)
+    }
+  })
+
+  it('should still serve other pages', async () => {
+    const res = await fetch('/other')
+    expect(res.status).toBe(200)
+    expect(await res.text()).toContain('Other page')
+  })
+}
@@ -0,0 +1,3 @@
+export default function Other() {
+  return <div>Other page</div>
+}
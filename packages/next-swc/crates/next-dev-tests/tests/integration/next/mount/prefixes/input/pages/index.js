@@ -0,0 +1,39 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <div id="test">Main project</div>
+}
+
+function runTests() {
+  it('should render the main project', () => {
+    expect(document.getElementById('test').textContent).toBe('Main project')
+  })
+
+  it('should serve the mounted projects under their prefixes', async () => {
+    for (const [prefix, text] of [
+      ['/a', 'Project A'],
+      ['/b', 'Project B'],
+    ]) {
+      const res = await fetch(prefix)
+      expect(res.status).toBe(200)
+      expect(await res.text()).toContain(text)
+    }
+  })
+
+  it('should load the scripts of a mounted page from its prefix', async () => {
+    const res = await fetch('/a')
+    const html = new DOMParser().parseFromString(await res.text(), 'text/html')
+    const scripts = Array.from(html.querySelectorAll('script[src]'), (script) =>
+      script.getAttribute('src')
+    )
+    expect(scripts.length).toBeGreaterThan(0)
+    for (const src of scripts) {
+      expect(src).toMatch(/^\/a\/_next\//)
+      const script = await fetch(src)
+      expect(script.status).toBe(200)
+      expect(script.headers.get('content-type')).toContain('javascript')
+    }
+  })
+}
@@ -0,0 +1,20 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should add the configured headers to internal routes', async () => {
+    const res = await fetch('/__nextjs_rewrites')
+    expect(res.status).toBe(200)
+    expect(res.headers.get('cache-control')).toBe('no-store')
+    expect(res.headers.get('x-robots-tag')).toBe('noindex')
+  })
+
+  it('should not add the headers to app routes', async () => {
+    const res = await fetch('/')
+    expect(res.status).toBe(200)
+    expect(res.headers.get('x-robots-tag')).toBeNull()
+  })
+}
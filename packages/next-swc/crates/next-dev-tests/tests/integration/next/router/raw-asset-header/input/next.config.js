@@ -0,0 +1,13 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  async rewrites() {
+    return {
+      beforeFiles: [
+        {
+          source: '/data.txt',
+          destination: '/rewritten.txt',
+        },
+      ],
+    }
+  },
+}
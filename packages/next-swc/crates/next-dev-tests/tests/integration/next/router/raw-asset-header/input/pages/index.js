@@ -0,0 +1,22 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should apply rewrites to normal requests', async () => {
+    const res = await fetch('/data.txt')
+    expect(await res.text()).toBe('rewritten\n')
+  })
+
+  it('should skip rewrites with the x-nextjs-raw header', async () => {
+    const res = await fetch('/data.txt', { headers: { 'x-nextjs-raw': '1' } })
+    expect(await res.text()).toBe('raw\n')
+  })
+
+  it('should apply rewrites when x-nextjs-raw is not 1', async () => {
+    const res = await fetch('/data.txt', { headers: { 'x-nextjs-raw': '0' } })
+    expect(await res.text()).toBe('rewritten\n')
+  })
+}
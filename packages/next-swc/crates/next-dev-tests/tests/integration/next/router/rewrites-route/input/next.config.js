@@ -0,0 +1,21 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  async rewrites() {
+    return {
+      beforeFiles: [
+        {
+          source: '/docs/:path*',
+          destination: 'https://docs.example.com/:path*',
+          has: [{ type: 'header', key: 'x-docs' }],
+        },
+      ],
+      afterFiles: [
+        {
+          source: '/about',
+          destination: '/',
+        },
+      ],
+      fallback: [],
+    }
+  },
+}
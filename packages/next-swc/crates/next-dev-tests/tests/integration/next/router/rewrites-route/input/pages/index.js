@@ -0,0 +1,26 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should serve the rewrites from next.config.js', async () => {
+    const res = await fetch('/__nextjs_rewrites')
+    expect(res.status).toBe(200)
+    expect(res.headers.get('content-type')).toContain('application/json')
+
+    const rewrites = await res.json()
+    expect(rewrites.beforeFiles).toEqual([
+      expect.objectContaining({
+        source: '/docs/:path*',
+        destination: 'https://docs.example.com/:path*',
+        has: [{ type: 'header', key: 'x-docs' }],
+      }),
+    ])
+    expect(rewrites.afterFiles).toEqual([
+      expect.objectContaining({ source: '/about', destination: '/' }),
+    ])
+    expect(rewrites.fallback).toEqual([])
+  })
+}
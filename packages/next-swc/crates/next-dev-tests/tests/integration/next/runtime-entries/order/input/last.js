@@ -0,0 +1 @@
+globalThis.__RUNTIME_ENTRIES__.push('last')
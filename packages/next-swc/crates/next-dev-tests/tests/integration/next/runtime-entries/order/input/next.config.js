@@ -0,0 +1,10 @@
+module.exports = {
+  experimental: {
+    turbo: {
+      runtimeEntries: [
+        { request: './last.js' },
+        { request: './polyfill.js', position: 'before-refresh' },
+      ],
+    },
+  },
+}
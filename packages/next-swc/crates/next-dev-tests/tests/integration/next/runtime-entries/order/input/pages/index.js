@@ -0,0 +1,11 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should evaluate the runtime entries in their configured order', () => {
+    expect(globalThis.__RUNTIME_ENTRIES__).toEqual(['polyfill', 'last'])
+  })
+}
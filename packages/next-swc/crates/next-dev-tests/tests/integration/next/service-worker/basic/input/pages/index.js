@@ -0,0 +1,29 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should serve a script importing the service worker chunks', async () => {
+    const res = await fetch('/sw.js')
+    expect(res.status).toBe(200)
+    const script = await res.text()
+    expect(script).toMatch(/^importScripts\(.*"\/_next\/service-worker\//)
+  })
+
+  it('should run the service worker with the configured NODE_ENV', async () => {
+    await navigator.serviceWorker.register('/sw.js')
+    const registration = await navigator.serviceWorker.ready
+    const reply = new Promise((resolve) => {
+      navigator.serviceWorker.addEventListener(
+        'message',
+        (event) => resolve(event.data),
+        { once: true }
+      )
+    })
+    registration.active.postMessage('env')
+    expect(await reply).toEqual({ NODE_ENV: 'test', turbopack: true })
+    await registration.unregister()
+  })
+}
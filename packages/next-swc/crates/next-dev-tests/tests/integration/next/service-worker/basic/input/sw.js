@@ -0,0 +1,6 @@
+self.addEventListener('message', (event) => {
+  event.source.postMessage({
+    NODE_ENV: process.env.NODE_ENV,
+    turbopack: process.turbopack,
+  })
+})
@@ -0,0 +1,7 @@
+module.exports = {
+  experimental: {
+    turbo: {
+      sourceMaps: 'cheap',
+    },
+  },
+}
@@ -0,0 +1,33 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+/** The source map of this page, or of its section in a chunk's map. */
+async function findPageSourceMap() {
+  const scripts = document.querySelectorAll('script[src*="/_next/"]')
+  for (const { src } of scripts) {
+    const chunk = await (await fetch(src)).text()
+    if (!chunk.includes('//# sourceMappingURL=')) continue
+    const map = await (await fetch(`${src}.map`)).json()
+    const maps = map.sections ? map.sections.map((s) => s.map) : [map]
+    const pageMap = maps.find((m) =>
+      m.sources.some((source) => source.endsWith('pages/index.js'))
+    )
+    if (pageMap) return pageMap
+  }
+  throw new Error('no source map of pages/index.js was found')
+}
+
+function runTests() {
+  it('should serve line-only source maps without sources', async () => {
+    const map = await findPageSourceMap()
+    expect(map.sourcesContent).toBeUndefined()
+    expect(map.mappings.length).toBeGreaterThan(0)
+    for (const line of map.mappings.split(';')) {
+      // At most one segment per generated line.
+      expect(line).not.toContain(',')
+    }
+  })
+}
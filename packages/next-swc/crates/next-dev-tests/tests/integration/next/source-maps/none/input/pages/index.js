@@ -0,0 +1,38 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function chunkUrls() {
+  return Array.from(document.querySelectorAll('script[src*="/_next/"]')).map(
+    (script) => script.src
+  )
+}
+
+function runTests() {
+  it('should not reference source maps from client chunks', async () => {
+    const urls = chunkUrls()
+    expect(urls.length).toBeGreaterThan(0)
+    for (const url of urls) {
+      const chunk = await (await fetch(url)).text()
+      expect(chunk).not.toContain('//# sourceMappingURL=')
+      expect((await fetch(`${url}.map`)).status).toBe(404)
+    }
+  })
+
+  it('should explain why client frames are unavailable', async () => {
+    const params = new URLSearchParams({
+      isServer: 'false',
+      isEdgeServer: 'false',
+      file: chunkUrls()[0],
+      lineNumber: '1',
+      column: '1',
+    })
+    const res = await fetch(`/__nextjs_original-stack-frame?${params}`)
+    expect(res.status).toBe(204)
+    expect(res.headers.get('x-nextjs-original-frame-unavailable')).toContain(
+      'Source maps are disabled'
+    )
+  })
+}
@@ -34,13 +34,15 @@ pub struct DevServerOptions {
     #[cfg_attr(feature = "serializable", serde(default = "default_port"))]
     pub port: u16,
 
-    /// Hostname on which to start the application
+    /// Hostname on which to start the application. Can be passed multiple
+    /// times to listen on several addresses, e.g. `-H 127.0.0.1 -H
+    /// 192.168.1.2`, which all use the same port.
     #[cfg_attr(
         feature = "cli",
         clap(short = 'H', long, value_parser, default_value = "::")
     )]
-    #[cfg_attr(feature = "serializable", serde(default = "default_host"))]
-    pub hostname: IpAddr,
+    #[cfg_attr(feature = "serializable", serde(default = "default_hosts"))]
+    pub hostname: Vec<IpAddr>,
 
     /// Compile all, instead of only compiling referenced assets when their
     /// parent asset is requested
@@ -48,16 +50,38 @@ pub struct DevServerOptions {
     #[cfg_attr(feature = "serializable", serde(default))]
     pub eager_compile: bool,
 
+    /// Print the next config of the project as Turbopack resolves it,
+    /// including defaults, as JSON and exit.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub print_config: bool,
+
     /// Display version of the binary. Noop if used in library mode.
     #[cfg_attr(feature = "cli", clap(long))]
     #[cfg_attr(feature = "serializable", serde(default))]
     pub display_version: bool,
 
     /// Don't open the browser automatically when the dev server has started.
+    /// Setting the `BROWSER` environment variable to `none` does the same,
+    /// while another value names the browser executable to open.
     #[cfg_attr(feature = "cli", clap(long))]
     #[cfg_attr(feature = "serializable", serde(default))]
     pub no_open: bool,
 
+    /// The browser to open when the dev server has started, one of chrome,
+    /// firefox, safari, opera, ie or webpositive. Takes precedence over the
+    /// `BROWSER` environment variable. Falls back to the default browser when
+    /// it can't be opened.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub browser: Option<String>,
+
+    /// Print a QR code of the server's LAN URL for testing on mobile devices,
+    /// instead of opening the browser.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub qr_code: bool,
+
     /// Filter by issue severity.
     #[cfg_attr(feature = "cli", clap(short, long))]
     #[cfg_attr(feature = "serializable", serde(default))]
@@ -73,17 +97,206 @@ pub struct DevServerOptions {
     #[cfg_attr(feature = "serializable", serde(default))]
     pub log_detail: bool,
 
-    /// Whether to enable full task stats recording in Turbo Engine.
+    /// Treat issues of this category as errors, even if they are only
+    /// warnings. Can be passed multiple times.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub error_on: Vec<String>,
+
+    /// After each compilation, list the changed files which caused it, along
+    /// with the number of tasks it recomputed.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub explain_rebuilds: bool,
+
+    /// Don't record which files caused each compilation. This reduces the file
+    /// watching overhead on very large projects, at the cost of compilations
+    /// being logged without their reasons. Ignored with --explain-rebuilds.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub no_invalidation_reasons: bool,
+
+    /// Whether to enable full task stats recording in Turbo Engine. On unix,
+    /// sending SIGUSR2 to the server toggles it.
     #[cfg_attr(feature = "cli", clap(long))]
     #[cfg_attr(feature = "serializable", serde(default))]
     pub full_stats: bool,
 
+    /// The number of worker threads that run Turbo Engine tasks. 0 or unset
+    /// uses one per CPU core. Fewer threads trade compilation throughput for
+    /// less contention with other processes, e.g. on shared CI runners.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub threads: Option<usize>,
+
+    /// Exit with an error when more than this many Turbo Engine tasks are in
+    /// progress at the same time, which usually means that a misconfigured
+    /// glob or import pattern matches far too many files.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub max_tasks: Option<usize>,
+
     /// Enable experimental garbage collection with the provided memory limit in
     /// MB.
     #[cfg_attr(feature = "cli", clap(long))]
     #[cfg_attr(feature = "serializable", serde(default))]
     pub memory_limit: Option<usize>,
 
+    /// Pause file watching after the dev server has been idle for the given
+    /// number of seconds. Watching resumes with the next request.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub idle_timeout: Option<u64>,
+
+    /// Detect file changes by scanning the project every given number of
+    /// milliseconds instead of using native file events. Use this when changes
+    /// are not picked up, e.g. on network file systems or container volumes.
+    /// Scanning large projects often costs noticeable CPU.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "MS"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub watch_poll: Option<u64>,
+
+    /// How many more times to try watching the project directory when native
+    /// file events fail to initialize, e.g. because of the inotify limits.
+    /// Defaults to 3.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "N"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub watch_retries: Option<u32>,
+
+    /// When watching the project directory still fails after retrying, scan it
+    /// every given number of milliseconds instead. Defaults to 1000.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "MS"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub watch_fallback_poll: Option<u64>,
+
+    /// Fail to start when watching the project directory fails, instead of
+    /// falling back to scanning it.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub no_watch_fallback: bool,
+
+    /// Exit with an error when the first compilation hasn't finished within
+    /// the given number of seconds after the server has started, e.g. in CI
+    /// smoke tests.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "SECS"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub ready_timeout: Option<u64>,
+
+    /// How to tell a process supervisor that the server is listening and the
+    /// first compilation has finished: `stdout` only prints the ready banner,
+    /// `file:<path>` and `fd:<n>` also write a JSON line with the server URL
+    /// to a file or an inherited file descriptor (unix only).
+    #[cfg_attr(feature = "cli", clap(long, value_name = "stdout|file:PATH|fd:N"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub ready_signal: Option<String>,
+
+    /// Shorthand for `--ready-signal fd:<FD>`.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "FD"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub ready_fd: Option<i32>,
+
+    /// Shorthand for `--ready-signal file:<PATH>`, e.g. for a named pipe.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "PATH"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub ready_file: Option<PathBuf>,
+
+    /// Pathnames of routes to compile in the background once the server has
+    /// started, e.g. `--warm-routes /,/blog`.
+    #[cfg_attr(feature = "cli", clap(long, value_delimiter = ','))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub warm_routes: Vec<String>,
+
+    /// Adds a webpack loader rule in the form `GLOB=LOADER`, e.g.
+    /// `--loader '*.svg=@svgr/webpack'`. Repeat the flag to chain loaders for
+    /// a glob.
+    /// These rules replace the `experimental.turbo.rules` entry for the same
+    /// glob. Meant for quick experiments in development, not for production.
+    #[cfg_attr(feature = "cli", clap(long = "loader", value_name = "GLOB=LOADER"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub loader_rules: Vec<String>,
+
+    /// Rewrites the original source paths reported for stack frames, in the
+    /// form `FROM=TO`. Use this when the project is built from a different
+    /// directory than the editor has open, e.g. through a symlink.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "FROM=TO"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub source_root_remap: Option<String>,
+
+    /// A response header for the internal `__turbopack__`-style routes, in the
+    /// form `Name: value`. Can be repeated.
+    #[cfg_attr(
+        feature = "cli",
+        clap(long = "internal-route-header", value_name = "HEADER")
+    )]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub internal_route_headers: Vec<String>,
+
+    /// Serve the trace file written with `NEXT_TURBOPACK_TRACING` at
+    /// `/__nextjs_trace`, for downloading it from a remote dev server. The
+    /// trace reveals the paths and timings of the project.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub serve_trace: bool,
+
+    /// Serves the introspection UI at `/<ROUTE>` instead of `/__turbopack__`,
+    /// e.g. when an app route has the same path.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "ROUTE"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub introspection_route: Option<String>,
+
+    /// Serves the turbo-tasks visualization at `/<ROUTE>` instead of
+    /// `/__turbo_tasks__`, e.g. when an app route has the same path.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "ROUTE"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub turbo_tasks_route: Option<String>,
+
+    /// Serves requests with the `x-nextjs-raw: 1` header from the compiled
+    /// sources directly, skipping rewrites and middleware. A debugging aid for
+    /// rewrite issues.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub raw_asset_header: bool,
+
+    /// Don't serve the pages and build manifests the Next.js client uses for
+    /// client-side navigation. Only useful when embedding a single web entry,
+    /// where the manifest routes could collide with its paths.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub no_manifest_source: bool,
+
+    /// Builds the client chunks without hot module replacement, e.g. to
+    /// measure its overhead. Changes then need a reload.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub no_hmr: bool,
+
+    /// Compiles a service worker entry, relative to the project directory, and
+    /// serves it at `/sw.js`, or at the given pathname in the form
+    /// `ENTRY=PATHNAME`, e.g. `--service-worker service-worker.ts=/worker.js`.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "ENTRY[=PATHNAME]"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub service_worker: Option<String>,
+
+    /// Serves another project under a path prefix, in the form `PREFIX=DIR`,
+    /// e.g. `--mount docs=../docs` serves the project in `../docs` at
+    /// `/docs`. The project must be inside the root directory. Can be
+    /// repeated.
+    #[cfg_attr(feature = "cli", clap(long = "mount", value_name = "PREFIX=DIR"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub mounted_projects: Vec<String>,
+
+    /// A label shown above the page while it is compiling or failed to
+    /// compile, e.g. to tell several dev servers apart.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub fallback_label: Option<String>,
+
+    /// The favicon URL of the page shown while a page is compiling or failed
+    /// to compile, e.g. `/brand.ico`.
+    #[cfg_attr(feature = "cli", clap(long, value_name = "URL"))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub fallback_favicon: Option<String>,
+
     // ==
     // = Inherited options from next-dev, need revisit later.
     // ==
@@ -113,8 +326,8 @@ fn default_port() -> u16 {
 }
 
 #[cfg(feature = "serializable")]
-fn default_host() -> IpAddr {
+fn default_hosts() -> Vec<IpAddr> {
     // IPv6 address can accept both IPv4 and v6 requests.
     // https://nodejs.org/api/net.html#serverlistenport-host-backlog-callback
-    IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED)
+    vec![IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED)]
 }
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use turbo_tasks::{Value, Vc};
+use turbopack_binding::turbopack::dev_server::source::{
+    route_tree::{RouteTree, RouteType},
+    ContentSource, ContentSourceContent, ContentSourceData, GetContentSourceContent, HeaderList,
+    RewriteBuilder,
+};
+
+/// Adds `headers` to every response of the `inner` content source.
+#[turbo_tasks::value(shared)]
+pub struct ResponseHeadersContentSource {
+    inner: Vc<Box<dyn ContentSource>>,
+    headers: Vc<HeaderList>,
+}
+
+#[turbo_tasks::value_impl]
+impl ResponseHeadersContentSource {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: Vc<Box<dyn ContentSource>>,
+        headers: Vc<HeaderList>,
+    ) -> Vc<ResponseHeadersContentSource> {
+        ResponseHeadersContentSource { inner, headers }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for ResponseHeadersContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::CatchAll, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for ResponseHeadersContentSource {
+    #[turbo_tasks::function]
+    async fn get(
+        self: Vc<Self>,
+        path: String,
+        _data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let this = self.await?;
+        let sources = this.inner.get_routes().get(path).await?.clone_value();
+        let rewrite = RewriteBuilder::new_sources(Vc::cell(sources)).response_headers(this.headers);
+        Ok(ContentSourceContent::Rewrite(rewrite.build()).cell())
+    }
+}
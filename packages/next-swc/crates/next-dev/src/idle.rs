@@ -0,0 +1,235 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tokio::sync::{watch, Mutex as AsyncMutex};
+use turbo_tasks::{util::FormatDuration, TurboTasks};
+use turbopack_binding::turbo::{tasks_fs::DiskFileSystem, tasks_memory::MemoryBackend};
+
+/// A [DiskFileSystem] created by the dev server which is watched for changes.
+pub(crate) struct WatchedFileSystem {
+    pub name: String,
+    pub root: String,
+    pub with_invalidation_reason: bool,
+}
+
+/// When the server was last active, and whether it has been declared idle
+/// since. Both are updated under one lock, so a request can't slip in between
+/// the idleness check and pausing.
+struct Activity {
+    last: Instant,
+    idle: bool,
+}
+
+/// What is watched, and whether watching is paused. Pausing and resuming hold
+/// this lock until the file systems are updated, so they never interleave.
+#[derive(Default)]
+struct Watching {
+    turbo_tasks: Option<Arc<TurboTasks<MemoryBackend>>>,
+    file_systems: Vec<WatchedFileSystem>,
+    paused: bool,
+}
+
+/// Pauses file watching once the dev server has neither received a request
+/// nor had any work in progress for `timeout`, and resumes it on the next
+/// request.
+pub(crate) struct IdleMonitor {
+    timeout: Duration,
+    activity: Mutex<Activity>,
+    watching: AsyncMutex<Watching>,
+    /// Whether the server is idle, for the tasks that suspend meanwhile.
+    idle: watch::Sender<bool>,
+}
+
+impl IdleMonitor {
+    pub fn new(timeout: Duration) -> Arc<Self> {
+        Arc::new(IdleMonitor {
+            timeout,
+            activity: Mutex::new(Activity {
+                last: Instant::now(),
+                idle: false,
+            }),
+            watching: Default::default(),
+            idle: watch::channel(false).0,
+        })
+    }
+
+    /// Records activity, resuming file watching if the server was idle.
+    pub fn touch(self: &Arc<Self>) {
+        let was_idle = {
+            let mut activity = self.activity.lock().unwrap();
+            activity.last = Instant::now();
+            std::mem::replace(&mut activity.idle, false)
+        };
+        if was_idle {
+            let this = self.clone();
+            tokio::spawn(async move { this.resume().await });
+        }
+    }
+
+    /// Waits until the server is not idle.
+    pub async fn wait_until_active(&self) {
+        let mut idle = self.idle.subscribe();
+        while *idle.borrow_and_update() {
+            // The sender lives as long as `self`, so this can't fail.
+            if idle.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Spawns the background task that detects idleness, which pauses and
+    /// resumes watching the `file_systems`.
+    pub async fn start(
+        self: &Arc<Self>,
+        turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+        file_systems: Vec<WatchedFileSystem>,
+    ) {
+        {
+            let mut watching = self.watching.lock().await;
+            watching.turbo_tasks = Some(turbo_tasks.clone());
+            watching.file_systems = file_systems;
+        }
+        let this = self.clone();
+        tokio::spawn(async move {
+            loop {
+                this.wait_until_active().await;
+                let elapsed = {
+                    let mut activity = this.activity.lock().unwrap();
+                    if turbo_tasks.get_in_progress_count() > 0 {
+                        activity.last = Instant::now();
+                    }
+                    activity.last.elapsed()
+                };
+                if elapsed < this.timeout {
+                    tokio::time::sleep(this.timeout - elapsed).await;
+                    continue;
+                }
+                this.pause().await;
+            }
+        });
+    }
+
+    async fn pause(&self) {
+        let mut watching = self.watching.lock().await;
+        let elapsed = {
+            let mut activity = self.activity.lock().unwrap();
+            let elapsed = activity.last.elapsed();
+            // A request may have arrived since the idleness check.
+            if elapsed < self.timeout {
+                return;
+            }
+            activity.idle = true;
+            elapsed
+        };
+        self.idle.send_replace(true);
+        if watching.paused {
+            return;
+        }
+        match set_watching(&watching, false).await {
+            Ok(()) => {
+                watching.paused = true;
+                println!(
+                    "{event_type} - idle for {elapsed}, paused file watching",
+                    event_type = "event".purple(),
+                    elapsed = FormatDuration(elapsed),
+                )
+            }
+            Err(err) => eprintln!("failed to pause file watching: {err:?}"),
+        }
+    }
+
+    async fn resume(&self) {
+        let mut watching = self.watching.lock().await;
+        // A later pause may have won the race for the lock.
+        if self.activity.lock().unwrap().idle {
+            return;
+        }
+        if watching.paused {
+            match set_watching(&watching, true).await {
+                Ok(()) => {
+                    watching.paused = false;
+                    println!(
+                        "{event_type} - leaving idle state, resumed file watching",
+                        event_type = "event".purple(),
+                    )
+                }
+                Err(err) => eprintln!("failed to resume file watching: {err:?}"),
+            }
+        }
+        self.idle.send_replace(false);
+    }
+}
+
+async fn set_watching(watching: &Watching, enabled: bool) -> Result<()> {
+    let Some(turbo_tasks) = &watching.turbo_tasks else {
+        return Ok(());
+    };
+    for WatchedFileSystem {
+        name,
+        root,
+        with_invalidation_reason,
+    } in &watching.file_systems
+    {
+        let name = name.clone();
+        let root = root.clone();
+        let with_invalidation_reason = *with_invalidation_reason;
+        turbo_tasks
+            .run_once(async move {
+                // `DiskFileSystem::new` is a turbo-tasks function, so this
+                // resolves to the instance created by the dev server.
+                let disk_fs = DiskFileSystem::new(name, root).await?;
+                if !enabled {
+                    disk_fs.stop_watching();
+                } else {
+                    if with_invalidation_reason {
+                        disk_fs.start_watching_with_invalidation_reason()?;
+                    } else {
+                        disk_fs.start_watching()?;
+                    }
+                    // Changes made while paused have not been observed.
+                    disk_fs.invalidate();
+                }
+                Ok(())
+            })
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::IdleMonitor;
+
+    #[tokio::test]
+    async fn activity_prevents_pausing() {
+        let monitor = IdleMonitor::new(Duration::from_secs(60));
+        monitor.pause().await;
+        assert!(!*monitor.idle.borrow());
+    }
+
+    #[tokio::test]
+    async fn touch_resumes_after_pausing() {
+        let monitor = IdleMonitor::new(Duration::ZERO);
+        for _ in 0..100 {
+            // Pausing and resuming race for the lock, but a touch always
+            // leaves the server active.
+            let pause = tokio::spawn({
+                let monitor = monitor.clone();
+                async move { monitor.pause().await }
+            });
+            monitor.touch();
+            pause.await.unwrap();
+            monitor.touch();
+            tokio::time::timeout(Duration::from_secs(5), monitor.wait_until_active())
+                .await
+                .unwrap();
+            assert!(!monitor.watching.lock().await.paused);
+        }
+    }
+}
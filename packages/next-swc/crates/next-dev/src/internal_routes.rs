@@ -0,0 +1,189 @@
+use anyhow::{bail, Result};
+
+/// The reserved prefix of the internal routes, which yields the well-known
+/// `__turbopack__` and `__nextjs_rewrites` paths.
+pub const DEFAULT_ROUTE_PREFIX: &str = "__";
+
+/// The error overlay and the Next.js dev server request original stack frames
+/// from this path, so it doesn't move with the prefix.
+const ORIGINAL_STACK_FRAME_ROUTE: &str = "__nextjs_original-stack-frame";
+
+/// The paths the dev server mounts its internal content sources at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternalRoutes {
+    pub introspect: String,
+    pub turbo_tasks: String,
+    pub original_stack_frame: String,
+    pub rewrites: String,
+    /// Only served when tracing is enabled.
+    pub trace: String,
+}
+
+impl InternalRoutes {
+    pub fn new(prefix: &str) -> Self {
+        InternalRoutes {
+            introspect: format!("{prefix}turbopack__"),
+            turbo_tasks: format!("{prefix}turbo_tasks__"),
+            original_stack_frame: ORIGINAL_STACK_FRAME_ROUTE.to_string(),
+            rewrites: format!("{prefix}nextjs_rewrites"),
+            trace: format!("{prefix}nextjs_trace"),
+        }
+    }
+
+    /// Whether `path` is handled by one of the internal routes instead of the
+    /// app.
+    pub fn is_internal(&self, path: &str) -> bool {
+        let path = path.trim_start_matches('/');
+        [
+            &self.introspect,
+            &self.turbo_tasks,
+            &self.original_stack_frame,
+            &self.rewrites,
+            &self.trace,
+        ]
+        .iter()
+        .any(|route| {
+            path.strip_prefix(route.as_str())
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// The first internal route which would shadow the app under
+    /// `base_path`, i.e. which is the first segment of it.
+    pub fn base_path_collision(&self, base_path: &str) -> Option<&str> {
+        let first_segment = base_path.trim_start_matches('/').split('/').next()?;
+        [
+            &self.introspect,
+            &self.turbo_tasks,
+            &self.original_stack_frame,
+            &self.rewrites,
+            &self.trace,
+        ]
+        .into_iter()
+        .find(|route| route.as_str() == first_segment)
+        .map(String::as_str)
+    }
+}
+
+/// Parses a header in the `Name: value` form.
+pub fn parse_header(header: &str) -> Result<(String, String)> {
+    let Some((name, value)) = header.split_once(':') else {
+        bail!("invalid header {header:?}, expected `Name: value`");
+    };
+    let name = name.trim();
+    if name.is_empty() || name.chars().any(|c| c.is_ascii_whitespace()) {
+        bail!("invalid header name {name:?}");
+    }
+    Ok((name.to_string(), value.trim().to_string()))
+}
+
+/// Checks that `prefix` can be used as the start of a URL path segment.
+pub fn validate_route_prefix(prefix: &str) -> Result<()> {
+    validate_url_safe("internal route prefix", prefix)
+}
+
+/// Checks that `route`, e.g. the path of the introspection route, is a single
+/// URL path segment.
+pub fn validate_route(name: &str, route: &str) -> Result<()> {
+    validate_url_safe(&format!("{name} route"), route)
+}
+
+fn validate_url_safe(what: &str, value: &str) -> Result<()> {
+    if value.is_empty() {
+        bail!("the {what} must not be empty");
+    }
+    if let Some(c) = value
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !matches!(c, '-' | '_' | '.' | '~'))
+    {
+        bail!("the {what} {value:?} contains {c:?}, which is not URL-safe");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_header, validate_route, validate_route_prefix, InternalRoutes, DEFAULT_ROUTE_PREFIX,
+    };
+
+    #[test]
+    fn default_prefix() {
+        assert_eq!(
+            InternalRoutes::new(DEFAULT_ROUTE_PREFIX),
+            InternalRoutes {
+                introspect: "__turbopack__".to_string(),
+                turbo_tasks: "__turbo_tasks__".to_string(),
+                original_stack_frame: "__nextjs_original-stack-frame".to_string(),
+                rewrites: "__nextjs_rewrites".to_string(),
+                trace: "__nextjs_trace".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn custom_prefix_frees_colliding_routes() {
+        let routes = InternalRoutes::new(DEFAULT_ROUTE_PREFIX);
+        assert!(routes.is_internal("/__turbopack__/graph"));
+
+        let routes = InternalRoutes::new("_internal-");
+        assert!(!routes.is_internal("/__turbopack__/graph"));
+        assert!(routes.is_internal("/_internal-turbopack__/graph"));
+        assert!(routes.is_internal("/_internal-nextjs_rewrites"));
+        assert!(routes.is_internal("/_internal-nextjs_trace"));
+    }
+
+    #[test]
+    fn original_stack_frame_route_is_fixed() {
+        // The error overlay requests this path regardless of the prefix.
+        let routes = InternalRoutes::new("_internal-");
+        assert_eq!(routes.original_stack_frame, "__nextjs_original-stack-frame");
+        assert!(routes.is_internal("/__nextjs_original-stack-frame"));
+        assert!(!routes.is_internal("/_internal-nextjs_original-stack-frame"));
+    }
+
+    #[test]
+    fn validates_prefix() {
+        assert!(validate_route_prefix("__").is_ok());
+        assert!(validate_route_prefix("_dev.internal~").is_ok());
+        assert!(validate_route_prefix("").is_err());
+        assert!(validate_route_prefix("a/b").is_err());
+        assert!(validate_route_prefix("a b").is_err());
+    }
+
+    #[test]
+    fn validates_routes() {
+        assert!(validate_route("introspection", "_devtools").is_ok());
+        assert!(validate_route("introspection", "").is_err());
+        assert!(validate_route("introspection", "dev/tools").is_err());
+    }
+
+    #[test]
+    fn detects_base_path_collisions() {
+        let mut routes = InternalRoutes::new(DEFAULT_ROUTE_PREFIX);
+        assert_eq!(routes.base_path_collision(""), None);
+        assert_eq!(routes.base_path_collision("/docs"), None);
+        assert_eq!(
+            routes.base_path_collision("/__turbopack__/app"),
+            Some("__turbopack__")
+        );
+
+        routes.turbo_tasks = "docs".to_string();
+        assert_eq!(routes.base_path_collision("/docs"), Some("docs"));
+        assert_eq!(routes.base_path_collision("/docs-v2"), None);
+    }
+
+    #[test]
+    fn parses_headers() {
+        assert_eq!(
+            parse_header("X-Robots-Tag: noindex").unwrap(),
+            ("X-Robots-Tag".to_string(), "noindex".to_string())
+        );
+        assert_eq!(
+            parse_header("Cache-Control:no-store").unwrap(),
+            ("Cache-Control".to_string(), "no-store".to_string())
+        );
+        assert!(parse_header("no-store").is_err());
+        assert!(parse_header(": no-store").is_err());
+    }
+}
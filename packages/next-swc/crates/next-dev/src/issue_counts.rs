@@ -0,0 +1,247 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use turbo_tasks::{RawVc, ReadRef, TransientInstance, TransientValue, Vc};
+use turbopack_binding::turbopack::core::issue::{
+    CapturedIssues, IssueReporter, IssueSeverity, PlainIssue,
+};
+
+/// The file path, category, title and description of an issue.
+type IssueKey = (String, String, String, String);
+
+/// The number of distinct errors and warnings reported during a compilation.
+/// Bugs and fatal issues count as errors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IssueCounts {
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+impl IssueCounts {
+    /// Counts one issue of each of the `severities`.
+    pub fn from_severities(severities: impl IntoIterator<Item = IssueSeverity>) -> Self {
+        let mut counts = Self::default();
+        for severity in severities {
+            match Counted::of(severity) {
+                Some(Counted::Error) => counts.errors += 1,
+                Some(Counted::Warning) => counts.warnings += 1,
+                None => {}
+            }
+        }
+        counts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors == 0 && self.warnings == 0
+    }
+}
+
+/// What an issue counts as.
+enum Counted {
+    Error,
+    Warning,
+}
+
+impl Counted {
+    fn of(severity: IssueSeverity) -> Option<Self> {
+        match severity {
+            IssueSeverity::Bug | IssueSeverity::Fatal | IssueSeverity::Error => {
+                Some(Counted::Error)
+            }
+            IssueSeverity::Warning => Some(Counted::Warning),
+            _ => None,
+        }
+    }
+}
+
+impl Display for IssueCounts {
+    /// Formats the counts as e.g. `3 errors, 1 warning`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} error{}, {} warning{}",
+            self.errors,
+            plural(self.errors),
+            self.warnings,
+            plural(self.warnings)
+        )
+    }
+}
+
+#[derive(Default)]
+struct SeenIssues {
+    errors: HashSet<IssueKey>,
+    warnings: HashSet<IssueKey>,
+}
+
+/// Collects the errors and warnings reported since the last
+/// [IssueCounter::take], so a summary can be printed after each compilation.
+/// An issue which is reported by several requests is counted once.
+#[derive(Default)]
+pub struct IssueCounter {
+    seen: Mutex<SeenIssues>,
+}
+
+impl IssueCounter {
+    fn record(&self, issue: &PlainIssue) {
+        let key = (
+            issue.file_path.clone(),
+            issue.category.clone(),
+            issue.title.clone(),
+            issue.description.clone(),
+        );
+        self.record_key(issue.severity, key);
+    }
+
+    fn record_key(&self, severity: IssueSeverity, key: IssueKey) {
+        let mut seen = self.seen.lock().unwrap();
+        match Counted::of(severity) {
+            Some(Counted::Error) => {
+                seen.errors.insert(key);
+            }
+            Some(Counted::Warning) => {
+                seen.warnings.insert(key);
+            }
+            None => {}
+        }
+    }
+
+    /// Returns the counts since the last call and starts counting anew.
+    pub fn take(&self) -> IssueCounts {
+        let seen = std::mem::take(&mut *self.seen.lock().unwrap());
+        IssueCounts {
+            errors: seen.errors.len(),
+            warnings: seen.warnings.len(),
+        }
+    }
+}
+
+/// Wraps an [IssueReporter] and records the reported issues in an
+/// [IssueCounter]. The wrapped reporter still receives every report.
+#[turbo_tasks::value(shared, serialization = "none", eq = "manual", cell = "new")]
+pub(crate) struct CountingIssueReporter {
+    reporter: Vc<Box<dyn IssueReporter>>,
+    #[turbo_tasks(trace_ignore, debug_ignore)]
+    counter: TransientInstance<Arc<IssueCounter>>,
+}
+
+#[turbo_tasks::value_impl]
+impl CountingIssueReporter {
+    #[turbo_tasks::function]
+    pub fn new(
+        reporter: Vc<Box<dyn IssueReporter>>,
+        counter: TransientInstance<Arc<IssueCounter>>,
+    ) -> Vc<Self> {
+        CountingIssueReporter { reporter, counter }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl IssueReporter for CountingIssueReporter {
+    #[turbo_tasks::function]
+    async fn report_issues(
+        &self,
+        issues: TransientInstance<ReadRef<CapturedIssues>>,
+        source: TransientValue<RawVc>,
+        min_failing_severity: Vc<IssueSeverity>,
+    ) -> Result<Vc<bool>> {
+        for issue in issues.get_plain_issues().await? {
+            self.counter.record(&issue);
+        }
+        Ok(self
+            .reporter
+            .report_issues(issues, source, min_failing_severity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use turbopack_binding::turbopack::core::issue::IssueSeverity;
+
+    use super::{IssueCounter, IssueCounts, IssueKey};
+
+    fn key(title: &str) -> IssueKey {
+        (
+            "/project/pages/index.js".to_string(),
+            "resolve".to_string(),
+            title.to_string(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn counts_reset_between_rebuilds() {
+        let counter = IssueCounter::default();
+
+        // The first compilation reports an error twice, e.g. from two
+        // requests, and a warning.
+        counter.record_key(IssueSeverity::Error, key("Module not found"));
+        counter.record_key(IssueSeverity::Error, key("Module not found"));
+        counter.record_key(IssueSeverity::Warning, key("Unsupported attribute"));
+        counter.record_key(IssueSeverity::Hint, key("Unused import"));
+        assert_eq!(
+            counter.take(),
+            IssueCounts {
+                errors: 1,
+                warnings: 1
+            }
+        );
+
+        // A rebuild which fixed the error only counts the warning it reports.
+        counter.record_key(IssueSeverity::Warning, key("Unsupported attribute"));
+        assert_eq!(
+            counter.take(),
+            IssueCounts {
+                errors: 0,
+                warnings: 1
+            }
+        );
+
+        // A rebuild without issues.
+        assert!(counter.take().is_empty());
+    }
+
+    #[test]
+    fn counts_severities() {
+        assert_eq!(
+            IssueCounts::from_severities([
+                IssueSeverity::Bug,
+                IssueSeverity::Fatal,
+                IssueSeverity::Error,
+                IssueSeverity::Warning,
+                IssueSeverity::Hint,
+                IssueSeverity::Note,
+            ]),
+            IssueCounts {
+                errors: 3,
+                warnings: 1
+            }
+        );
+    }
+
+    #[test]
+    fn formats_counts() {
+        assert_eq!(
+            IssueCounts {
+                errors: 3,
+                warnings: 5
+            }
+            .to_string(),
+            "3 errors, 5 warnings"
+        );
+        assert_eq!(
+            IssueCounts {
+                errors: 1,
+                warnings: 0
+            }
+            .to_string(),
+            "1 error, 0 warnings"
+        );
+        assert!(IssueCounts::default().is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use turbo_tasks::{RawVc, ReadRef, TransientInstance, TransientValue, Vc};
+use turbopack_binding::turbopack::core::issue::{
+    CapturedIssues, IssueReporter, IssueSeverity, PlainIssue,
+};
+
+/// The file path, category, title and description of an issue.
+type IssueKey = (String, String, String, String);
+
+/// Receives each issue once, as soon as a report contains it for the first
+/// time, e.g. to keep a live list of issues in a custom overlay. The issue
+/// carries its severity and source.
+pub trait IssueListener: Send + Sync + 'static {
+    fn on_issue(&self, issue: ReadRef<PlainIssue>);
+
+    /// Receives an issue once no report contains it anymore, e.g. after the
+    /// file it was found in was fixed.
+    fn on_issue_removed(&self, _issue: ReadRef<PlainIssue>) {}
+}
+
+impl<T> IssueListener for T
+where
+    T: Fn(ReadRef<PlainIssue>) + Send + Sync + 'static,
+{
+    fn on_issue(&self, issue: ReadRef<PlainIssue>) {
+        self(issue)
+    }
+}
+
+/// Which sources report which issues. Generic over the source and the issue,
+/// so the bookkeeping doesn't depend on turbo-tasks.
+struct IssueSources<S, T> {
+    /// The issues of the last report of each source.
+    by_source: HashMap<S, HashSet<IssueKey>>,
+    /// Each current issue with the number of sources which report it.
+    issues: HashMap<IssueKey, (T, usize)>,
+}
+
+impl<S, T> Default for IssueSources<S, T> {
+    fn default() -> Self {
+        IssueSources {
+            by_source: HashMap::new(),
+            issues: HashMap::new(),
+        }
+    }
+}
+
+impl<S: Hash + Eq, T: Clone> IssueSources<S, T> {
+    /// Replaces the issues of `source` and returns the issues which no source
+    /// reported before, and the issues which no source reports anymore.
+    fn update(
+        &mut self,
+        source: S,
+        issues: impl IntoIterator<Item = (IssueKey, T)>,
+    ) -> (Vec<T>, Vec<T>) {
+        let previous = self.by_source.remove(&source).unwrap_or_default();
+        let mut keys = HashSet::new();
+        let mut added = vec![];
+        for (key, issue) in issues {
+            // An issue this source reported before is already counted.
+            if !keys.insert(key.clone()) || previous.contains(&key) {
+                continue;
+            }
+            match self.issues.entry(key) {
+                Entry::Occupied(mut entry) => entry.get_mut().1 += 1,
+                Entry::Vacant(entry) => {
+                    added.push(issue.clone());
+                    entry.insert((issue, 1));
+                }
+            }
+        }
+        let mut removed = vec![];
+        for key in previous.difference(&keys) {
+            if let Entry::Occupied(mut entry) = self.issues.entry(key.clone()) {
+                entry.get_mut().1 -= 1;
+                if entry.get().1 == 0 {
+                    removed.push(entry.remove().0);
+                }
+            }
+        }
+        self.by_source.insert(source, keys);
+        (added, removed)
+    }
+}
+
+/// The issues an [IssueListener] has been told about. A single instance is
+/// shared by all reporters, like an
+/// [IssueCounter](crate::issue_counts::IssueCounter), since a new reporter is
+/// created for every request.
+pub(crate) struct ListenedIssues {
+    listener: Arc<dyn IssueListener>,
+    sources: Mutex<IssueSources<RawVc, ReadRef<PlainIssue>>>,
+}
+
+impl ListenedIssues {
+    pub fn new(listener: Arc<dyn IssueListener>) -> Self {
+        ListenedIssues {
+            listener,
+            sources: Default::default(),
+        }
+    }
+
+    /// Replaces the issues of `source` with `issues`. New issues are passed to
+    /// the listener, and so are issues no source reports anymore.
+    fn update(&self, source: RawVc, issues: Vec<ReadRef<PlainIssue>>) {
+        let (added, removed) = self.sources.lock().unwrap().update(
+            source,
+            issues.into_iter().map(|issue| (issue_key(&issue), issue)),
+        );
+        // The listener is called without holding the lock, so it may take its
+        // time.
+        for issue in removed {
+            self.listener.on_issue_removed(issue);
+        }
+        for issue in added {
+            self.listener.on_issue(issue);
+        }
+    }
+}
+
+fn issue_key(issue: &PlainIssue) -> IssueKey {
+    (
+        issue.file_path.clone(),
+        issue.category.clone(),
+        issue.title.clone(),
+        issue.description.clone(),
+    )
+}
+
+/// Wraps an [IssueReporter] and passes the issues it hasn't seen before to an
+/// [IssueListener]. The wrapped reporter still receives every report.
+#[turbo_tasks::value(shared, serialization = "none", eq = "manual", cell = "new")]
+pub(crate) struct ListeningIssueReporter {
+    reporter: Vc<Box<dyn IssueReporter>>,
+    #[turbo_tasks(trace_ignore, debug_ignore)]
+    listened: TransientInstance<Arc<ListenedIssues>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ListeningIssueReporter {
+    #[turbo_tasks::function]
+    pub fn new(
+        reporter: Vc<Box<dyn IssueReporter>>,
+        listened: TransientInstance<Arc<ListenedIssues>>,
+    ) -> Vc<Self> {
+        ListeningIssueReporter { reporter, listened }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl IssueReporter for ListeningIssueReporter {
+    #[turbo_tasks::function]
+    async fn report_issues(
+        &self,
+        issues: TransientInstance<ReadRef<CapturedIssues>>,
+        source: TransientValue<RawVc>,
+        min_failing_severity: Vc<IssueSeverity>,
+    ) -> Result<Vc<bool>> {
+        self.listened
+            .update(*source, issues.get_plain_issues().await?);
+        Ok(self
+            .reporter
+            .report_issues(issues, source, min_failing_severity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IssueKey, IssueSources};
+
+    fn issue(title: &'static str) -> (IssueKey, &'static str) {
+        (
+            (
+                "/project/index.js".to_string(),
+                "parse".to_string(),
+                title.to_string(),
+                String::new(),
+            ),
+            title,
+        )
+    }
+
+    #[test]
+    fn reports_added_and_removed_issues_once() {
+        let mut sources = IssueSources::default();
+
+        assert_eq!(
+            sources.update("a", [issue("x"), issue("y")]),
+            (vec!["x", "y"], vec![])
+        );
+        assert_eq!(sources.update("b", [issue("x")]), (vec![], vec![]));
+        assert_eq!(
+            sources.update("a", [issue("x"), issue("y")]),
+            (vec![], vec![])
+        );
+        // `x` is still reported by `b`.
+        assert_eq!(sources.update("a", []), (vec![], vec!["y"]));
+        assert_eq!(sources.update("b", []), (vec![], vec!["x"]));
+        assert_eq!(sources.update("a", [issue("x")]), (vec!["x"], vec![]));
+    }
+}
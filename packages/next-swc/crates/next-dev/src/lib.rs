@@ -4,39 +4,78 @@
 #![feature(async_fn_in_trait)]
 
 pub mod devserver_options;
+mod headers_source;
+mod idle;
+pub mod internal_routes;
+pub mod issue_counts;
+pub mod issue_listener;
+mod poll_watcher;
+mod qr_code;
+mod ready_signal;
+pub mod task_limit;
+mod trace_source;
 mod turbo_tasks_viz;
+pub mod update_reasons;
+pub mod update_tasks;
+mod warmup;
 
 use std::{
     collections::HashSet,
     env::current_dir,
     future::{join, Future},
     io::{stdout, Write},
-    net::{IpAddr, SocketAddr},
+    net::{IpAddr, Ipv6Addr, SocketAddr},
     path::{Path, PathBuf, MAIN_SEPARATOR},
     sync::Arc,
     time::{Duration, Instant},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use devserver_options::DevServerOptions;
 use dunce::canonicalize;
+use futures::future::try_join_all;
+use headers_source::ResponseHeadersContentSource;
+use idle::{IdleMonitor, WatchedFileSystem};
 use indexmap::IndexMap;
+use internal_routes::{
+    parse_header, validate_route, validate_route_prefix, InternalRoutes, DEFAULT_ROUTE_PREFIX,
+};
+use issue_counts::{CountingIssueReporter, IssueCounter};
+use issue_listener::{IssueListener, ListenedIssues, ListeningIssueReporter};
 use next_core::{
-    app_structure::find_app_dir_if_enabled,
-    create_app_source, create_page_source, create_web_entry_source,
+    app_structure::{find_app_dir_if_enabled, get_entrypoints, OptionAppDir},
+    client_source_maps::{CheapSourceMapsContentSource, ClientSourceMapsDisabledContentSource},
+    create_app_source, create_page_source, create_service_worker_source, create_web_entry_source,
     dev_manifest::DevManifestContentSource,
+    etag_source::ETagContentSource,
+    issue_reporter::ErrorOnIssueReporter,
+    middleware::middleware_files,
     mode::NextMode,
     next_client::{get_client_chunking_context, get_client_compile_time_info},
-    next_config::{load_next_config, load_rewrites},
+    next_config::{
+        load_next_config, load_next_config_or_default, load_rewrites, load_rewrites_or_default,
+        NextConfig, SourceMapLevel,
+    },
     next_image::NextImageContentSource,
-    pages_structure::find_pages_structure,
+    pages_structure::{find_pages_structure, PagesDirectoryStructure, PagesStructure},
+    raw_asset_source::RawAssetContentSource,
+    rewrites_source::RewritesContentSource,
     router_source::NextRouterContentSource,
     source_map::NextSourceMapTraceContentSource,
+    source_root_remap::SourceRootRemapContentSource,
     tracing_presets::{
         TRACING_NEXT_TARGETS, TRACING_NEXT_TURBOPACK_TARGETS, TRACING_NEXT_TURBO_TASKS_TARGETS,
     },
+    util::{internal_dir, DEFAULT_DIST_DIR},
+    FallbackBranding,
 };
 use owo_colors::OwoColorize;
+use poll_watcher::{
+    polling_would_help, retry_with_backoff, watch_mode, PollWatcher, WatchFallbackIssue, WatchMode,
+};
+use ready_signal::{signal_ready, ReadySignal};
+use serde_json::json;
+use trace_source::{TraceFile, TraceFileSource};
 use tracing_subscriber::{prelude::*, EnvFilter, Registry};
 use turbo_tasks::{
     util::FormatDuration, StatsType, TransientInstance, TurboTasks, TurboTasksBackendApi,
@@ -45,7 +84,7 @@ use turbo_tasks::{
 use turbopack_binding::{
     turbo::{
         tasks_env::{CustomProcessEnv, ProcessEnv},
-        tasks_fs::{DiskFileSystem, FileSystem},
+        tasks_fs::{DiskFileSystem, FileSystem, FileSystemPath},
         tasks_memory::MemoryBackend,
     },
     turbopack::{
@@ -58,8 +97,8 @@ use turbopack_binding::{
         },
         core::{
             environment::ServerAddr,
-            issue::{IssueReporter, IssueSeverity},
-            resolve::parse::Request,
+            issue::{IssueExt, IssueReporter, IssueSeverity},
+            resolve::{find_context_file, parse::Request, FindContextFileResult},
             server_fs::ServerFileSystem,
             PROJECT_FILESYSTEM_NAME,
         },
@@ -68,7 +107,7 @@ use turbopack_binding::{
             introspect::IntrospectionSource,
             source::{
                 combined::CombinedContentSource, router::PrefixedRouterContentSource,
-                static_assets::StaticAssetsContentSource, ContentSource,
+                static_assets::StaticAssetsContentSource, ContentSource, HeaderList,
             },
             DevServer, DevServerBuilder,
         },
@@ -77,6 +116,8 @@ use turbopack_binding::{
         turbopack::evaluate_context::node_build_environment,
     },
 };
+use update_reasons::UpdateReasons;
+use update_tasks::{CreatedTaskCounter, UpdateTaskBreakdown};
 
 #[derive(Clone)]
 pub enum EntryRequest {
@@ -84,20 +125,86 @@ pub enum EntryRequest {
     Module(String, String),
 }
 
+/// A project which is served under `/{prefix}` next to the main project.
+struct MountedProject {
+    prefix: String,
+    /// Mounted projects have no entry requests, no service worker and no
+    /// watch failure of their own.
+    options: Arc<DevSourcesOptions>,
+}
+
+/// The options of the sources of a project. These are created once when the
+/// server is built and the same instance is passed on every request, so the
+/// sources are reused across requests.
+#[derive(Clone)]
+struct DevSourcesOptions {
+    root_dir: String,
+    project_dir: String,
+    entry_requests: Vec<EntryRequest>,
+    eager_compile: bool,
+    turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+    browserslist_query: String,
+    server_addr: SocketAddr,
+    project_fs_override: Option<Vc<Box<dyn FileSystem>>>,
+    output_fs_override: Option<Vc<Box<dyn FileSystem>>>,
+    native_watching: bool,
+    invalidation_reasons: bool,
+    loader_rules: IndexMap<String, Vec<String>>,
+    source_root_remap: Option<(String, String)>,
+    internal_routes: InternalRoutes,
+    internal_route_headers: Vec<(String, String)>,
+    service_worker: Option<(String, String)>,
+    fallback_branding: FallbackBranding,
+    raw_asset_header: bool,
+    enable_manifest_source: bool,
+    watch_retries: u32,
+    watch_failure: Option<String>,
+    /// The prefix a mounted project is served under, which becomes its
+    /// `basePath` and `assetPrefix`.
+    mount_prefix: Option<String>,
+    trace_file: Option<TraceFile>,
+    enable_hmr: Option<bool>,
+}
+
 pub struct NextDevServerBuilder {
     turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
     project_dir: String,
     root_dir: String,
     entry_requests: Vec<EntryRequest>,
     eager_compile: bool,
-    hostname: Option<IpAddr>,
+    hostnames: Vec<IpAddr>,
     issue_reporter: Option<Box<dyn IssueReporterProvider>>,
+    issue_listener: Option<Arc<dyn IssueListener>>,
+    issue_counter: Option<Arc<IssueCounter>>,
     port: Option<u16>,
     browserslist_query: String,
     log_level: IssueSeverity,
     show_all: bool,
     log_detail: bool,
+    invalidation_reasons: bool,
+    error_on: Vec<String>,
     allow_retry: bool,
+    project_fs: Option<Vc<Box<dyn FileSystem>>>,
+    output_fs: Option<Vc<Box<dyn FileSystem>>>,
+    idle_monitor: Option<Arc<IdleMonitor>>,
+    watch_poll: Option<Duration>,
+    watch_retries: u32,
+    watch_fallback_poll: Option<Duration>,
+    warm_routes: Vec<String>,
+    loader_rules: IndexMap<String, Vec<String>>,
+    source_root_remap: Option<(String, String)>,
+    route_prefix: String,
+    introspection_route: Option<String>,
+    turbo_tasks_route: Option<String>,
+    internal_route_headers: Vec<(String, String)>,
+    service_worker: Option<(String, String)>,
+    mounted_projects: Vec<(String, String)>,
+    fallback_branding: FallbackBranding,
+    raw_asset_header: bool,
+    enable_manifest_source: bool,
+    trace_file: Option<TraceFile>,
+    enable_hmr: Option<bool>,
+    start: Instant,
 }
 
 impl NextDevServerBuilder {
@@ -112,8 +219,10 @@ impl NextDevServerBuilder {
             root_dir,
             entry_requests: vec![],
             eager_compile: false,
-            hostname: None,
+            hostnames: vec![],
             issue_reporter: None,
+            issue_listener: None,
+            issue_counter: None,
             port: None,
             browserslist_query: "last 1 Chrome versions, last 1 Firefox versions, last 1 Safari \
                                  versions, last 1 Edge versions"
@@ -121,7 +230,30 @@ impl NextDevServerBuilder {
             log_level: IssueSeverity::Warning,
             show_all: false,
             log_detail: false,
+            invalidation_reasons: true,
+            error_on: vec![],
             allow_retry: false,
+            project_fs: None,
+            output_fs: None,
+            idle_monitor: None,
+            watch_poll: None,
+            watch_retries: DEFAULT_WATCH_RETRIES,
+            watch_fallback_poll: Some(DEFAULT_WATCH_FALLBACK_POLL),
+            warm_routes: vec![],
+            loader_rules: IndexMap::new(),
+            source_root_remap: None,
+            route_prefix: DEFAULT_ROUTE_PREFIX.to_string(),
+            introspection_route: None,
+            turbo_tasks_route: None,
+            internal_route_headers: vec![],
+            service_worker: None,
+            mounted_projects: vec![],
+            fallback_branding: FallbackBranding::default(),
+            raw_asset_header: false,
+            enable_manifest_source: true,
+            trace_file: None,
+            enable_hmr: None,
+            start: Instant::now(),
         }
     }
 
@@ -135,8 +267,11 @@ impl NextDevServerBuilder {
         self
     }
 
+    /// Adds an address to listen on. Can be called multiple times, e.g. to be
+    /// reachable on loopback and on the network. All addresses use the same
+    /// port.
     pub fn hostname(mut self, hostname: IpAddr) -> NextDevServerBuilder {
-        self.hostname = Some(hostname);
+        self.hostnames.push(hostname);
         self
     }
 
@@ -170,6 +305,198 @@ impl NextDevServerBuilder {
         self
     }
 
+    /// Whether to record which files caused each compilation, as reported by
+    /// [UpdateInfo::reasons]. Defaults to `true`. Turning it off reduces the
+    /// file watching overhead on very large projects.
+    pub fn invalidation_reasons(mut self, invalidation_reasons: bool) -> NextDevServerBuilder {
+        self.invalidation_reasons = invalidation_reasons;
+        self
+    }
+
+    /// Treats issues of the given categories as errors, even if they are only
+    /// warnings. Only applies to the default console reporter.
+    pub fn error_on(mut self, error_on: Vec<String>) -> NextDevServerBuilder {
+        self.error_on = error_on;
+        self
+    }
+
+    /// Serves the project from the given file system instead of a watched
+    /// [DiskFileSystem], e.g. an in-memory one for hermetic tests. The file
+    /// system's root corresponds to `root_dir`.
+    ///
+    /// The file system must be resolved, as it is used outside of the task
+    /// it was created in.
+    pub fn project_fs(mut self, project_fs: Vc<Box<dyn FileSystem>>) -> NextDevServerBuilder {
+        self.project_fs = Some(project_fs);
+        self
+    }
+
+    /// Writes build output to the given file system instead of a
+    /// [DiskFileSystem] rooted at `project_dir`.
+    ///
+    /// The file system must be resolved, as it is used outside of the task
+    /// it was created in.
+    pub fn output_fs(mut self, output_fs: Vc<Box<dyn FileSystem>>) -> NextDevServerBuilder {
+        self.output_fs = Some(output_fs);
+        self
+    }
+
+    /// Pauses file watching after the dev server has been idle for the given
+    /// duration. Watching resumes with the next request.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> NextDevServerBuilder {
+        self.idle_monitor = Some(IdleMonitor::new(idle_timeout));
+        self
+    }
+
+    /// The monitor created by [Self::idle_timeout], so other background work
+    /// can be suspended while the server is idle.
+    pub(crate) fn idle_monitor(&self) -> Option<Arc<IdleMonitor>> {
+        self.idle_monitor.clone()
+    }
+
+    /// Detects file changes by scanning the project every `interval` instead
+    /// of using native file events, for file systems that don't deliver them,
+    /// e.g. network shares and some container volumes. Each scan stats every
+    /// file under the root directory, so shorter intervals and larger trees
+    /// cost more CPU.
+    pub fn watch_poll(mut self, interval: Duration) -> NextDevServerBuilder {
+        self.watch_poll = Some(interval);
+        self
+    }
+
+    /// How many more times to try setting up native file events when they
+    /// fail to initialize, e.g. because of the inotify limits. Defaults to 3.
+    pub fn watch_retries(mut self, watch_retries: u32) -> NextDevServerBuilder {
+        self.watch_retries = watch_retries;
+        self
+    }
+
+    /// Scans the project every `interval` when native file events can't be
+    /// set up, see [NextDevServerBuilder::watch_poll]. Defaults to every
+    /// second. With `None`, the dev server fails to start instead.
+    pub fn watch_fallback_poll(mut self, interval: Option<Duration>) -> NextDevServerBuilder {
+        self.watch_fallback_poll = interval;
+        self
+    }
+
+    /// Compiles the route with the given pathname in the background once the
+    /// server has started, while other routes stay lazily compiled.
+    pub fn warm_route(mut self, pathname: String) -> NextDevServerBuilder {
+        self.warm_routes.push(pathname);
+        self
+    }
+
+    /// Applies `loader` to files matching `glob`, in addition to the rules in
+    /// `next.config.js`. Loaders added for the same glob run in order, and
+    /// replace a configured rule for that glob.
+    ///
+    /// This is a convenience for local experiments; production builds don't
+    /// see these rules.
+    pub fn loader_rule(mut self, glob: String, loader: String) -> NextDevServerBuilder {
+        self.loader_rules.entry(glob).or_default().push(loader);
+        self
+    }
+
+    /// Replaces the `from` prefix of original paths in traced stack frames
+    /// with `to`, so they point at the files the editor has open when the
+    /// project is built from a different directory, e.g. through a symlink.
+    pub fn source_root_remap(mut self, from: String, to: String) -> NextDevServerBuilder {
+        self.source_root_remap = Some((from, to));
+        self
+    }
+
+    /// Sets the prefix of the dev server's internal routes, e.g.
+    /// `__turbopack__` and `__nextjs_rewrites` for the default `__`. Use this
+    /// to move them out of the way of an app route with the same path. The
+    /// prefix must be URL-safe. `__nextjs_original-stack-frame` stays in place,
+    /// since the error overlay requests it at that path.
+    pub fn route_prefix(mut self, route_prefix: String) -> NextDevServerBuilder {
+        self.route_prefix = route_prefix;
+        self
+    }
+
+    /// Serves the introspection UI at `/{route}` instead of `/__turbopack__`,
+    /// whatever the route prefix. The route must be a single URL-safe path
+    /// segment.
+    pub fn introspection_route(mut self, route: String) -> NextDevServerBuilder {
+        self.introspection_route = Some(route);
+        self
+    }
+
+    /// Serves the turbo-tasks visualization at `/{route}` instead of
+    /// `/__turbo_tasks__`, whatever the route prefix. The route must be a
+    /// single URL-safe path segment.
+    pub fn turbo_tasks_route(mut self, route: String) -> NextDevServerBuilder {
+        self.turbo_tasks_route = Some(route);
+        self
+    }
+
+    /// Adds a response header to the dev server's internal routes, e.g.
+    /// `X-Robots-Tag: noindex`, for when they are served behind a proxy. App
+    /// routes are not affected.
+    pub fn internal_route_header(mut self, name: String, value: String) -> NextDevServerBuilder {
+        self.internal_route_headers.push((name, value));
+        self
+    }
+
+    /// Serves requests with the `x-nextjs-raw: 1` header from the compiled
+    /// sources directly, skipping rewrites and middleware, e.g. to fetch a
+    /// chunk while debugging a rewrite.
+    pub fn raw_asset_header(mut self, raw_asset_header: bool) -> NextDevServerBuilder {
+        self.raw_asset_header = raw_asset_header;
+        self
+    }
+
+    /// Whether to serve `_devPagesManifest.json`, `_buildManifest.js` and
+    /// `_devMiddlewareManifest.json`. The router doesn't depend on them, but
+    /// client-side navigation between pages does, so they can only be disabled
+    /// when embedding the server without pages, e.g. for a single web entry.
+    /// Enabled by default.
+    pub fn enable_manifest_source(mut self, enable_manifest_source: bool) -> NextDevServerBuilder {
+        self.enable_manifest_source = enable_manifest_source;
+        self
+    }
+
+    /// Whether the client chunks support hot module replacement. Enabled by
+    /// default, disabling it e.g. measures its overhead.
+    pub fn enable_hmr(mut self, enable_hmr: bool) -> NextDevServerBuilder {
+        self.enable_hmr = Some(enable_hmr);
+        self
+    }
+
+    /// Serves the trace file written with `NEXT_TURBOPACK_TRACING` at the
+    /// internal `nextjs_trace` route, for downloading it from a remote dev
+    /// server. Like the introspection routes, the trace reveals the paths
+    /// and timings of the project, so it is only served when opted into.
+    pub fn serve_trace(mut self, trace_file: TraceFile) -> NextDevServerBuilder {
+        self.trace_file = Some(trace_file);
+        self
+    }
+
+    /// Compiles `entry`, relative to the project directory, as a service
+    /// worker, and serves it at `pathname`, e.g. `/sw.js`.
+    pub fn service_worker(mut self, entry: String, pathname: String) -> NextDevServerBuilder {
+        self.service_worker = Some((entry, pathname));
+        self
+    }
+
+    /// Serves the project in `project_dir` under `/{prefix}`, next to the main
+    /// project, with the same turbo-tasks backend, e.g. to demo several apps
+    /// of a monorepo at once. The project must be inside the root directory,
+    /// and the prefix must be a single URL-safe path segment. The internal
+    /// routes, e.g. the introspection UI, only exist once for all projects.
+    pub fn mount_project(mut self, prefix: String, project_dir: String) -> NextDevServerBuilder {
+        self.mounted_projects.push((prefix, project_dir));
+        self
+    }
+
+    /// Sets the label and favicon of the page shown while a page is compiling
+    /// or failed to compile, instead of the default Next.js look.
+    pub fn fallback_branding(mut self, branding: FallbackBranding) -> NextDevServerBuilder {
+        self.fallback_branding = branding;
+        self
+    }
+
     pub fn issue_reporter(
         mut self,
         issue_reporter: Box<dyn IssueReporterProvider>,
@@ -178,15 +505,44 @@ impl NextDevServerBuilder {
         self
     }
 
+    /// Passes each issue to `issue_listener` when it is first reported, and
+    /// again once it is no longer reported, in addition to the issue reporter.
+    pub fn issue_listener(
+        mut self,
+        issue_listener: Arc<dyn IssueListener>,
+    ) -> NextDevServerBuilder {
+        self.issue_listener = Some(issue_listener);
+        self
+    }
+
+    /// Records the errors and warnings of each report in `issue_counter`, e.g.
+    /// to print a summary after each compilation.
+    pub fn issue_counter(mut self, issue_counter: Arc<IssueCounter>) -> NextDevServerBuilder {
+        self.issue_counter = Some(issue_counter);
+        self
+    }
+
     /// Attempts to find an open port to bind.
-    fn find_port(&self, host: IpAddr, port: u16, max_attempts: u16) -> Result<DevServerBuilder> {
+    /// Listens on all hostnames at the first port which is free on all of
+    /// them.
+    fn find_port(
+        &self,
+        hosts: &[IpAddr],
+        port: u16,
+        max_attempts: u16,
+    ) -> Result<Vec<DevServerBuilder>> {
         // max_attempts of 1 means we loop 0 times.
         let max_attempts = max_attempts - 1;
         let mut attempts = 0;
+        let hosts = listen_hosts(hosts);
         loop {
             let current_port = port + attempts;
-            let addr = SocketAddr::new(host, current_port);
-            let listen_result = DevServer::listen(addr);
+            // The listeners bound before a failure are dropped, which frees
+            // their port for the next attempt.
+            let listen_result = hosts
+                .iter()
+                .map(|&host| DevServer::listen(SocketAddr::new(host, current_port)))
+                .collect::<Result<Vec<_>>>();
 
             if let Err(e) = &listen_result {
                 if self.allow_retry && attempts < max_attempts {
@@ -220,9 +576,44 @@ impl NextDevServerBuilder {
 
     pub async fn build(self) -> Result<DevServer> {
         let port = self.port.context("port must be set")?;
-        let host = self.hostname.context("hostname must be set")?;
+        if self.hostnames.is_empty() {
+            bail!("hostname must be set");
+        }
+
+        validate_route_prefix(&self.route_prefix)?;
+        let mut internal_routes = InternalRoutes::new(&self.route_prefix);
+        if let Some(route) = self.introspection_route {
+            validate_route("introspection", &route)?;
+            internal_routes.introspect = route;
+        }
+        if let Some(route) = self.turbo_tasks_route {
+            validate_route("turbo-tasks", &route)?;
+            internal_routes.turbo_tasks = route;
+        }
+
+        let mut mounted_projects = Vec::with_capacity(self.mounted_projects.len());
+        for (prefix, project_dir) in self.mounted_projects {
+            validate_route("mounted project", &prefix)?;
+            if internal_routes.is_internal(&prefix) || prefix == "_next" {
+                bail!("the mounted project prefix {prefix:?} is reserved by the dev server");
+            }
+            if mounted_projects
+                .iter()
+                .any(|(mounted, _): &(String, String)| *mounted == prefix)
+            {
+                bail!("more than one project is mounted at {prefix:?}");
+            }
+            if !Path::new(&project_dir).starts_with(&self.root_dir) {
+                bail!(
+                    "the mounted project {project_dir} is outside of the root directory {}",
+                    self.root_dir
+                );
+            }
+            mounted_projects.push((prefix, project_dir));
+        }
 
-        let server = self.find_port(host, port, 10)?;
+        let listeners = self.find_port(&self.hostnames, port, 10)?;
+        let addr = listeners[0].addr;
 
         let turbo_tasks = self.turbo_tasks;
         let project_dir = self.project_dir;
@@ -230,7 +621,11 @@ impl NextDevServerBuilder {
         let eager_compile = self.eager_compile;
         let show_all = self.show_all;
         let log_detail = self.log_detail;
+        let invalidation_reasons = self.invalidation_reasons;
+        let error_on = Arc::new(self.error_on);
         let browserslist_query = self.browserslist_query;
+        let project_fs = self.project_fs;
+        let output_fs = self.output_fs;
         let log_options = Arc::new(LogOptions {
             current_dir: current_dir().unwrap(),
             project_dir: PathBuf::from(project_dir.clone()),
@@ -238,42 +633,289 @@ impl NextDevServerBuilder {
             log_detail,
             log_level: self.log_level,
         });
-        let entry_requests = Arc::new(self.entry_requests);
-        let server_addr = Arc::new(server.addr);
         let tasks = turbo_tasks.clone();
         let issue_provider = self.issue_reporter.unwrap_or_else(|| {
             // Initialize a ConsoleUi reporter if no custom reporter was provided
-            Box::new(move || Vc::upcast(ConsoleUi::new(log_options.clone().into())))
+            Box::new(move || {
+                Vc::upcast(ErrorOnIssueReporter::new(
+                    Vc::upcast(ConsoleUi::new(log_options.clone().into())),
+                    Vc::cell((*error_on).clone()),
+                ))
+            })
         });
+        let issue_provider: Box<dyn IssueReporterProvider> = match self.issue_listener {
+            Some(issue_listener) => {
+                // A new reporter is created for every request, so the issues
+                // which were passed on are shared by all of them.
+                let listened =
+                    TransientInstance::new(Arc::new(ListenedIssues::new(issue_listener)));
+                let issue_provider: Arc<dyn IssueReporterProvider> = Arc::from(issue_provider);
+                Box::new(move || {
+                    Vc::upcast(ListeningIssueReporter::new(
+                        issue_provider.get_issue_reporter(),
+                        listened.clone(),
+                    ))
+                })
+            }
+            None => issue_provider,
+        };
+        let issue_provider: Box<dyn IssueReporterProvider> = match self.issue_counter {
+            Some(issue_counter) => {
+                let issue_counter = TransientInstance::new(issue_counter);
+                let issue_provider: Arc<dyn IssueReporterProvider> = Arc::from(issue_provider);
+                Box::new(move || {
+                    Vc::upcast(CountingIssueReporter::new(
+                        issue_provider.get_issue_reporter(),
+                        issue_counter.clone(),
+                    ))
+                })
+            }
+            None => issue_provider,
+        };
 
+        let mut native_watching = self.watch_poll.is_none();
+        let mut watch_poll = self.watch_poll;
+        let watch_retries = self.watch_retries;
+        let mut watch_failure = None;
+        if native_watching && project_fs.is_none() {
+            let root_dir = root_dir.clone();
+            // `project_fs` is a turbo-tasks function, so the sources reuse the
+            // file system which is already watched.
+            let started = tasks
+                .run_once(async move {
+                    project_fs(root_dir, true, invalidation_reasons, watch_retries)
+                        .resolve()
+                        .await?;
+                    Ok(())
+                })
+                .await;
+            if let WatchMode::Poll { interval, reason } =
+                watch_mode(started, self.watch_fallback_poll)?
+            {
+                println!(
+                    "{} - failed to watch the project directory, detecting changes by scanning it \
+                     every {}: {reason}",
+                    "warn ".yellow(),
+                    FormatDuration(interval),
+                );
+                native_watching = false;
+                watch_poll = Some(interval);
+                watch_failure = Some(reason);
+            }
+        }
+        if native_watching && project_fs.is_none() && polling_would_help(Path::new(&root_dir)) {
+            println!(
+                "{} - the project is on a volume shared with the container host, if file changes \
+                 are not picked up, try --watch-poll",
+                "warn ".yellow(),
+            );
+        }
+
+        let idle_monitor = self.idle_monitor;
+        if let Some(idle_monitor) = &idle_monitor {
+            let mut file_systems = vec![];
+            if native_watching && project_fs.is_none() {
+                file_systems.push(WatchedFileSystem {
+                    name: PROJECT_FILESYSTEM_NAME.to_string(),
+                    root: root_dir.clone(),
+                    with_invalidation_reason: invalidation_reasons,
+                });
+            }
+            if native_watching && output_fs.is_none() {
+                file_systems.push(WatchedFileSystem {
+                    name: "output".to_string(),
+                    root: project_dir.clone(),
+                    with_invalidation_reason: false,
+                });
+            }
+            idle_monitor.start(tasks.clone(), file_systems).await;
+        }
+
+        if let Some(interval) = watch_poll {
+            if project_fs.is_none() {
+                // The output file system is only written by the dev server, so
+                // it is not polled. The output directories are usually inside
+                // the project directory, so they are skipped when scanning it.
+                let project_dirs = std::iter::once(project_dir.clone()).chain(
+                    mounted_projects
+                        .iter()
+                        .map(|(_, project_dir)| project_dir.clone()),
+                );
+                let output_dirs = output_dirs(&tasks, &root_dir, project_dirs).await?;
+                PollWatcher::new(
+                    interval,
+                    tasks.clone(),
+                    vec![WatchedFileSystem {
+                        name: PROJECT_FILESYSTEM_NAME.to_string(),
+                        root: root_dir.clone(),
+                        with_invalidation_reason: invalidation_reasons,
+                    }],
+                    output_dirs,
+                    idle_monitor.clone(),
+                )
+                .start();
+            }
+        }
+
+        let options = Arc::new(DevSourcesOptions {
+            root_dir,
+            project_dir,
+            entry_requests: self.entry_requests,
+            eager_compile,
+            turbo_tasks,
+            browserslist_query,
+            server_addr: addr,
+            project_fs_override: project_fs,
+            output_fs_override: output_fs,
+            native_watching,
+            invalidation_reasons,
+            loader_rules: self.loader_rules,
+            source_root_remap: self.source_root_remap,
+            internal_routes,
+            internal_route_headers: self.internal_route_headers,
+            service_worker: self.service_worker,
+            fallback_branding: self.fallback_branding,
+            raw_asset_header: self.raw_asset_header,
+            enable_manifest_source: self.enable_manifest_source,
+            watch_retries,
+            watch_failure,
+            mount_prefix: None,
+            trace_file: self.trace_file,
+            enable_hmr: self.enable_hmr,
+        });
+        let mounted_projects = Arc::new(
+            mounted_projects
+                .into_iter()
+                .map(|(prefix, project_dir)| MountedProject {
+                    options: Arc::new(DevSourcesOptions {
+                        project_dir,
+                        entry_requests: vec![],
+                        output_fs_override: None,
+                        service_worker: None,
+                        watch_failure: None,
+                        mount_prefix: Some(prefix.clone()),
+                        trace_file: None,
+                        ..(*options).clone()
+                    }),
+                    prefix,
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        if log_detail {
+            let start = self.start;
+            let tasks = tasks.clone();
+            let options = options.clone();
+            tokio::spawn(async move {
+                let summary = tasks
+                    .run_once(async move {
+                        let summary = route_summary(options.into()).strongly_consistent().await?;
+                        Ok(summary)
+                    })
+                    .await;
+                match summary {
+                    Ok(summary) => println!(
+                        "{event_type} - found {pages_routes} pages routes, {app_routes} app \
+                         routes, app dir: {app_dir}, middleware: {middleware} in {elapsed}",
+                        event_type = "event".purple(),
+                        pages_routes = summary.pages_routes,
+                        app_routes = summary.app_routes,
+                        app_dir = summary.app_dir.as_deref().unwrap_or("no"),
+                        middleware = if summary.middleware { "yes" } else { "no" },
+                        elapsed = FormatDuration(start.elapsed()),
+                    ),
+                    Err(err) => eprintln!("failed to summarize routes: {err:?}"),
+                }
+            });
+        }
+
+        let project_path = {
+            let options = options.clone();
+            move || dev_project_path(options.clone().into())
+        };
         let source = move || {
-            source(
-                root_dir.clone(),
-                project_dir.clone(),
-                entry_requests.clone().into(),
-                eager_compile,
-                turbo_tasks.clone().into(),
-                browserslist_query.clone(),
-                server_addr.clone().into(),
-            )
+            if let Some(idle_monitor) = &idle_monitor {
+                idle_monitor.touch();
+            }
+            source(options.clone().into(), mounted_projects.clone().into())
         };
 
-        let issue_reporter_arc = Arc::new(move || issue_provider.get_issue_reporter());
-        Ok(server.serve(tasks, source, issue_reporter_arc))
+        let issue_reporter_arc: Arc<dyn Fn() -> Vc<Box<dyn IssueReporter>> + Send + Sync> =
+            Arc::new(move || issue_provider.get_issue_reporter());
+
+        if !self.warm_routes.is_empty() {
+            tokio::spawn(warmup::warm_routes(
+                tasks.clone(),
+                source.clone(),
+                project_path,
+                issue_reporter_arc.clone(),
+                self.warm_routes,
+            ));
+        }
+        let servers = listeners
+            .into_iter()
+            .map(|listener| {
+                listener.serve(tasks.clone(), source.clone(), issue_reporter_arc.clone())
+            })
+            .collect::<Vec<_>>();
+        // All listeners serve the same source, so the server is reported at
+        // the first address.
+        Ok(DevServer {
+            addr,
+            future: Box::pin(async move {
+                try_join_all(servers.into_iter().map(|server| server.future)).await?;
+                Ok(())
+            }),
+        })
     }
 }
 
+/// Returns the addresses which need a listener of their own. Turbopack binds
+/// the unspecified IPv6 address as a dual-stack socket, which accepts
+/// connections on every IPv4 and IPv6 address, so binding any other address
+/// at the same port would fail with `AddrInUse`. `-H :: -H 0.0.0.0` is then
+/// served by the `::` listener alone.
+fn listen_hosts(hosts: &[IpAddr]) -> Vec<IpAddr> {
+    let unspecified_v6 = IpAddr::V6(Ipv6Addr::UNSPECIFIED);
+    if hosts.contains(&unspecified_v6) {
+        vec![unspecified_v6]
+    } else {
+        hosts.to_vec()
+    }
+}
+
+/// When `native_watching` is false, changes are detected by a
+/// [PollWatcher] instead. Setting up native file events is retried
+/// `watch_retries` times. Recording the reasons of invalidations costs memory
+/// on very large projects, so it can be turned off.
 #[turbo_tasks::function]
-async fn project_fs(project_dir: String) -> Result<Vc<Box<dyn FileSystem>>> {
+async fn project_fs(
+    project_dir: String,
+    native_watching: bool,
+    invalidation_reasons: bool,
+    watch_retries: u32,
+) -> Result<Vc<Box<dyn FileSystem>>> {
     let disk_fs = DiskFileSystem::new(PROJECT_FILESYSTEM_NAME.to_string(), project_dir.to_string());
-    disk_fs.await?.start_watching_with_invalidation_reason()?;
+    if native_watching {
+        let disk_fs = disk_fs.await?;
+        retry_with_backoff(watch_retries, WATCH_RETRY_BACKOFF, || {
+            if invalidation_reasons {
+                disk_fs.start_watching_with_invalidation_reason()
+            } else {
+                disk_fs.start_watching()
+            }
+        })
+        .await?;
+    }
     Ok(Vc::upcast(disk_fs))
 }
 
 #[turbo_tasks::function]
-async fn output_fs(project_dir: String) -> Result<Vc<Box<dyn FileSystem>>> {
+async fn output_fs(project_dir: String, native_watching: bool) -> Result<Vc<Box<dyn FileSystem>>> {
     let disk_fs = DiskFileSystem::new("output".to_string(), project_dir.to_string());
-    disk_fs.await?.start_watching()?;
+    if native_watching {
+        disk_fs.await?.start_watching()?;
+    }
     Ok(Vc::upcast(disk_fs))
 }
 
@@ -293,19 +935,126 @@ async fn server_env(
     Ok(Vc::upcast(CustomProcessEnv::new(env, Vc::cell(map))))
 }
 
+#[turbo_tasks::value]
+struct DevSources {
+    source: Vc<Box<dyn ContentSource>>,
+    /// The app without the internal routes, for mounting it under a prefix.
+    app_source: Vc<Box<dyn ContentSource>>,
+    route_summary: Vc<RouteSummary>,
+    project_path: Vc<FileSystemPath>,
+}
+
+/// The routes discovered in the project.
+#[turbo_tasks::value]
+struct RouteSummary {
+    pages_routes: usize,
+    app_routes: usize,
+    middleware: bool,
+    /// The app directory relative to the project directory, if one was found.
+    app_dir: Option<String>,
+}
+
 #[turbo_tasks::function]
 async fn source(
-    root_dir: String,
-    project_dir: String,
-    entry_requests: TransientInstance<Vec<EntryRequest>>,
-    eager_compile: bool,
-    turbo_tasks: TransientInstance<TurboTasks<MemoryBackend>>,
-    browserslist_query: String,
-    server_addr: TransientInstance<SocketAddr>,
+    options: TransientInstance<DevSourcesOptions>,
+    mounted_projects: TransientInstance<Vec<MountedProject>>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
-    let output_fs = output_fs(project_dir.clone());
-    let fs = project_fs(root_dir.clone());
-    let project_relative = project_dir.strip_prefix(&root_dir).unwrap_or_else(|| {
+    let mut routes = Vec::with_capacity(mounted_projects.len());
+    for project in mounted_projects.iter() {
+        // Only the app of a mounted project is served, the internal routes of
+        // the main project are shared.
+        let app_source = dev_sources(project.options.clone().into())
+            .await?
+            .app_source;
+        routes.push((project.prefix.clone(), app_source));
+    }
+
+    let source = dev_sources(options).await?.source;
+    if routes.is_empty() {
+        return Ok(source);
+    }
+    Ok(Vc::upcast(
+        PrefixedRouterContentSource {
+            prefix: Default::default(),
+            routes,
+            fallback: source,
+        }
+        .cell(),
+    ))
+}
+
+/// Takes the same options as [source], so the sources are only created once.
+#[turbo_tasks::function]
+async fn route_summary(options: TransientInstance<DevSourcesOptions>) -> Result<Vc<RouteSummary>> {
+    Ok(dev_sources(options).await?.route_summary)
+}
+
+/// The directory of the project, see [route_summary].
+#[turbo_tasks::function]
+async fn dev_project_path(
+    options: TransientInstance<DevSourcesOptions>,
+) -> Result<Vc<FileSystemPath>> {
+    Ok(dev_sources(options).await?.project_path)
+}
+
+#[turbo_tasks::function]
+async fn compute_route_summary(
+    project_path: Vc<FileSystemPath>,
+    pages_structure: Vc<PagesStructure>,
+    app_dir: Vc<OptionAppDir>,
+    page_extensions: Vc<Vec<String>>,
+) -> Result<Vc<RouteSummary>> {
+    async fn count_pages(directory: Vc<PagesDirectoryStructure>) -> Result<usize> {
+        let directory = directory.await?;
+        let mut count = directory.items.len();
+        for child in directory.children.iter() {
+            count += Box::pin(count_pages(*child)).await?;
+        }
+        Ok(count)
+    }
+
+    let pages_structure = pages_structure.await?;
+    let mut pages_routes = 0;
+    for directory in [pages_structure.pages, pages_structure.api]
+        .into_iter()
+        .flatten()
+    {
+        pages_routes += count_pages(directory).await?;
+    }
+
+    let (app_routes, app_dir) = match *app_dir.await? {
+        Some(app_dir) => (
+            get_entrypoints(app_dir, page_extensions).await?.len(),
+            project_path
+                .await?
+                .get_path_to(&*app_dir.await?)
+                .map(str::to_string),
+        ),
+        None => (0, None),
+    };
+
+    let middleware = matches!(
+        *find_context_file(project_path, middleware_files(page_extensions)).await?,
+        FindContextFileResult::Found(..)
+    );
+
+    Ok(RouteSummary {
+        pages_routes,
+        app_routes,
+        middleware,
+        app_dir,
+    }
+    .cell())
+}
+
+/// The path of the project directory in the file system of the root
+/// directory.
+fn project_path(
+    fs: Vc<Box<dyn FileSystem>>,
+    root_dir: &str,
+    project_dir: &str,
+) -> Vc<FileSystemPath> {
+    let project_relative = project_dir.strip_prefix(root_dir).unwrap_or_else(|| {
         panic!(
             "project directory '{project_dir}' exists outside of the root directory '{root_dir}'"
         )
@@ -314,13 +1063,17 @@ async fn source(
         .strip_prefix(MAIN_SEPARATOR)
         .unwrap_or(project_relative)
         .replace(MAIN_SEPARATOR, "/");
-    let project_path = fs.root().join(project_relative);
-
-    let server_addr = ServerAddr::new(*server_addr).cell();
+    fs.root().join(project_relative)
+}
 
-    let env = load_env(project_path);
-    let env = server_env(env, server_addr);
-    let build_output_root = output_fs.root().join(".next/build".to_string());
+/// The context for evaluating code in Node.js at build time, e.g. the next
+/// config. Its output is written to the `build` directory in `dist_root`.
+fn execution_context(
+    project_path: Vc<FileSystemPath>,
+    dist_root: Vc<FileSystemPath>,
+    env: Vc<Box<dyn ProcessEnv>>,
+) -> Vc<ExecutionContext> {
+    let build_output_root = dist_root.join("build".to_string());
 
     let build_chunking_context = DevChunkingContext::builder(
         project_path,
@@ -331,15 +1084,148 @@ async fn source(
     )
     .build();
 
+    ExecutionContext::new(project_path, Vc::upcast(build_chunking_context), env)
+}
+
+/// The context for evaluating the next config. The config is evaluated before
+/// its `distDir` is known, so its output is always written to the default
+/// `distDir`.
+fn next_config_execution_context(
+    project_path: Vc<FileSystemPath>,
+    output_fs: Vc<Box<dyn FileSystem>>,
+    env: Vc<Box<dyn ProcessEnv>>,
+) -> Vc<ExecutionContext> {
+    execution_context(
+        project_path,
+        output_fs.root().join(DEFAULT_DIST_DIR.to_string()),
+        env,
+    )
+    .with_layer("next_config".to_string())
+}
+
+/// The next config and rewrites of the project, with all defaults applied,
+/// as pretty-printed JSON.
+#[turbo_tasks::function]
+async fn resolved_next_config(root_dir: String, project_dir: String) -> Result<Vc<String>> {
+    let output_fs = output_fs(project_dir.clone(), false);
+    let fs = project_fs(root_dir.clone(), false, false, 0);
+    let project_path = project_path(fs, &root_dir, &project_dir);
+    let execution_context =
+        next_config_execution_context(project_path, output_fs, load_env(project_path));
+    let config = load_next_config(execution_context).await?;
+    let rewrites = load_rewrites(execution_context).await?;
+    Ok(Vc::cell(serde_json::to_string_pretty(&json!({
+        "config": &*config,
+        "rewrites": &*rewrites,
+    }))?))
+}
+
+/// The `distDir` of the project, in which the dev server writes its output.
+#[turbo_tasks::function]
+async fn dist_root(
+    output_fs: Vc<Box<dyn FileSystem>>,
+    next_config: Vc<NextConfig>,
+) -> Result<Vc<FileSystemPath>> {
+    Ok(output_fs
+        .root()
+        .join(next_config.dist_dir().await?.clone_value()))
+}
+
+/// The `distDir` of the project, relative to the project directory.
+#[turbo_tasks::function]
+async fn project_dist_dir(root_dir: String, project_dir: String) -> Result<Vc<String>> {
+    let output_fs = output_fs(project_dir.clone(), false);
+    let fs = project_fs(root_dir.clone(), false, false, 0);
+    let project_path = project_path(fs, &root_dir, &project_dir);
     let execution_context =
-        ExecutionContext::new(project_path, Vc::upcast(build_chunking_context), env);
+        next_config_execution_context(project_path, output_fs, load_env(project_path));
+    Ok(load_next_config_or_default(execution_context).dist_dir())
+}
+
+/// The absolute `distDir`s of the given projects.
+async fn output_dirs(
+    turbo_tasks: &Arc<TurboTasks<MemoryBackend>>,
+    root_dir: &str,
+    project_dirs: impl IntoIterator<Item = String>,
+) -> Result<Vec<PathBuf>> {
+    let mut output_dirs = vec![];
+    for project_dir in project_dirs {
+        let (root_dir, dir) = (root_dir.to_string(), project_dir.clone());
+        let dist_dir = turbo_tasks
+            .run_once(async move { Ok(project_dist_dir(root_dir, dir).await?.clone_value()) })
+            .await?;
+        output_dirs.push(Path::new(&project_dir).join(dist_dir));
+    }
+    Ok(output_dirs)
+}
+
+#[turbo_tasks::function]
+async fn dev_sources(options: TransientInstance<DevSourcesOptions>) -> Result<Vc<DevSources>> {
+    let DevSourcesOptions {
+        root_dir,
+        project_dir,
+        entry_requests,
+        eager_compile,
+        turbo_tasks,
+        browserslist_query,
+        server_addr,
+        project_fs_override,
+        output_fs_override,
+        native_watching,
+        invalidation_reasons,
+        loader_rules,
+        source_root_remap,
+        internal_routes,
+        internal_route_headers,
+        service_worker,
+        fallback_branding,
+        raw_asset_header,
+        enable_manifest_source,
+        watch_retries,
+        watch_failure,
+        mount_prefix,
+        trace_file,
+        enable_hmr,
+    } = &*options;
+    let output_fs =
+        output_fs_override.unwrap_or_else(|| output_fs(project_dir.clone(), *native_watching));
+    let fs = project_fs_override.unwrap_or_else(|| {
+        project_fs(
+            root_dir.clone(),
+            *native_watching,
+            *invalidation_reasons,
+            *watch_retries,
+        )
+    });
+    let project_path = project_path(fs, root_dir, project_dir);
+    if let Some(reason) = watch_failure {
+        WatchFallbackIssue {
+            path: project_path,
+            reason: reason.clone(),
+        }
+        .cell()
+        .emit();
+    }
+
+    let server_addr = ServerAddr::new(*server_addr).cell();
+    let fallback_branding = fallback_branding.clone().cell();
+
+    let env = load_env(project_path);
+    let env = server_env(env, server_addr);
 
     let mode = NextMode::DevServer;
-    let next_config_execution_context = execution_context.with_layer("next_config".to_string());
-    let next_config = load_next_config(next_config_execution_context);
-    let rewrites = load_rewrites(next_config_execution_context);
+    let next_config_execution_context = next_config_execution_context(project_path, output_fs, env);
+    let next_config = load_next_config_or_default(next_config_execution_context)
+        .with_extra_loader_rules(Vc::cell(loader_rules.clone()));
+    let next_config = match mount_prefix {
+        Some(prefix) => next_config.with_mount_prefix(prefix.clone()),
+        None => next_config,
+    };
+    let rewrites = load_rewrites_or_default(next_config_execution_context);
 
-    let output_root = output_fs.root().join(".next/server".to_string());
+    let dist_root = dist_root(output_fs, next_config);
+    let execution_context = execution_context(project_path, dist_root, env);
+    let output_root = dist_root.join("server".to_string());
 
     let dev_server_fs = Vc::upcast::<Box<dyn FileSystem>>(ServerFileSystem::new());
     let dev_server_root = dev_server_fs.root();
@@ -360,17 +1246,26 @@ async fn source(
         execution_context,
         entry_requests,
         dev_server_root,
-        eager_compile,
+        *eager_compile,
         browserslist_query.clone(),
         next_config,
     );
-    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
+    let client_compile_time_info =
+        get_client_compile_time_info(mode, browserslist_query.clone(), project_path, next_config);
     let client_chunking_context = get_client_chunking_context(
         project_path,
         dev_server_root,
         client_compile_time_info.environment(),
         mode,
+        *enable_hmr,
+        next_config.client_source_maps(),
     );
+    // Calling a turbo-tasks function schedules its task right away without
+    // waiting for the result, so the page and app sources below are already
+    // built concurrently. Nothing in this function may await them before they
+    // are combined, or their construction would become sequential. Issues are
+    // collected from the task graph rather than in completion order, so their
+    // order stays deterministic.
     let pages_structure =
         find_pages_structure(project_path, dev_server_root, next_config.page_extensions());
     let page_source = create_page_source(
@@ -384,6 +1279,7 @@ async fn source(
         client_compile_time_info,
         next_config,
         server_addr,
+        fallback_branding,
     );
     let app_dir = find_app_dir_if_enabled(project_path);
     let app_source = create_app_source(
@@ -397,26 +1293,40 @@ async fn source(
         client_compile_time_info,
         next_config,
         server_addr,
+        fallback_branding,
     );
-    let viz = Vc::upcast(turbo_tasks_viz::TurboTasksSource::new(turbo_tasks.into()));
+    let viz = Vc::upcast(turbo_tasks_viz::TurboTasksSource::new(
+        turbo_tasks.clone().into(),
+        vec![fs, output_fs],
+    ));
     let static_source = Vc::upcast(StaticAssetsContentSource::new(
         String::new(),
         project_path.join("public".to_string()),
     ));
-    let manifest_source = Vc::upcast(
-        DevManifestContentSource {
-            page_roots: vec![page_source],
-            rewrites,
-        }
-        .cell(),
-    );
-    let main_source = CombinedContentSource::new(vec![
-        manifest_source,
-        static_source,
-        app_source,
-        page_source,
-        web_source,
-    ]);
+    let mut sources: Vec<Vc<Box<dyn ContentSource>>> = vec![];
+    if *enable_manifest_source {
+        sources.push(Vc::upcast(
+            DevManifestContentSource {
+                page_roots: vec![page_source],
+                rewrites,
+            }
+            .cell(),
+        ));
+    }
+    sources.extend([static_source, app_source, page_source]);
+    if let Some((entry, pathname)) = service_worker {
+        sources.push(create_service_worker_source(
+            project_path,
+            execution_context,
+            dev_server_root,
+            entry.clone(),
+            pathname.clone(),
+            browserslist_query.clone(),
+            next_config,
+        ));
+    }
+    sources.push(web_source);
+    let main_source = CombinedContentSource::new(sources);
     let introspect = Vc::upcast(
         IntrospectionSource {
             roots: HashSet::from([Vc::upcast(main_source)]),
@@ -425,6 +1335,21 @@ async fn source(
     );
     let main_source = Vc::upcast(main_source);
     let source_map_trace = Vc::upcast(NextSourceMapTraceContentSource::new(main_source));
+    let source_map_trace = match source_root_remap {
+        Some((from, to)) => Vc::upcast(SourceRootRemapContentSource::new(
+            source_map_trace,
+            from.clone(),
+            to.clone(),
+        )),
+        None => source_map_trace,
+    };
+    let client_source_maps = *next_config.client_source_maps().await?;
+    let source_map_trace = match client_source_maps {
+        SourceMapLevel::None => {
+            Vc::upcast(ClientSourceMapsDisabledContentSource::new(source_map_trace))
+        }
+        SourceMapLevel::Cheap | SourceMapLevel::Full => source_map_trace,
+    };
     let img_source = Vc::upcast(NextImageContentSource::new(main_source));
     let router_source = Vc::upcast(NextRouterContentSource::new(
         main_source,
@@ -434,25 +1359,82 @@ async fn source(
         app_dir,
         pages_structure,
     ));
+    let router_source = if *raw_asset_header {
+        Vc::upcast(RawAssetContentSource::new(router_source, main_source))
+    } else {
+        router_source
+    };
+    let router_source = match client_source_maps {
+        SourceMapLevel::Cheap => Vc::upcast(CheapSourceMapsContentSource::new(router_source)),
+        SourceMapLevel::None | SourceMapLevel::Full => router_source,
+    };
+    let router_source = Vc::upcast(ETagContentSource::new(router_source));
+    let base_path = next_config.base_path().await?;
+    if let Some(route) = internal_routes.base_path_collision(&base_path) {
+        bail!(
+            "the internal route /{route} would shadow the app at the basePath {base_path:?}, move \
+             it with the route prefix or the introspection and turbo-tasks route options"
+        );
+    }
+    let with_internal_headers = |source: Vc<Box<dyn ContentSource>>| {
+        if internal_route_headers.is_empty() {
+            source
+        } else {
+            Vc::upcast(ResponseHeadersContentSource::new(
+                source,
+                HeaderList::new(internal_route_headers.clone()),
+            ))
+        }
+    };
+    let mut routes = vec![
+        (
+            internal_routes.introspect.clone(),
+            with_internal_headers(introspect),
+        ),
+        (
+            internal_routes.turbo_tasks.clone(),
+            with_internal_headers(viz),
+        ),
+        (
+            internal_routes.original_stack_frame.clone(),
+            with_internal_headers(source_map_trace),
+        ),
+        (
+            internal_routes.rewrites.clone(),
+            with_internal_headers(Vc::upcast(RewritesContentSource::new(rewrites))),
+        ),
+        // TODO: Load path from next.config.js
+        ("_next/image".to_string(), img_source),
+    ];
+    if let Some(trace_file) = trace_file {
+        routes.push((
+            internal_routes.trace.clone(),
+            with_internal_headers(Vc::upcast(TraceFileSource::new(trace_file.clone()))),
+        ));
+    }
     let source = Vc::upcast(
         PrefixedRouterContentSource {
             prefix: Default::default(),
-            routes: vec![
-                ("__turbopack__".to_string(), introspect),
-                ("__turbo_tasks__".to_string(), viz),
-                (
-                    "__nextjs_original-stack-frame".to_string(),
-                    source_map_trace,
-                ),
-                // TODO: Load path from next.config.js
-                ("_next/image".to_string(), img_source),
-            ],
+            routes,
             fallback: router_source,
         }
         .cell(),
     );
 
-    Ok(source)
+    let route_summary = compute_route_summary(
+        project_path,
+        pages_structure,
+        app_dir,
+        next_config.page_extensions(),
+    );
+
+    Ok(DevSources {
+        source,
+        app_source: router_source,
+        route_summary,
+        project_path,
+    }
+    .cell())
 }
 
 pub fn register() {
@@ -467,9 +1449,35 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
     #[cfg(feature = "tokio_console")]
     console_subscriber::init();
 
+    register();
+
+    let dir = options
+        .dir
+        .as_ref()
+        .map(canonicalize)
+        .unwrap_or_else(current_dir)
+        .context("project directory can't be found")?
+        .to_str()
+        .context("project directory contains invalid characters")?
+        .to_string();
+
+    let root_dir = if let Some(root) = options.root.as_ref() {
+        canonicalize(root)
+            .context("root directory can't be found")?
+            .to_str()
+            .context("root directory contains invalid characters")?
+            .to_string()
+    } else {
+        dir.clone()
+    };
+
+    let tt = TurboTasks::new(MemoryBackend::new(
+        options.memory_limit.map_or(usize::MAX, |l| l * 1024 * 1024),
+    ));
+
     let trace = std::env::var("NEXT_TURBOPACK_TRACING").ok();
 
-    let _guard = if let Some(mut trace) = trace {
+    let (trace_guard, trace_file) = if let Some(mut trace) = trace {
         // Trace presets
         match trace.as_str() {
             "overview" => {
@@ -491,96 +1499,239 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
 
         let subscriber = subscriber.with(EnvFilter::builder().parse(trace).unwrap());
 
-        let internal_dir = options
-            .dir
-            .as_deref()
-            .unwrap_or_else(|| Path::new("."))
-            .join(".next");
+        // The trace file is written to the `distDir`, so the next config is
+        // loaded before the subscriber is set up and isn't traced itself.
+        let (root_dir, project_dir) = (root_dir.clone(), dir.clone());
+        let dist_dir = tt
+            .run_once(
+                async move { Ok(project_dist_dir(root_dir, project_dir).await?.clone_value()) },
+            )
+            .await?;
+        let internal_dir = internal_dir(Path::new(&dir), Some(&dist_dir));
         std::fs::create_dir_all(&internal_dir)
-            .context("Unable to create .next directory")
+            .context("Unable to create the distDir")
             .unwrap();
-        let trace_file = internal_dir.join("trace.log");
-        let trace_writer = std::fs::File::create(trace_file).unwrap();
-        let (trace_writer, guard) = TraceWriter::new(trace_writer);
+        let trace_file = TraceFile::create(internal_dir.join("trace.log")).unwrap();
+        let (trace_writer, guard) = TraceWriter::new(trace_file.clone());
         let subscriber = subscriber.with(RawTraceLayer::new(trace_writer));
 
         let guard = ExitGuard::new(guard).unwrap();
 
         subscriber.init();
 
-        Some(guard)
-    } else {
-        None
-    };
-
-    register();
-
-    let dir = options
-        .dir
-        .as_ref()
-        .map(canonicalize)
-        .unwrap_or_else(current_dir)
-        .context("project directory can't be found")?
-        .to_str()
-        .context("project directory contains invalid characters")?
-        .to_string();
-
-    let root_dir = if let Some(root) = options.root.as_ref() {
-        canonicalize(root)
-            .context("root directory can't be found")?
-            .to_str()
-            .context("root directory contains invalid characters")?
-            .to_string()
+        (Some(guard), Some(trace_file))
     } else {
-        dir.clone()
+        (None, None)
     };
 
-    let tt = TurboTasks::new(MemoryBackend::new(
-        options.memory_limit.map_or(usize::MAX, |l| l * 1024 * 1024),
-    ));
-
     let stats_type = match options.full_stats {
         true => StatsType::Full,
         false => StatsType::Essential,
     };
     tt.set_stats_type(stats_type);
 
+    if options.print_config {
+        let config = tt
+            .run_once(async move { Ok(resolved_next_config(root_dir, dir).await?.clone_value()) })
+            .await?;
+        println!("{config}");
+        return Ok(());
+    }
+
     let tt_clone = tt.clone();
 
     #[allow(unused_mut)]
     let mut server = NextDevServerBuilder::new(tt, dir, root_dir)
         .entry_request(EntryRequest::Relative("src/index".into()))
         .eager_compile(options.eager_compile)
-        .hostname(options.hostname)
         .port(options.port)
         .log_detail(options.log_detail)
+        // The rebuild explanation is based on the reasons.
+        .invalidation_reasons(!options.no_invalidation_reasons || options.explain_rebuilds)
         .show_all(options.show_all)
+        .error_on(options.error_on.clone())
         .log_level(
             options
                 .log_level
                 .map_or_else(|| IssueSeverity::Warning, |l| l.0),
         );
 
+    for &hostname in &options.hostname {
+        server = server.hostname(hostname);
+    }
+
+    for rule in &options.loader_rules {
+        let (glob, loader) = rule
+            .split_once('=')
+            .with_context(|| format!("invalid loader rule {rule}, expected GLOB=LOADER"))?;
+        server = server.loader_rule(glob.to_string(), loader.to_string());
+    }
+
+    if let Some(remap) = &options.source_root_remap {
+        let (from, to) = remap
+            .split_once('=')
+            .with_context(|| format!("invalid source root remap {remap}, expected FROM=TO"))?;
+        server = server.source_root_remap(from.to_string(), to.to_string());
+    }
+
+    match &trace_file {
+        Some(trace_file) if options.serve_trace => server = server.serve_trace(trace_file.clone()),
+        None if options.serve_trace => println!(
+            "{} - --serve-trace has no effect without NEXT_TURBOPACK_TRACING",
+            "warn ".yellow(),
+        ),
+        _ => {}
+    }
+
+    if let Some(route) = &options.introspection_route {
+        server = server.introspection_route(route.clone());
+    }
+
+    if let Some(route) = &options.turbo_tasks_route {
+        server = server.turbo_tasks_route(route.clone());
+    }
+
+    for header in &options.internal_route_headers {
+        let (name, value) = parse_header(header)?;
+        server = server.internal_route_header(name, value);
+    }
+
+    if let Some(service_worker) = &options.service_worker {
+        let (entry, pathname) = service_worker
+            .split_once('=')
+            .unwrap_or((service_worker, "/sw.js"));
+        server = server.service_worker(entry.to_string(), pathname.to_string());
+    }
+
+    for mount in &options.mounted_projects {
+        let (prefix, project_dir) = mount
+            .split_once('=')
+            .with_context(|| format!("invalid mount {mount}, expected PREFIX=DIR"))?;
+        let project_dir = canonicalize(project_dir)
+            .with_context(|| format!("mounted project directory {project_dir} can't be found"))?
+            .to_str()
+            .context("mounted project directory contains invalid characters")?
+            .to_string();
+        server = server.mount_project(prefix.to_string(), project_dir);
+    }
+
+    server = server.raw_asset_header(options.raw_asset_header);
+
+    server = server.enable_manifest_source(!options.no_manifest_source);
+    if options.no_hmr {
+        server = server.enable_hmr(false);
+    }
+
+    server = server.fallback_branding(FallbackBranding {
+        label: options.fallback_label.clone(),
+        favicon: options.fallback_favicon.clone(),
+    });
+
+    for pathname in &options.warm_routes {
+        server = server.warm_route(pathname.clone());
+    }
+
+    if let Some(idle_timeout) = options.idle_timeout {
+        server = server.idle_timeout(Duration::from_secs(idle_timeout));
+    }
+
+    if let Some(watch_poll) = options.watch_poll {
+        server = server.watch_poll(Duration::from_millis(watch_poll));
+    }
+
+    if let Some(watch_retries) = options.watch_retries {
+        server = server.watch_retries(watch_retries);
+    }
+
+    if options.no_watch_fallback {
+        server = server.watch_fallback_poll(None);
+    } else if let Some(watch_fallback_poll) = options.watch_fallback_poll {
+        server = server.watch_fallback_poll(Some(Duration::from_millis(watch_fallback_poll)));
+    }
+
     #[cfg(feature = "serializable")]
     {
         server = server.allow_retry(options.allow_retry);
     }
 
+    let mut ready_signals = vec![];
+    if let Some(ready_signal) = &options.ready_signal {
+        ready_signals.push(ready_signal.parse::<ReadySignal>()?);
+    }
+    if let Some(fd) = options.ready_fd {
+        ready_signals.push(ReadySignal::Fd(fd));
+    }
+    if let Some(path) = &options.ready_file {
+        ready_signals.push(ReadySignal::File(path.clone()));
+    }
+    #[cfg(not(unix))]
+    if ready_signals
+        .iter()
+        .any(|signal| matches!(signal, ReadySignal::Fd(_)))
+    {
+        bail!("fd ready signals are only supported on unix, use a file instead");
+    }
+    // An unusable fd fails at startup rather than once the server is ready.
+    #[cfg(unix)]
+    for signal in &ready_signals {
+        if let ReadySignal::Fd(fd) = signal {
+            ready_signal::check_ready_fd(*fd)?;
+        }
+    }
+
+    let issue_counter = Arc::new(IssueCounter::default());
+    server = server.issue_counter(issue_counter.clone());
+
+    let idle_monitor = server.idle_monitor();
     let server = server.build().await?;
 
+    let index_uri = ServerAddr::new(server.addr).to_string()?;
     {
-        let index_uri = ServerAddr::new(server.addr).to_string()?;
-        println!(
-            "{} - started server on {}, url: {}",
-            "ready".green(),
-            server.addr,
-            index_uri
-        );
-        if !options.no_open {
-            let _ = webbrowser::open(&index_uri);
+        let mut uris = HashSet::new();
+        let mut network_uris = vec![];
+        for &hostname in &options.hostname {
+            let addr = SocketAddr::new(hostname, server.addr.port());
+            let uri = ServerAddr::new(addr).to_string()?;
+            // Unspecified addresses are all printed as localhost.
+            if uris.insert(uri.clone()) {
+                println!(
+                    "{} - started server on {}, url: {}",
+                    "ready".green(),
+                    addr,
+                    uri
+                );
+            }
+            network_uris.extend(qr_code::network_urls(addr));
+        }
+        for uri in network_uris {
+            if uris.insert(uri.clone()) {
+                println!("{} - network: {}", "ready".green(), uri);
+            }
+        }
+        if options.qr_code {
+            qr_code::print_lan_qr_code(server.addr);
+        } else if !options.no_open {
+            open_browser(&index_uri, options.browser.as_deref());
         }
     }
 
+    let full_stats_toggle = toggle_full_stats_on_signal(tt_clone.clone(), options.full_stats);
+    let (ready_sender, ready_receiver) = tokio::sync::watch::channel(false);
+    let ready_timeout = ready_timeout(
+        tt_clone.clone(),
+        options.ready_timeout,
+        ready_receiver.clone(),
+    );
+    let ready_signal = signal_ready(ready_signals, index_uri, ready_receiver);
+    let task_limit = {
+        let tt = tt_clone.clone();
+        async move {
+            match options.max_tasks {
+                Some(max_tasks) => task_limit::task_limit_exceeded(&tt, max_tasks).await,
+                None => std::future::pending().await,
+            }
+        }
+    };
     let stats_future = async move {
         if options.log_detail {
             println!(
@@ -591,7 +1742,15 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
         }
 
         let mut progress_counter = 0;
+        // The first update counts the tasks of the initial compilation as new.
+        let mut created_tasks = CreatedTaskCounter::default();
+        let mut ready_sender = Some(ready_sender);
+        let mut had_issues = false;
         loop {
+            // Nothing is compiled while the server is idle.
+            if let Some(idle_monitor) = &idle_monitor {
+                idle_monitor.wait_until_active().await;
+            }
             let update_future = profile_timeout(
                 tt_clone.as_ref(),
                 tt_clone.aggregated_update_info(Duration::from_millis(100), Duration::MAX),
@@ -605,21 +1764,35 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
             }) = update_future.await
             {
                 progress_counter = 0;
+                if let Some(ready_sender) = ready_sender.take() {
+                    ready_sender.send_replace(true);
+                }
+                let breakdown = if options.log_detail {
+                    UpdateTaskBreakdown::new(count, created_tasks.created_since_last(&tt_clone))
+                } else {
+                    Default::default()
+                };
                 match (options.log_detail, !reasons.is_empty()) {
                     (true, true) => {
                         println!(
-                            "\x1b[2K{event_type} - {reasons} {elapsed} ({tasks} tasks)",
+                            "\x1b[2K{event_type} - {reasons} {elapsed} ({tasks} tasks, {new} new, \
+                             {invalidated} invalidated)",
                             event_type = "event".purple(),
                             elapsed = FormatDuration(elapsed),
                             tasks = count,
+                            new = breakdown.new_tasks,
+                            invalidated = breakdown.invalidated_tasks,
                         );
                     }
                     (true, false) => {
                         println!(
-                            "\x1b[2K{event_type} - compilation {elapsed} ({tasks} tasks)",
+                            "\x1b[2K{event_type} - compilation {elapsed} ({tasks} tasks, {new} \
+                             new, {invalidated} invalidated)",
                             event_type = "event".purple(),
                             elapsed = FormatDuration(elapsed),
                             tasks = count,
+                            new = breakdown.new_tasks,
+                            invalidated = breakdown.invalidated_tasks,
                         );
                     }
                     (false, true) => {
@@ -639,6 +1812,26 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
                         }
                     }
                 }
+                // A summary without issues is only printed when it tells that
+                // the issues of the previous compilation were fixed.
+                let issue_counts = issue_counter.take();
+                if !issue_counts.is_empty() || had_issues {
+                    println!(
+                        "{event_type} - {issue_counts}",
+                        event_type = "event".purple(),
+                    );
+                }
+                had_issues = !issue_counts.is_empty();
+                if options.explain_rebuilds {
+                    let changed_files = UpdateReasons::new(&reasons).changed_files;
+                    if !changed_files.is_empty() {
+                        println!(
+                            "{event_type} - {count} tasks recomputed after changes to {files}",
+                            event_type = "event".purple(),
+                            files = changed_files.join(", "),
+                        );
+                    }
+                }
             } else {
                 progress_counter += 1;
                 print!(
@@ -650,9 +1843,152 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
         }
     };
 
-    join!(stats_future, async { server.future.await.unwrap() }).await;
+    let err = tokio::select! {
+        _ = join!(
+            stats_future,
+            full_stats_toggle,
+            ready_signal,
+            async { server.future.await.unwrap() }
+        ) => return Ok(()),
+        err = ready_timeout => err,
+        err = task_limit => err,
+    };
+    eprintln!("{} - {err}", "error".red());
+    // Exiting skips destructors, so the trace is written out first.
+    drop(trace_guard);
+    if let Some(mut trace_file) = trace_file {
+        let _ = trace_file.flush();
+    }
+    std::process::exit(1);
+}
+
+/// How to open the browser, following the `BROWSER` environment variable
+/// convention of other dev tools.
+#[derive(Debug, PartialEq, Eq)]
+enum Browser {
+    /// `BROWSER=none` disables opening the browser.
+    None,
+    /// The system's default browser.
+    Default,
+    /// An executable which is passed the URL.
+    Command(String),
+}
+
+impl Browser {
+    fn from_env(browser: Option<&str>) -> Browser {
+        match browser.map(str::trim) {
+            None | Some("") => Browser::Default,
+            Some(browser) if browser.eq_ignore_ascii_case("none") => Browser::None,
+            Some(browser) => Browser::Command(browser.to_string()),
+        }
+    }
+}
+
+/// Maps the `--browser` option to a browser known to [webbrowser].
+fn named_browser(name: &str) -> Option<webbrowser::Browser> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "chrome" => webbrowser::Browser::Chrome,
+        "firefox" => webbrowser::Browser::Firefox,
+        "safari" => webbrowser::Browser::Safari,
+        "opera" => webbrowser::Browser::Opera,
+        "ie" | "internet-explorer" => webbrowser::Browser::InternetExplorer,
+        "webpositive" => webbrowser::Browser::WebPositive,
+        "default" => webbrowser::Browser::Default,
+        _ => return None,
+    })
+}
 
-    Ok(())
+/// Opens `url` in the browser named by the `--browser` option, or otherwise
+/// the one selected by the `BROWSER` environment variable. A browser which
+/// can't be opened falls back to the default one.
+fn open_browser(url: &str, browser: Option<&str>) {
+    if let Some(name) = browser {
+        match named_browser(name) {
+            Some(browser) => {
+                if webbrowser::open_browser(browser, url).is_ok() {
+                    return;
+                }
+                println!(
+                    "{} - {name} could not be opened, using the default browser",
+                    "warn ".yellow(),
+                );
+            }
+            None => println!(
+                "{} - unknown browser {name}, expected one of chrome, firefox, safari, opera, ie \
+                 or webpositive; using the default browser",
+                "warn ".yellow(),
+            ),
+        }
+        let _ = webbrowser::open(url);
+        return;
+    }
+    match Browser::from_env(std::env::var("BROWSER").ok().as_deref()) {
+        Browser::None => {}
+        Browser::Default => {
+            let _ = webbrowser::open(url);
+        }
+        Browser::Command(browser) => {
+            if let Err(err) = std::process::Command::new(&browser).arg(url).spawn() {
+                eprintln!("failed to open {url} with {browser}: {err}");
+            }
+        }
+    }
+}
+
+/// Toggles between essential and full task stats on SIGUSR2, so full stats can
+/// be captured for a slow rebuild without restarting the server.
+#[cfg(unix)]
+async fn toggle_full_stats_on_signal(tt: Arc<TurboTasks<MemoryBackend>>, mut full_stats: bool) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let Ok(mut signals) = signal(SignalKind::user_defined2()) else {
+        return;
+    };
+    while signals.recv().await.is_some() {
+        full_stats = !full_stats;
+        tt.set_stats_type(match full_stats {
+            true => StatsType::Full,
+            false => StatsType::Essential,
+        });
+        println!(
+            "{event_type} - {state} full task stats",
+            event_type = "event".purple(),
+            state = if full_stats { "enabled" } else { "disabled" },
+        );
+    }
+}
+
+#[cfg(not(unix))]
+async fn toggle_full_stats_on_signal(_tt: Arc<TurboTasks<MemoryBackend>>, _full_stats: bool) {}
+
+const DEFAULT_WATCH_RETRIES: u32 = 3;
+
+/// The wait before the first retry of setting up native file events. Each
+/// further retry waits twice as long.
+const WATCH_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+const DEFAULT_WATCH_FALLBACK_POLL: Duration = Duration::from_secs(1);
+
+/// Resolves with an error when `ready` isn't signaled within `timeout`
+/// seconds. Never resolves otherwise.
+async fn ready_timeout(
+    tt: Arc<TurboTasks<MemoryBackend>>,
+    timeout: Option<u64>,
+    mut ready: tokio::sync::watch::Receiver<bool>,
+) -> anyhow::Error {
+    if let Some(timeout) = timeout {
+        if tokio::time::timeout(Duration::from_secs(timeout), ready.wait_for(|&ready| ready))
+            .await
+            .is_err()
+        {
+            return anyhow!(
+                "the first compilation didn't finish within {timeout}s, {tasks} tasks are still \
+                 in progress",
+                tasks = tt.get_in_progress_count(),
+            );
+        }
+    }
+    std::future::pending().await
 }
 
 #[cfg(feature = "profile")]
@@ -696,3 +2032,237 @@ where
         self()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, TcpStream},
+        path::Path,
+        time::Duration,
+    };
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use turbo_tasks::{TurboTasks, Vc};
+    use turbopack_binding::turbo::{
+        tasks_fs::{DiskFileSystem, FileContent, FileSystem},
+        tasks_memory::MemoryBackend,
+    };
+
+    use super::{
+        dist_root, listen_hosts, load_env, load_next_config_or_default, named_browser,
+        next_config_execution_context, output_dirs, output_fs, project_dist_dir, project_fs,
+        project_path, register, Browser, NextDevServerBuilder,
+    };
+
+    #[test]
+    fn browser_option_selects_browser() {
+        assert_eq!(named_browser("firefox"), Some(webbrowser::Browser::Firefox));
+        assert_eq!(named_browser("Chrome"), Some(webbrowser::Browser::Chrome));
+        assert_eq!(
+            named_browser("ie"),
+            Some(webbrowser::Browser::InternetExplorer)
+        );
+        assert_eq!(named_browser("netscape"), None);
+    }
+
+    #[test]
+    fn browser_follows_env() {
+        assert_eq!(Browser::from_env(None), Browser::Default);
+        assert_eq!(Browser::from_env(Some("")), Browser::Default);
+        assert_eq!(Browser::from_env(Some("none")), Browser::None);
+        assert_eq!(Browser::from_env(Some("NONE")), Browser::None);
+        assert_eq!(
+            Browser::from_env(Some("firefox")),
+            Browser::Command("firefox".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn project_fs_invalidates_without_reasons() {
+        register();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("index.js"), "before").unwrap();
+        let root = dir.path().to_str().unwrap().to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+
+        let read = || {
+            let root = root.clone();
+            tt.run_once(async move {
+                let path = project_fs(root, true, false, 0)
+                    .root()
+                    .join("index.js".to_string());
+                let content = path.read().strongly_consistent().await?;
+                Ok(match &*content {
+                    FileContent::Content(file) => file.content().to_str()?.to_string(),
+                    FileContent::NotFound => String::new(),
+                })
+            })
+        };
+
+        assert_eq!(read().await.unwrap(), "before");
+        fs::write(dir.path().join("index.js"), "after").unwrap();
+        for _ in 0..50 {
+            if read().await.unwrap() == "after" {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("the change was not picked up by the watcher");
+    }
+
+    #[tokio::test]
+    async fn serves_routes_from_an_injected_file_system() {
+        register();
+        // The injected file system is the workspace, so the `next` package
+        // resolves as usual.
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(4)
+            .unwrap();
+        let dir = tempfile::tempdir_in(env!("CARGO_MANIFEST_DIR")).unwrap();
+        fs::create_dir(dir.path().join("public")).unwrap();
+        fs::write(dir.path().join("public/hello.txt"), "hello").unwrap();
+        // The root directory only exists as the root of the injected file
+        // system, so the project can only be found relative to it.
+        let root_dir = "/injected".to_string();
+        let project_relative = dir.path().strip_prefix(workspace).unwrap();
+        let project_dir = format!("{root_dir}/{}", project_relative.to_str().unwrap());
+        let tt = TurboTasks::new(MemoryBackend::default());
+
+        let workspace_root = workspace.to_str().unwrap().to_string();
+        let output_root = dir.path().to_str().unwrap().to_string();
+        let (project_fs, output_fs) = tt
+            .run_once(async move {
+                let project_fs: Vc<Box<dyn FileSystem>> =
+                    Vc::upcast(DiskFileSystem::new("injected".to_string(), workspace_root));
+                let output_fs: Vc<Box<dyn FileSystem>> = Vc::upcast(DiskFileSystem::new(
+                    "injected-output".to_string(),
+                    output_root,
+                ));
+                Ok((project_fs.resolve().await?, output_fs.resolve().await?))
+            })
+            .await
+            .unwrap();
+        let server = NextDevServerBuilder::new(tt, project_dir, root_dir)
+            .project_fs(project_fs)
+            .output_fs(output_fs)
+            .hostname(IpAddr::V4(Ipv4Addr::LOCALHOST))
+            .port(portpicker::pick_unused_port().unwrap())
+            .build()
+            .await
+            .unwrap();
+        let addr = server.addr;
+        tokio::spawn(server.future);
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /hello.txt HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(body.contains("hello"), "{response}");
+    }
+
+    #[test]
+    fn unspecified_v6_host_covers_other_hosts() {
+        let v4 = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+        let v6 = IpAddr::V6(Ipv6Addr::UNSPECIFIED);
+        let loopback = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert_eq!(listen_hosts(&[v6, v4]), vec![v6]);
+        assert_eq!(listen_hosts(&[v4, v6]), vec![v6]);
+        assert_eq!(listen_hosts(&[loopback, v4]), vec![loopback, v4]);
+    }
+
+    #[tokio::test]
+    async fn listens_on_unspecified_v6_and_v4_hosts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap().to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let builder = NextDevServerBuilder::new(tt, root.clone(), root);
+        let port = portpicker::pick_unused_port().unwrap();
+
+        let listeners = builder
+            .find_port(
+                &[
+                    IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                    IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                ],
+                port,
+                1,
+            )
+            .unwrap();
+
+        assert_eq!(listeners.len(), 1);
+        TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+        TcpStream::connect((Ipv6Addr::LOCALHOST, port)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn output_follows_dist_dir() {
+        register();
+        // The config is evaluated with the `next` package of the workspace.
+        let dir = tempfile::tempdir_in(env!("CARGO_MANIFEST_DIR")).unwrap();
+        fs::write(
+            dir.path().join("next.config.js"),
+            "module.exports = { distDir: 'out' }",
+        )
+        .unwrap();
+        let root = dir.path().to_str().unwrap().to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+
+        let (dist_dir, dist_root) = tt
+            .run_once(async move {
+                // Used for the trace file.
+                let dist_dir = project_dist_dir(root.clone(), root.clone()).await?;
+                // Used for the build and server output.
+                let project_path =
+                    project_path(project_fs(root.clone(), false, false, 0), &root, &root);
+                let output_fs = output_fs(root, false);
+                let next_config = load_next_config_or_default(next_config_execution_context(
+                    project_path,
+                    output_fs,
+                    load_env(project_path),
+                ));
+                let dist_root = dist_root(output_fs, next_config).await?;
+                Ok((dist_dir.clone_value(), dist_root.path.clone()))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(dist_dir, "out");
+        assert_eq!(dist_root, "out");
+    }
+
+    #[tokio::test]
+    async fn polling_skips_the_dist_dir_of_every_project() {
+        register();
+        // The config is evaluated with the `next` package of the workspace.
+        let dir = tempfile::tempdir_in(env!("CARGO_MANIFEST_DIR")).unwrap();
+        fs::write(
+            dir.path().join("next.config.js"),
+            "module.exports = { distDir: 'build' }",
+        )
+        .unwrap();
+        let mounted = dir.path().join("docs");
+        fs::create_dir(&mounted).unwrap();
+        let root = dir.path().to_str().unwrap().to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+
+        let output_dirs = output_dirs(
+            &tt,
+            &root,
+            [root.clone(), mounted.to_str().unwrap().to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            output_dirs,
+            vec![dir.path().join("build"), mounted.join(".next")]
+        );
+    }
+}
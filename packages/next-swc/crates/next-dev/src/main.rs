@@ -20,21 +20,26 @@ fn main() -> Result<()> {
 fn main() {
     use turbopack_binding::turbo::malloc::TurboMalloc;
 
-    tokio::runtime::Builder::new_multi_thread()
+    let options = next_dev::devserver_options::DevServerOptions::parse();
+
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    // Turbo Engine schedules its tasks on the runtime's worker threads.
+    if let Some(threads) = options.threads.filter(|&threads| threads > 0) {
+        builder.worker_threads(threads);
+    }
+    builder
         .enable_all()
         .on_thread_stop(|| {
             TurboMalloc::thread_stop();
         })
         .build()
         .unwrap()
-        .block_on(main_inner())
+        .block_on(main_inner(options))
         .unwrap()
 }
 
 #[cfg(feature = "cli")]
-async fn main_inner() -> Result<()> {
-    let options = next_dev::devserver_options::DevServerOptions::parse();
-
+async fn main_inner(options: next_dev::devserver_options::DevServerOptions) -> Result<()> {
     if options.display_version {
         // Note: enabling git causes trouble with aarch64 linux builds with libz-sys
         println!(
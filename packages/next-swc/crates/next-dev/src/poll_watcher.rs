@@ -0,0 +1,442 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Result;
+use turbo_tasks::{TurboTasks, Vc};
+use turbopack_binding::{
+    turbo::{
+        tasks_fs::{DiskFileSystem, FileSystemPath},
+        tasks_memory::MemoryBackend,
+    },
+    turbopack::core::issue::{Issue, IssueSeverity},
+};
+
+use crate::idle::{IdleMonitor, WatchedFileSystem};
+
+/// Detects file changes by periodically scanning the file systems instead of
+/// relying on native file events, which are not delivered on some network
+/// file systems and container volumes.
+///
+/// Every poll walks the whole tree and stats each file, so the CPU cost grows
+/// with the size of the tree and shrinks with the interval. Changed files are
+/// not known individually, so a detected change invalidates every read of
+/// the file system. Unchanged files are read again but don't cause
+/// recomputation.
+pub(crate) struct PollWatcher {
+    interval: Duration,
+    turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+    file_systems: Vec<WatchedFileSystem>,
+    /// The output directories of the served projects. The dev server writes
+    /// to them on every compilation, so scanning them would rebuild forever.
+    output_dirs: Arc<Vec<PathBuf>>,
+    idle_monitor: Option<Arc<IdleMonitor>>,
+}
+
+impl PollWatcher {
+    pub fn new(
+        interval: Duration,
+        turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+        file_systems: Vec<WatchedFileSystem>,
+        output_dirs: Vec<PathBuf>,
+        idle_monitor: Option<Arc<IdleMonitor>>,
+    ) -> Self {
+        PollWatcher {
+            interval,
+            turbo_tasks,
+            file_systems,
+            output_dirs: Arc::new(output_dirs),
+            idle_monitor,
+        }
+    }
+
+    /// Spawns the background task that polls for changes.
+    pub fn start(self) {
+        tokio::spawn(async move {
+            let mut fingerprints = Vec::with_capacity(self.file_systems.len());
+            for file_system in &self.file_systems {
+                fingerprints.push(snapshot(&file_system.root, &self.output_dirs).await);
+            }
+            loop {
+                tokio::time::sleep(self.interval).await;
+                // Changes made while idle are picked up by the first poll after
+                // resuming.
+                if let Some(idle_monitor) = &self.idle_monitor {
+                    idle_monitor.wait_until_active().await;
+                }
+                for (file_system, fingerprint) in
+                    self.file_systems.iter().zip(fingerprints.iter_mut())
+                {
+                    let new_fingerprint = snapshot(&file_system.root, &self.output_dirs).await;
+                    if new_fingerprint == *fingerprint {
+                        continue;
+                    }
+                    *fingerprint = new_fingerprint;
+                    if let Err(err) = self.invalidate(file_system).await {
+                        eprintln!("failed to invalidate {}: {err:?}", file_system.name);
+                    }
+                }
+            }
+        });
+    }
+
+    async fn invalidate(&self, file_system: &WatchedFileSystem) -> Result<()> {
+        let name = file_system.name.clone();
+        let root = file_system.root.clone();
+        self.turbo_tasks
+            .run_once(async move {
+                // `DiskFileSystem::new` is a turbo-tasks function, so this
+                // resolves to the instance created by the dev server.
+                DiskFileSystem::new(name, root).await?.invalidate();
+                Ok(())
+            })
+            .await
+    }
+}
+
+async fn snapshot(root: &str, output_dirs: &Arc<Vec<PathBuf>>) -> u64 {
+    let root = root.to_string();
+    let output_dirs = output_dirs.clone();
+    tokio::task::spawn_blocking(move || fingerprint(Path::new(&root), &output_dirs))
+        .await
+        .unwrap_or_default()
+}
+
+/// Directories which are not scanned wherever they are: they are large and
+/// written by tools rather than edited.
+const IGNORED_DIRS: &[&str] = &["node_modules", ".git"];
+
+/// Hashes the paths, sizes and modification times of all entries under
+/// `root`, except for [IGNORED_DIRS] and the `output_dirs`. Entries that can't
+/// be read are skipped.
+fn fingerprint(root: &Path, output_dirs: &[PathBuf]) -> u64 {
+    fn visit(dir: &Path, output_dirs: &[PathBuf], hasher: &mut DefaultHasher) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut entries: Vec<_> = entries.filter_map(|entry| entry.ok()).collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let path = entry.path();
+            path.hash(hasher);
+            // Don't follow symlinks, they may point outside of the root or
+            // form cycles.
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            metadata.len().hash(hasher);
+            metadata.modified().ok().hash(hasher);
+            if metadata.is_dir()
+                && !IGNORED_DIRS
+                    .iter()
+                    .any(|ignored| entry.file_name() == *ignored)
+                && !output_dirs.contains(&path)
+            {
+                visit(&path, output_dirs, hasher);
+            }
+        }
+    }
+
+    let mut hasher = DefaultHasher::new();
+    visit(root, output_dirs, &mut hasher);
+    hasher.finish()
+}
+
+/// How changes in the project directory are detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WatchMode {
+    /// Native file events are delivered.
+    Native,
+    /// Native file events couldn't be set up for `reason`, so the project is
+    /// scanned every `interval` instead.
+    Poll { interval: Duration, reason: String },
+}
+
+/// Calls `start` until it succeeds, retrying at most `retries` times. The
+/// first retry waits `backoff`, and each further one waits twice as long as
+/// the one before. Setting up native file events can fail transiently, e.g.
+/// when another process briefly holds many inotify watches.
+pub(crate) async fn retry_with_backoff<T>(
+    retries: u32,
+    mut backoff: Duration,
+    mut start: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match start() {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt >= retries => return Err(err),
+            Err(_) => {
+                attempt += 1;
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+    }
+}
+
+/// Decides how to detect changes after starting native file events resulted
+/// in `started`. A failure falls back to scanning every `fallback_poll`, or is
+/// returned when there is no fallback.
+pub(crate) fn watch_mode(
+    started: Result<()>,
+    fallback_poll: Option<Duration>,
+) -> Result<WatchMode> {
+    match (started, fallback_poll) {
+        (Ok(()), _) => Ok(WatchMode::Native),
+        (Err(err), Some(interval)) => Ok(WatchMode::Poll {
+            interval,
+            reason: format!("{err:#}"),
+        }),
+        (Err(err), None) => Err(err.context("failed to watch the project directory for changes")),
+    }
+}
+
+/// Reported when native file events couldn't be set up and changes are
+/// detected by scanning the project instead.
+#[turbo_tasks::value(shared)]
+pub(crate) struct WatchFallbackIssue {
+    pub path: Vc<FileSystemPath>,
+    pub reason: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for WatchFallbackIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("watch".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(
+            "File changes are detected by polling because watching the project failed".to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "{}\n\nPolling scans the whole project, which costs CPU and picks up changes later. \
+             On Linux, this is usually caused by the inotify limits, which can be raised with \
+             `sysctl fs.inotify.max_user_watches` and `sysctl fs.inotify.max_user_instances`.",
+            self.reason
+        ))
+    }
+}
+
+/// File systems which don't deliver native file events for changes made
+/// outside of the container or VM, e.g. on the host of a Docker Desktop
+/// volume.
+const UNWATCHABLE_FILE_SYSTEMS: &[&str] = &[
+    "9p",
+    "cifs",
+    "fakeowner",
+    "fuse.grpcfuse",
+    "fuse.osxfs",
+    "nfs",
+    "nfs4",
+    "prl_fs",
+    "smb3",
+    "vboxsf",
+    "virtiofs",
+];
+
+/// Whether polling would likely pick up changes to `path` which native file
+/// events miss: the dev server runs in a container and `path` is on a volume
+/// shared with the host.
+pub(crate) fn polling_would_help(path: &Path) -> bool {
+    is_container()
+        && fs::read_to_string("/proc/self/mountinfo").map_or(false, |mountinfo| {
+            mount_fs_type(&mountinfo, path)
+                .map_or(false, |fs_type| UNWATCHABLE_FILE_SYSTEMS.contains(&fs_type))
+        })
+}
+
+/// The type of the file system `path` is on, according to the contents of
+/// `/proc/self/mountinfo`: the mount with the longest mount point containing
+/// `path`.
+fn mount_fs_type<'a>(mountinfo: &'a str, path: &Path) -> Option<&'a str> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let (mount, fs) = line.split_once(" - ")?;
+            let mount_point = mount.split(' ').nth(4)?;
+            let fs_type = fs.split(' ').next()?;
+            // Spaces in mount points are escaped as `\040`.
+            let mount_point = mount_point.replace("\\040", " ");
+            path.starts_with(&mount_point)
+                .then(|| (mount_point.len(), fs_type))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, fs_type)| fs_type)
+}
+
+/// Whether the dev server seems to run inside a container.
+fn is_container() -> bool {
+    Path::new("/.dockerenv").exists()
+        || Path::new("/run/.containerenv").exists()
+        || std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+        || fs::read_to_string("/proc/1/cgroup").map_or(false, |cgroup| is_container_cgroup(&cgroup))
+}
+
+fn is_container_cgroup(cgroup: &str) -> bool {
+    cgroup.lines().any(|line| {
+        ["docker", "kubepods", "containerd", "lxc"]
+            .iter()
+            .any(|runtime| line.contains(runtime))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path, time::Duration};
+
+    use anyhow::bail;
+
+    use super::{
+        fingerprint, is_container_cgroup, mount_fs_type, retry_with_backoff, watch_mode, WatchMode,
+    };
+
+    #[test]
+    fn fingerprint_changes_with_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/index.js"), "a").unwrap();
+        let initial = fingerprint(dir.path(), &[]);
+        assert_eq!(fingerprint(dir.path(), &[]), initial);
+
+        fs::write(dir.path().join("src/index.js"), "ab").unwrap();
+        let modified = fingerprint(dir.path(), &[]);
+        assert_ne!(modified, initial);
+
+        fs::write(dir.path().join("src/other.js"), "").unwrap();
+        assert_ne!(fingerprint(dir.path(), &[]), modified);
+    }
+
+    #[test]
+    fn fingerprint_ignores_output_and_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        // A project with `distDir: "build"`, and a mounted project using the
+        // default `distDir`.
+        let output_dirs = [dir.path().join("build"), dir.path().join("docs/.next")];
+        fs::create_dir_all(dir.path().join("build/server")).unwrap();
+        fs::create_dir_all(dir.path().join("docs/.next/server")).unwrap();
+        fs::create_dir_all(dir.path().join("node_modules/react")).unwrap();
+        let initial = fingerprint(dir.path(), &output_dirs);
+
+        fs::write(dir.path().join("build/server/page.js"), "a").unwrap();
+        fs::write(dir.path().join("docs/.next/server/page.js"), "a").unwrap();
+        fs::write(dir.path().join("node_modules/react/index.js"), "a").unwrap();
+        assert_eq!(fingerprint(dir.path(), &output_dirs), initial);
+    }
+
+    #[test]
+    fn fingerprint_scans_directories_named_like_other_output() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".next")).unwrap();
+        let output_dirs = [dir.path().join("build")];
+        let initial = fingerprint(dir.path(), &output_dirs);
+
+        // Only the configured `distDir` is output, `.next` is a source
+        // directory of this project.
+        fs::write(dir.path().join(".next/notes.md"), "a").unwrap();
+        assert_ne!(fingerprint(dir.path(), &output_dirs), initial);
+    }
+
+    #[test]
+    fn finds_the_file_system_of_a_path() {
+        let mountinfo = [
+            "1 0 0:1 / / rw - overlay overlay rw",
+            "2 1 0:2 / /app rw - fakeowner /run/host_mark/Users rw",
+            "3 2 0:3 / /app/node_modules rw - ext4 /dev/vda1 rw",
+            r"4 1 0:4 / /my\040app rw - virtiofs shared rw",
+        ]
+        .join("\n");
+        let mountinfo = mountinfo.as_str();
+        assert_eq!(
+            mount_fs_type(mountinfo, Path::new("/app/src")),
+            Some("fakeowner")
+        );
+        assert_eq!(
+            mount_fs_type(mountinfo, Path::new("/app/node_modules/react")),
+            Some("ext4")
+        );
+        assert_eq!(
+            mount_fs_type(mountinfo, Path::new("/my app")),
+            Some("virtiofs")
+        );
+        assert_eq!(mount_fs_type(mountinfo, Path::new("/srv")), Some("overlay"));
+        // A mount point only contains paths below it.
+        assert_eq!(
+            mount_fs_type(mountinfo, Path::new("/application")),
+            Some("overlay")
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_until_watching_starts() {
+        let mut attempts = 0;
+        let started = retry_with_backoff(3, Duration::from_millis(1), || {
+            attempts += 1;
+            if attempts < 3 {
+                bail!("inotify watch limit reached");
+            }
+            Ok(())
+        })
+        .await;
+        assert_eq!(attempts, 3);
+        assert_eq!(
+            watch_mode(started, Some(Duration::from_secs(1))).unwrap(),
+            WatchMode::Native
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_polling_when_watching_fails() {
+        let mut attempts = 0;
+        let started = retry_with_backoff(2, Duration::from_millis(1), || {
+            attempts += 1;
+            bail!("inotify watch limit reached")
+        })
+        .await;
+        assert_eq!(attempts, 3);
+        assert_eq!(
+            watch_mode(started, Some(Duration::from_secs(1))).unwrap(),
+            WatchMode::Poll {
+                interval: Duration::from_secs(1),
+                reason: "inotify watch limit reached".to_string(),
+            }
+        );
+
+        let started = retry_with_backoff(0, Duration::from_millis(1), || {
+            bail!("inotify watch limit reached")
+        })
+        .await;
+        assert!(watch_mode(started, None).is_err());
+    }
+
+    #[test]
+    fn detects_container_cgroups() {
+        assert!(is_container_cgroup(
+            "12:pids:/docker/3f5e6a\n11:memory:/docker/3f5e6a"
+        ));
+        assert!(is_container_cgroup("0::/kubepods/besteffort/pod1234"));
+        assert!(!is_container_cgroup("0::/user.slice/user-1000.slice"));
+    }
+}
@@ -0,0 +1,144 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use qrcode::{render::unicode, QrCode};
+
+/// Prints a QR code of the URL other devices in the local network can reach
+/// the server at.
+pub(crate) fn print_lan_qr_code(addr: SocketAddr) {
+    if addr.ip().is_loopback() {
+        println!(
+            "{} - the server is bound to {}, which other devices can't reach. Use --hostname \
+             0.0.0.0 to listen on all interfaces",
+            "warn ".yellow(),
+            addr.ip()
+        );
+        return;
+    }
+    let Some(lan_addr) = lan_addr(addr, local_lan_ip(addr.ip())) else {
+        println!(
+            "{} - could not determine the LAN address of this machine",
+            "warn ".yellow(),
+        );
+        return;
+    };
+    let url = format!("http://{lan_addr}");
+    match render(&url) {
+        Ok(qr_code) => println!(
+            "{event_type} - scan to open {url}\n{qr_code}",
+            event_type = "event".purple(),
+        ),
+        Err(err) => eprintln!("failed to render QR code: {err:?}"),
+    }
+}
+
+/// The URLs other devices in the local network can reach the server bound to
+/// `addr` at. Empty when the server can only be reached locally or the
+/// machine has no LAN address.
+pub(crate) fn network_urls(addr: SocketAddr) -> Vec<String> {
+    let v4 = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+    let v6 = IpAddr::V6(Ipv6Addr::UNSPECIFIED);
+    network_addrs(addr, local_lan_ip(v4), local_lan_ip(v6))
+        .into_iter()
+        .map(|addr| format!("http://{addr}"))
+        .collect()
+}
+
+/// Like [lan_addr], but an unspecified IPv6 address also accepts IPv4
+/// connections, so it is reachable at both LAN IPs of the machine.
+fn network_addrs(
+    addr: SocketAddr,
+    lan_v4: Option<IpAddr>,
+    lan_v6: Option<IpAddr>,
+) -> Vec<SocketAddr> {
+    match addr.ip() {
+        IpAddr::V6(ip) if ip.is_unspecified() => [lan_v4, lan_v6]
+            .into_iter()
+            .flatten()
+            .map(|ip| SocketAddr::new(ip, addr.port()))
+            .collect(),
+        IpAddr::V4(_) => lan_addr(addr, lan_v4).into_iter().collect(),
+        IpAddr::V6(_) => lan_addr(addr, lan_v6).into_iter().collect(),
+    }
+}
+
+/// The address to reach the server bound to `addr` at, given the machine's
+/// LAN IP. Returns `None` when the server can only be reached locally.
+fn lan_addr(addr: SocketAddr, lan_ip: Option<IpAddr>) -> Option<SocketAddr> {
+    let ip = addr.ip();
+    if ip.is_loopback() {
+        None
+    } else if ip.is_unspecified() {
+        lan_ip.map(|lan_ip| SocketAddr::new(lan_ip, addr.port()))
+    } else {
+        Some(addr)
+    }
+}
+
+/// Finds the IP of the interface that routes to other hosts. Connecting a UDP
+/// socket doesn't send any packets.
+fn local_lan_ip(bound: IpAddr) -> Option<IpAddr> {
+    let (bind, remote) = match bound {
+        IpAddr::V4(_) => ("0.0.0.0:0", "192.0.2.1:80"),
+        IpAddr::V6(_) => ("[::]:0", "[2001:db8::1]:80"),
+    };
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(remote).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+fn render(url: &str) -> Result<String> {
+    let code = QrCode::new(url.as_bytes())?;
+    // Dark terminals are more common, so the colors are inverted.
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Light)
+        .light_color(unicode::Dense1x2::Dark)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    use super::{lan_addr, network_addrs};
+
+    #[test]
+    fn resolves_lan_addr() {
+        let lan_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+        assert_eq!(
+            lan_addr(([0, 0, 0, 0], 3000).into(), Some(lan_ip)),
+            Some(SocketAddr::new(lan_ip, 3000))
+        );
+        assert_eq!(lan_addr(([0, 0, 0, 0], 3000).into(), None), None);
+        assert_eq!(lan_addr(([127, 0, 0, 1], 3000).into(), Some(lan_ip)), None);
+        assert_eq!(
+            lan_addr(([10, 0, 0, 5], 3000).into(), Some(lan_ip)),
+            Some(([10, 0, 0, 5], 3000).into())
+        );
+    }
+
+    #[test]
+    fn resolves_network_addrs() {
+        let lan_v4 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+        let lan_v6 = IpAddr::V6(Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 20));
+        assert_eq!(
+            network_addrs("[::]:3000".parse().unwrap(), Some(lan_v4), Some(lan_v6)),
+            vec![SocketAddr::new(lan_v4, 3000), SocketAddr::new(lan_v6, 3000)]
+        );
+        assert_eq!(
+            network_addrs("0.0.0.0:3000".parse().unwrap(), Some(lan_v4), Some(lan_v6)),
+            vec![SocketAddr::new(lan_v4, 3000)]
+        );
+        assert_eq!(
+            network_addrs("[::]:3000".parse().unwrap(), None, None),
+            vec![]
+        );
+        assert_eq!(
+            network_addrs("[::1]:3000".parse().unwrap(), Some(lan_v4), Some(lan_v6)),
+            vec![]
+        );
+    }
+}
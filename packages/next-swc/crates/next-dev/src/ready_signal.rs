@@ -0,0 +1,223 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use tokio::sync::watch;
+
+/// How the dev server tells a process supervisor that it's ready, i.e. that
+/// it's listening and the first compilation has finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ReadySignal {
+    /// Only print the ready banner.
+    Stdout,
+    /// Write the ready line to a file, e.g. a named pipe.
+    File(PathBuf),
+    /// Write the ready line to an inherited file descriptor. Only supported on
+    /// unix.
+    Fd(i32),
+}
+
+impl FromStr for ReadySignal {
+    type Err = anyhow::Error;
+
+    /// Parses `stdout`, `file:<path>` or `fd:<n>`.
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "stdout" {
+            return Ok(ReadySignal::Stdout);
+        }
+        if let Some(path) = s.strip_prefix("file:") {
+            if path.is_empty() {
+                bail!("invalid ready signal {s:?}, the file path must not be empty");
+            }
+            return Ok(ReadySignal::File(PathBuf::from(path)));
+        }
+        if let Some(fd) = s.strip_prefix("fd:") {
+            let fd = fd
+                .parse()
+                .with_context(|| format!("invalid ready signal {s:?}, expected fd:<n>"))?;
+            return Ok(ReadySignal::Fd(fd));
+        }
+        bail!("invalid ready signal {s:?}, expected stdout, file:<path> or fd:<n>")
+    }
+}
+
+/// The line written once the server is listening and the first compilation
+/// has finished, e.g. `{"url":"http://localhost:3000"}`.
+fn ready_line(url: &str) -> String {
+    format!("{}\n", json!({ "url": url }))
+}
+
+/// Checks that `fd` is an open descriptor other than stdin, stdout or stderr.
+/// The standard streams are rejected because the ready line would be mixed
+/// into the output of the server.
+#[cfg(unix)]
+pub(crate) fn check_ready_fd(fd: i32) -> Result<()> {
+    use nix::fcntl::{fcntl, FcntlArg};
+
+    if (0..=2).contains(&fd) {
+        bail!("the ready signal fd {fd} is a standard stream, pass an inherited fd from 3 on");
+    }
+    fcntl(fd, FcntlArg::F_GETFD)
+        .with_context(|| format!("the ready signal fd {fd} is not an open file descriptor"))?;
+    Ok(())
+}
+
+/// Writes the ready line to the file descriptor `fd`, which was inherited
+/// from a process supervisor. The descriptor is only borrowed, so it stays
+/// open for its owner.
+#[cfg(unix)]
+pub(crate) fn signal_ready_fd(fd: i32, url: &str) -> Result<()> {
+    use std::{fs::File, os::unix::io::BorrowedFd};
+
+    check_ready_fd(fd)?;
+    // SAFETY: `fd` was checked to be open, and it is only borrowed for the
+    // duplication below.
+    let borrowed = unsafe { BorrowedFd::borrow_raw(fd) };
+    let mut file = File::from(
+        borrowed
+            .try_clone_to_owned()
+            .with_context(|| format!("failed to duplicate the ready signal fd {fd}"))?,
+    );
+    file.write_all(ready_line(url).as_bytes())
+        .with_context(|| format!("failed to write the ready signal to fd {fd}"))
+}
+
+/// Writes the ready line to the file at `path`. The file isn't truncated, so
+/// it can be a named pipe a process supervisor reads from.
+pub(crate) fn signal_ready_file(path: &Path, url: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)
+        .with_context(|| format!("failed to open the ready file {}", path.display()))?;
+    file.write_all(ready_line(url).as_bytes())
+        .with_context(|| format!("failed to write the ready file {}", path.display()))
+}
+
+/// Waits until `ready` is signaled, which happens once the server is
+/// listening and the first compilation has settled, and then sends each of
+/// the `signals`.
+pub(crate) async fn signal_ready(
+    signals: Vec<ReadySignal>,
+    url: String,
+    mut ready: watch::Receiver<bool>,
+) {
+    if signals.iter().all(|signal| *signal == ReadySignal::Stdout) {
+        return;
+    }
+    if ready.wait_for(|&ready| ready).await.is_err() {
+        return;
+    }
+    for signal in signals {
+        let result = match signal {
+            ReadySignal::Stdout => Ok(()),
+            ReadySignal::File(path) => signal_ready_file(&path, &url),
+            #[cfg(unix)]
+            ReadySignal::Fd(fd) => signal_ready_fd(fd, &url),
+            #[cfg(not(unix))]
+            ReadySignal::Fd(_) => Ok(()),
+        };
+        if let Err(err) = result {
+            eprintln!("{err:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf, time::Duration};
+
+    use tokio::sync::watch;
+
+    use super::{signal_ready, signal_ready_file, ReadySignal};
+
+    #[test]
+    fn parses_ready_signals() {
+        assert_eq!(
+            "stdout".parse::<ReadySignal>().unwrap(),
+            ReadySignal::Stdout
+        );
+        assert_eq!(
+            "file:/tmp/ready".parse::<ReadySignal>().unwrap(),
+            ReadySignal::File(PathBuf::from("/tmp/ready"))
+        );
+        assert_eq!("fd:3".parse::<ReadySignal>().unwrap(), ReadySignal::Fd(3));
+        assert!("fd:three".parse::<ReadySignal>().is_err());
+        assert!("file:".parse::<ReadySignal>().is_err());
+        assert!("socket".parse::<ReadySignal>().is_err());
+    }
+
+    #[test]
+    fn writes_ready_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ready");
+        signal_ready_file(&path, "http://localhost:3000").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{\"url\":\"http://localhost:3000\"}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn ready_file_appears_after_first_settle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ready");
+        let (sender, receiver) = watch::channel(false);
+        let signal = tokio::spawn(signal_ready(
+            vec![ReadySignal::File(path.clone())],
+            "http://localhost:3000".to_string(),
+            receiver,
+        ));
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!path.exists());
+
+        sender.send_replace(true);
+        signal.await.unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{\"url\":\"http://localhost:3000\"}\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writes_ready_fd() {
+        use std::{fs::File, io::Read, os::unix::io::FromRawFd};
+
+        use super::signal_ready_fd;
+
+        let (read_fd, write_fd) = nix::unistd::pipe().unwrap();
+        signal_ready_fd(write_fd, "http://localhost:3000").unwrap();
+        // The fd is still open after the signal, closing it ends the read.
+        nix::unistd::close(write_fd).unwrap();
+        let mut line = String::new();
+        // SAFETY: The read end of the pipe isn't used anywhere else.
+        unsafe { File::from_raw_fd(read_fd) }
+            .read_to_string(&mut line)
+            .unwrap();
+        assert_eq!(line, "{\"url\":\"http://localhost:3000\"}\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_standard_and_closed_fds() {
+        use super::check_ready_fd;
+
+        for fd in 0..=2 {
+            assert!(check_ready_fd(fd).is_err());
+        }
+        assert!(check_ready_fd(-1).is_err());
+
+        let (read_fd, write_fd) = nix::unistd::pipe().unwrap();
+        check_ready_fd(write_fd).unwrap();
+        nix::unistd::close(read_fd).unwrap();
+        nix::unistd::close(write_fd).unwrap();
+        assert!(check_ready_fd(write_fd).is_err());
+    }
+}
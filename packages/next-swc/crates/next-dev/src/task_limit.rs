@@ -0,0 +1,135 @@
+use std::{future::Future, time::Duration};
+
+use anyhow::{anyhow, Result};
+use turbo_tasks::TurboTasks;
+use turbopack_binding::turbo::tasks_memory::MemoryBackend;
+
+/// How often the number of in-progress tasks is checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Waits for `operation`, but gives up with an error as soon as more than
+/// `max_tasks` tasks are in progress at the same time, instead of letting a
+/// runaway task graph hang the caller.
+///
+/// Like [tokio::time::timeout], this only stops waiting. The tasks that were
+/// already scheduled keep running in `turbo_tasks`, which can't cancel them.
+/// The count also includes the tasks of any other operation running on the
+/// same instance, so the limit bounds the whole instance.
+pub async fn timeout_on_task_limit<T>(
+    turbo_tasks: &TurboTasks<MemoryBackend>,
+    max_tasks: usize,
+    operation: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::select! {
+        result = operation => result,
+        err = task_limit_exceeded(turbo_tasks, max_tasks) => Err(err),
+    }
+}
+
+/// Resolves once more than `max_tasks` tasks are in progress at the same
+/// time, with an error naming the likely cause.
+pub async fn task_limit_exceeded(
+    turbo_tasks: &TurboTasks<MemoryBackend>,
+    max_tasks: usize,
+) -> anyhow::Error {
+    task_count_exceeded(
+        || turbo_tasks.get_in_progress_count(),
+        max_tasks,
+        POLL_INTERVAL,
+    )
+    .await
+}
+
+async fn task_count_exceeded(
+    task_count: impl Fn() -> usize,
+    max_tasks: usize,
+    interval: Duration,
+) -> anyhow::Error {
+    loop {
+        let tasks = task_count();
+        if tasks > max_tasks {
+            return anyhow!(
+                "{tasks} tasks were in progress at the same time, more than the limit of \
+                 {max_tasks}. This usually means that a glob, a dynamic import or require, or a \
+                 require.context call matches far more files than intended, e.g. a whole disk. \
+                 Raise the limit if the project is just very large."
+            );
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::pending,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use anyhow::Result;
+    use turbo_tasks::TurboTasks;
+    use turbopack_binding::turbo::tasks_memory::MemoryBackend;
+
+    use super::{task_count_exceeded, timeout_on_task_limit};
+
+    /// Schedules `count` tasks on `turbo_tasks` which stay in progress for
+    /// the rest of the test.
+    fn schedule_busy_tasks(turbo_tasks: &Arc<TurboTasks<MemoryBackend>>, count: usize) {
+        for _ in 0..count {
+            // Once tasks are scheduled when created, not when awaited.
+            let _ = turbo_tasks.run_once(async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(())
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_waiting_when_turbo_tasks_exceed_the_limit() {
+        let tt = TurboTasks::new(MemoryBackend::default());
+        schedule_busy_tasks(&tt, 5);
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            timeout_on_task_limit(&tt, 3, pending::<Result<()>>()),
+        )
+        .await
+        .expect("the task limit was not detected");
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("more than the limit of 3"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn returns_the_result_below_the_limit() {
+        let tt = TurboTasks::new(MemoryBackend::default());
+        schedule_busy_tasks(&tt, 2);
+        let result = timeout_on_task_limit(&tt, 3, async { Ok(42) }).await;
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn aborts_when_the_task_count_exceeds_the_limit() {
+        let count = AtomicUsize::new(0);
+        let err = task_count_exceeded(
+            || count.fetch_add(10, Ordering::SeqCst),
+            25,
+            Duration::from_millis(1),
+        )
+        .await;
+        assert!(err.to_string().starts_with("30 tasks were in progress"));
+        assert!(err.to_string().contains("limit of 25"));
+    }
+
+    #[tokio::test]
+    async fn keeps_waiting_below_the_limit() {
+        let result = tokio::time::timeout(
+            Duration::from_millis(20),
+            task_count_exceeded(|| 25, 25, Duration::from_millis(1)),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use futures::{stream, Stream};
+use mime::APPLICATION_OCTET_STREAM;
+use tokio::io::AsyncReadExt;
+use turbo_tasks::{util::SharedError, Value, Vc};
+use turbopack_binding::{
+    turbo::tasks_bytes::Bytes,
+    turbopack::dev_server::source::{
+        route_tree::{RouteTree, RouteType},
+        Body, ContentSource, ContentSourceContent, ContentSourceData, ContentSourceDataVary,
+        GetContentSourceContent, ProxyResult,
+    },
+};
+
+/// The name a downloaded trace file is saved as.
+const CONTENT_DISPOSITION: &str = "attachment; filename=\"trace.log\"";
+
+/// How much of the trace file is read at once when serving it.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The file the `NEXT_TURBOPACK_TRACING` trace is written to. Writes are
+/// buffered, and the buffer is flushed before the file is served, so a
+/// download contains every event the trace writer has written so far.
+#[derive(Clone, Debug)]
+pub struct TraceFile {
+    path: PathBuf,
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl TraceFile {
+    pub fn create(path: PathBuf) -> Result<Self> {
+        let file = File::create(&path)
+            .with_context(|| format!("failed to create the trace file {}", path.display()))?;
+        Ok(TraceFile {
+            path,
+            writer: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Write for TraceFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.lock().unwrap().flush()
+    }
+}
+
+/// Serves the trace file, so it can be fetched from a remote dev server. It
+/// is only mounted with `--serve-trace`. Every request flushes the trace and
+/// reads the file again, and the file is sent in chunks as it is read, so a
+/// large trace isn't read into one buffer before responding.
+#[turbo_tasks::value(serialization = "none", eq = "manual", cell = "new", into = "new")]
+pub struct TraceFileSource {
+    #[turbo_tasks(trace_ignore)]
+    trace_file: TraceFile,
+}
+
+impl TraceFileSource {
+    pub fn new(trace_file: TraceFile) -> Vc<Self> {
+        Self::cell(TraceFileSource { trace_file })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for TraceFileSource {
+    #[turbo_tasks::function]
+    fn get_routes(self: Vc<Self>) -> Vc<RouteTree> {
+        RouteTree::new_route(Vec::new(), RouteType::Exact, Vc::upcast(self))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for TraceFileSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> Vc<ContentSourceDataVary> {
+        // The file changes with every trace event, so the response must not
+        // be cached.
+        ContentSourceDataVary {
+            cache_buster: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        self: Vc<Self>,
+        _path: String,
+        _data: Value<ContentSourceData>,
+    ) -> Result<Vc<ContentSourceContent>> {
+        let this = self.await?;
+        let mut trace_file = this.trace_file.clone();
+        trace_file
+            .flush()
+            .context("failed to flush the trace file")?;
+        let file = tokio::fs::File::open(trace_file.path())
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to read the trace file {}",
+                    trace_file.path().display()
+                )
+            })?;
+        Ok(ContentSourceContent::HttpProxy(
+            ProxyResult {
+                status: 200,
+                headers: vec![
+                    (
+                        "content-type".to_string(),
+                        APPLICATION_OCTET_STREAM.to_string(),
+                    ),
+                    (
+                        "content-disposition".to_string(),
+                        CONTENT_DISPOSITION.to_string(),
+                    ),
+                ],
+                body: Body::from_stream(read_chunks(file)),
+            }
+            .cell(),
+        )
+        .cell())
+    }
+}
+
+/// Reads `file` in chunks of [CHUNK_SIZE] until its current end.
+fn read_chunks(
+    file: tokio::fs::File,
+) -> impl Stream<Item = Result<Bytes, SharedError>> + Send + Unpin + 'static {
+    Box::pin(stream::try_unfold(file, |mut file| async move {
+        let mut chunk = vec![0; CHUNK_SIZE];
+        let read = file
+            .read(&mut chunk)
+            .await
+            .map_err(|err| SharedError::new(err.into()))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        chunk.truncate(read);
+        Ok(Some((Bytes::from(chunk), file)))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use futures::TryStreamExt;
+    use turbo_tasks::{TurboTasks, Value};
+    use turbopack_binding::{
+        turbo::tasks_memory::MemoryBackend,
+        turbopack::dev_server::source::{
+            ContentSourceContent, ContentSourceData, GetContentSourceContent,
+        },
+    };
+
+    use super::{TraceFile, TraceFileSource, CHUNK_SIZE, CONTENT_DISPOSITION};
+    use crate::register;
+
+    #[tokio::test]
+    async fn serves_the_flushed_trace_file() {
+        register();
+        let dir = tempfile::tempdir().unwrap();
+        let mut trace_file = TraceFile::create(dir.path().join("trace.log")).unwrap();
+        let tt = TurboTasks::new(MemoryBackend::default());
+
+        let read = |cache_buster: u64| {
+            let trace_file = trace_file.clone();
+            tt.run_once(async move {
+                let content = TraceFileSource::new(trace_file)
+                    .get(
+                        String::new(),
+                        Value::new(ContentSourceData {
+                            cache_buster,
+                            ..Default::default()
+                        }),
+                    )
+                    .strongly_consistent()
+                    .await?;
+                let ContentSourceContent::HttpProxy(proxy) = &*content else {
+                    anyhow::bail!("expected a streamed response");
+                };
+                let proxy = proxy.await?;
+                let chunks: Vec<_> = proxy.body.read().try_collect().await?;
+                let trace = chunks
+                    .iter()
+                    .flat_map(|chunk| chunk.to_vec())
+                    .collect::<Vec<u8>>();
+                Ok((trace, proxy.headers.clone()))
+            })
+        };
+
+        // The write is still buffered until the request flushes it.
+        trace_file.write_all(b"before").unwrap();
+        let (trace, headers) = read(1).await.unwrap();
+        assert_eq!(trace, b"before");
+        assert!(headers.contains(&(
+            "content-disposition".to_string(),
+            CONTENT_DISPOSITION.to_string()
+        )));
+
+        // A trace larger than a chunk is served completely, and every
+        // request reads the file again.
+        let events = vec![b'x'; CHUNK_SIZE * 2 + 1];
+        trace_file.write_all(&events).unwrap();
+        let (trace, _) = read(2).await.unwrap();
+        assert_eq!(trace.len(), "before".len() + events.len());
+    }
+}
@@ -1,13 +1,15 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::{bail, Result};
-use mime::TEXT_HTML_UTF_8;
+use indexmap::IndexMap;
+use mime::{APPLICATION_JSON, TEXT_HTML_UTF_8};
+use serde::Serialize;
 use turbo_tasks::{get_invalidator, TurboTasks, TurboTasksBackendApi, Value, Vc};
 use turbopack_binding::{
     turbo::{
-        tasks_fs::File,
+        tasks_fs::{DiskFileSystem, File, FileSystem},
         tasks_memory::{
-            stats::{ReferenceType, Stats},
+            stats::{GroupTree, ReferenceType, Stats},
             viz, MemoryBackend,
         },
     },
@@ -25,11 +27,19 @@ use turbopack_binding::{
 pub struct TurboTasksSource {
     #[turbo_tasks(debug_ignore, trace_ignore)]
     turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+    /// The file systems the invalidate route reads from disk again.
+    file_systems: Vec<Vc<Box<dyn FileSystem>>>,
 }
 
 impl TurboTasksSource {
-    pub fn new(turbo_tasks: Arc<TurboTasks<MemoryBackend>>) -> Vc<Self> {
-        Self::cell(TurboTasksSource { turbo_tasks })
+    pub fn new(
+        turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+        file_systems: Vec<Vc<Box<dyn FileSystem>>>,
+    ) -> Vc<Self> {
+        Self::cell(TurboTasksSource {
+            turbo_tasks,
+            file_systems,
+        })
     }
 }
 
@@ -39,6 +49,78 @@ const GRAPH_PATH: &str = "graph";
 const CALL_GRAPH_PATH: &str = "call-graph";
 const TABLE_PATH: &str = "table";
 const RESET_PATH: &str = "reset";
+const CACHE_PATH: &str = "cache";
+/// Reads every file from disk again, as if all files had changed. This is a
+/// debugging aid for problems which seem to go away after a restart. Work
+/// depending on a file whose content didn't change is not repeated.
+const INVALIDATE_PATH: &str = "invalidate";
+
+/// The content source kinds reported by the cache route, each with a fragment
+/// of the task names which belong to it. The first match wins, so `app` is
+/// checked before `page` to not count `app_page_source` tasks as pages.
+const SOURCE_KINDS: &[(&str, &str)] = &[
+    ("static", "StaticAssetsContentSource"),
+    ("app", "app_source"),
+    ("page", "page_source"),
+    ("web", "web_entry_source"),
+];
+
+/// Cache statistics of the tasks belonging to one kind of content source.
+/// Executions beyond the first one of a task are caused by invalidations, so a
+/// high `reexecutions` count relative to `tasks` means the cache is thrashing.
+/// Executions are only recorded with full stats enabled.
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SourceCacheStats {
+    tasks: u64,
+    executions: u64,
+    reexecutions: u64,
+}
+
+impl SourceCacheStats {
+    fn add(&mut self, count: u64, executions: u64) {
+        self.tasks += count;
+        self.executions += executions;
+        self.reexecutions += executions.saturating_sub(count);
+    }
+}
+
+fn source_kind(task_name: &str) -> Option<&'static str> {
+    SOURCE_KINDS
+        .iter()
+        .find(|(_, fragment)| task_name.contains(fragment))
+        .map(|(kind, _)| *kind)
+}
+
+/// Attributes every task in the call graph to the content source kind of its
+/// closest ancestor which belongs to one.
+fn collect_source_cache_stats(
+    tree: &GroupTree,
+    parent_kind: Option<&'static str>,
+    result: &mut IndexMap<&'static str, SourceCacheStats>,
+) {
+    let mut kind = parent_kind;
+    if let Some((ty, stats)) = &tree.primary {
+        kind = kind.or_else(|| source_kind(&ty.to_string()));
+        if let Some(kind) = kind {
+            result.entry(kind).or_default().add(
+                stats.count as u64,
+                stats.executions.unwrap_or_default() as u64,
+            );
+        }
+    }
+    for (ty, stats) in &tree.task_types {
+        if let Some(kind) = kind.or_else(|| source_kind(&ty.to_string())) {
+            result.entry(kind).or_default().add(
+                stats.count as u64,
+                stats.executions.unwrap_or_default() as u64,
+            );
+        }
+    }
+    for child in &tree.children {
+        collect_source_cache_stats(child, kind, result);
+    }
+}
 
 #[turbo_tasks::value_impl]
 impl ContentSource for TurboTasksSource {
@@ -65,6 +147,16 @@ impl ContentSource for TurboTasksSource {
                 RouteType::Exact,
                 Vc::upcast(self),
             ),
+            RouteTree::new_route(
+                vec![BaseSegment::Static(CACHE_PATH.to_string())],
+                RouteType::Exact,
+                Vc::upcast(self),
+            ),
+            RouteTree::new_route(
+                vec![BaseSegment::Static(INVALIDATE_PATH.to_string())],
+                RouteType::Exact,
+                Vc::upcast(self),
+            ),
         ])
         .merge()
     }
@@ -96,6 +188,27 @@ impl GetContentSourceContent for TurboTasksSource {
                 invalidator.invalidate();
             }
         });
+        if path == CACHE_PATH {
+            let mut stats = Stats::new();
+            let b = tt.backend();
+            b.with_all_cached_tasks(|task| {
+                stats.add_id(b, task);
+            });
+            let tree = stats.treeify(ReferenceType::Child);
+            let mut result = SOURCE_KINDS
+                .iter()
+                .map(|(kind, _)| (*kind, SourceCacheStats::default()))
+                .collect();
+            collect_source_cache_stats(&tree, None, &mut result);
+            return Ok(ContentSourceContent::static_content(
+                AssetContent::file(
+                    File::from(serde_json::to_string_pretty(&result)?)
+                        .with_content_type(APPLICATION_JSON)
+                        .into(),
+                )
+                .versioned(),
+            ));
+        }
         let html = match path.as_str() {
             GRAPH_PATH => {
                 let mut stats = Stats::new();
@@ -146,6 +259,16 @@ impl GetContentSourceContent for TurboTasksSource {
                 });
                 "Done".to_string()
             }
+            INVALIDATE_PATH => {
+                for fs in &this.file_systems {
+                    if let Some(disk_fs) =
+                        Vc::try_resolve_downcast_type::<DiskFileSystem>(*fs).await?
+                    {
+                        disk_fs.await?.invalidate();
+                    }
+                }
+                "Done".to_string()
+            }
             _ => bail!("Unknown path: {}", path),
         };
         Ok(ContentSourceContent::static_content(
@@ -0,0 +1,202 @@
+use serde::Serialize;
+use turbo_tasks::InvalidationReasonsSet;
+
+/// A single entry of the invalidation reasons reported for an update, e.g.
+/// `{ reason: "files changed", count: 3 }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpdateReasonCount {
+    pub reason: String,
+    pub count: u32,
+}
+
+/// The invalidation reasons of an [turbo_tasks::UpdateInfo], grouped by kind.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UpdateReasons {
+    /// One entry per reason kind.
+    pub kinds: Vec<UpdateReasonCount>,
+    /// The paths of the changed files. Merged reasons only list a few example
+    /// paths, so this can miss some files when many changed at once.
+    pub changed_files: Vec<String>,
+}
+
+impl UpdateReasons {
+    pub fn new(reasons: &InvalidationReasonsSet) -> Self {
+        // The set only exposes its reasons through `Display`, which joins them
+        // with `, ` and prefixes merged kinds with their count
+        // (`3 files changed (a.js, b.js, c.js)`). This is the only place that
+        // depends on that format.
+        Self::parse(&reasons.to_string())
+    }
+
+    fn parse(formatted: &str) -> Self {
+        let mut reasons = UpdateReasons::default();
+        for entry in split_top_level(formatted) {
+            let entry = entry.strip_prefix("and ").unwrap_or(entry);
+            if let Some(path) = entry
+                .strip_prefix("file ")
+                .and_then(|entry| entry.strip_suffix(" changed"))
+            {
+                reasons.kinds.push(UpdateReasonCount {
+                    reason: "files changed".to_string(),
+                    count: 1,
+                });
+                reasons.changed_files.push(path.to_string());
+                continue;
+            }
+            // Split off the list of examples, e.g. `(a.js, b.js, c.js)`.
+            let (entry, examples) = match entry.find(" (") {
+                Some(index) if entry.ends_with(')') => {
+                    (&entry[..index], Some(&entry[index + 2..entry.len() - 1]))
+                }
+                _ => (entry, None),
+            };
+            let kind = match entry.split_once(' ') {
+                Some((count, reason)) if count.parse::<u32>().is_ok() => UpdateReasonCount {
+                    reason: reason.to_string(),
+                    count: count.parse().unwrap(),
+                },
+                _ => UpdateReasonCount {
+                    reason: entry.to_string(),
+                    count: 1,
+                },
+            };
+            if let (Some(examples), "files changed") = (examples, kind.reason.as_str()) {
+                reasons.changed_files.extend(
+                    examples
+                        .split(", ")
+                        .filter(|path| !path.is_empty() && *path != "...")
+                        .map(|path| path.to_string()),
+                );
+            }
+            reasons.kinds.push(kind);
+        }
+        reasons
+    }
+}
+
+/// Splits on `, ` while ignoring separators nested in parentheses.
+fn split_top_level(formatted: &str) -> Vec<&str> {
+    let mut entries = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in formatted.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 && formatted[index..].starts_with(", ") => {
+                entries.push(&formatted[start..index]);
+                start = index + 2;
+            }
+            _ => {}
+        }
+    }
+    if start < formatted.len() {
+        entries.push(&formatted[start..]);
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use turbo_tasks::{InvalidationReasonsSet, TurboTasks, Vc};
+    use turbopack_binding::turbo::{
+        tasks_fs::{DiskFileSystem, FileSystem},
+        tasks_memory::MemoryBackend,
+    };
+
+    use super::{UpdateReasonCount, UpdateReasons};
+    use crate::register;
+
+    fn count(reason: &str, count: u32) -> UpdateReasonCount {
+        UpdateReasonCount {
+            reason: reason.to_string(),
+            count,
+        }
+    }
+
+    fn changed(files: &[&str]) -> UpdateReasons {
+        UpdateReasons {
+            kinds: vec![count("files changed", files.len() as u32)],
+            changed_files: files.iter().map(|file| file.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn empty_reasons() {
+        assert_eq!(
+            UpdateReasons::new(&InvalidationReasonsSet::default()),
+            UpdateReasons::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_the_reasons_of_a_real_update() {
+        register();
+        let dir = tempfile::tempdir().unwrap();
+        let files = ["a.js", "b.js"];
+        for file in files {
+            std::fs::write(dir.path().join(file), "1").unwrap();
+        }
+        let root = dir.path().to_string_lossy().to_string();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        tt.spawn_root_task(move || {
+            let root = root.clone();
+            Box::pin(async move {
+                let disk_fs = DiskFileSystem::new("project".to_string(), root);
+                disk_fs.await?.start_watching_with_invalidation_reason()?;
+                let fs: Vc<Box<dyn FileSystem>> = Vc::upcast(disk_fs);
+                for file in files {
+                    fs.root().join(file.to_string()).read().await?;
+                }
+                Ok::<Vc<()>, _>(Default::default())
+            })
+        });
+        // The initial compilation.
+        tt.get_or_wait_aggregated_update_info(Duration::from_millis(100))
+            .await;
+
+        for file in files {
+            std::fs::write(dir.path().join(file), "2").unwrap();
+        }
+        // Waits long enough for both changes to be part of the same update.
+        let update = tt
+            .get_or_wait_aggregated_update_info(Duration::from_secs(1))
+            .await;
+        let mut reasons = UpdateReasons::new(&update.reasons);
+        reasons.changed_files.sort();
+        assert_eq!(reasons.kinds, vec![count("files changed", 2)]);
+        assert_eq!(reasons.changed_files.len(), 2);
+        for (changed, file) in reasons.changed_files.iter().zip(files) {
+            assert!(changed.ends_with(file), "{changed} is not {file}");
+        }
+    }
+
+    #[test]
+    fn groups_reasons_by_kind() {
+        let reasons = UpdateReasons::parse(
+            "3 files changed (src/a.js, src/b.js, ...), 2 writes to output directory (.next/a, \
+             .next/b), and initial compilation",
+        );
+        assert_eq!(
+            reasons,
+            UpdateReasons {
+                kinds: vec![
+                    count("files changed", 3),
+                    count("writes to output directory", 2),
+                    count("initial compilation", 1),
+                ],
+                changed_files: vec!["src/a.js".to_string(), "src/b.js".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn single_file_changes_are_a_kind() {
+        assert_eq!(
+            UpdateReasons::parse("file src/index.js changed"),
+            changed(&["src/index.js"])
+        );
+    }
+}
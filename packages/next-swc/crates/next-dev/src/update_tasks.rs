@@ -0,0 +1,125 @@
+use turbo_tasks::TurboTasks;
+use turbopack_binding::turbo::tasks_memory::MemoryBackend;
+
+/// Counts the tasks created by a [TurboTasks] instance from the size of the
+/// [MemoryBackend]'s task cache, which gains an entry for every task that
+/// hadn't run before. The default counts from the creation of the instance.
+#[derive(Default)]
+pub struct CreatedTaskCounter {
+    cached_tasks: usize,
+}
+
+impl CreatedTaskCounter {
+    pub fn new(turbo_tasks: &TurboTasks<MemoryBackend>) -> Self {
+        CreatedTaskCounter {
+            cached_tasks: cached_task_count(turbo_tasks),
+        }
+    }
+
+    /// The number of tasks created since the last call, or since the counter
+    /// was created.
+    pub fn created_since_last(&mut self, turbo_tasks: &TurboTasks<MemoryBackend>) -> usize {
+        let cached_tasks = cached_task_count(turbo_tasks);
+        // Tasks dropped from the cache by garbage collection shrink it, which
+        // undercounts the tasks created in the same update.
+        let created = cached_tasks.saturating_sub(self.cached_tasks);
+        self.cached_tasks = cached_tasks;
+        created
+    }
+}
+
+/// The number of entries in the backend's task cache. The [MemoryBackend]
+/// keeps no counter of its own, so the cache is walked once per call.
+fn cached_task_count(turbo_tasks: &TurboTasks<MemoryBackend>) -> usize {
+    let mut count = 0;
+    turbo_tasks.backend().with_all_cached_tasks(|_| count += 1);
+    count
+}
+
+/// Splits the tasks executed during an update into cache misses, i.e. tasks
+/// which had never run before, and tasks whose cached result was invalidated.
+/// Many new tasks point to a cold cache, many invalidated ones to a wide
+/// invalidation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateTaskBreakdown {
+    pub new_tasks: usize,
+    pub invalidated_tasks: usize,
+}
+
+impl UpdateTaskBreakdown {
+    /// `created_tasks` is the number of tasks created during the update, see
+    /// [CreatedTaskCounter].
+    pub fn new(executed_tasks: usize, created_tasks: usize) -> Self {
+        // Tasks may be created without being executed yet.
+        let new_tasks = created_tasks.min(executed_tasks);
+        UpdateTaskBreakdown {
+            new_tasks,
+            invalidated_tasks: executed_tasks - new_tasks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks::TurboTasks;
+    use turbopack_binding::turbo::{tasks_fs::DiskFileSystem, tasks_memory::MemoryBackend};
+
+    use super::{CreatedTaskCounter, UpdateTaskBreakdown};
+    use crate::register;
+
+    #[test]
+    fn cold_cache() {
+        assert_eq!(
+            UpdateTaskBreakdown::new(100, 100),
+            UpdateTaskBreakdown {
+                new_tasks: 100,
+                invalidated_tasks: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn invalidation() {
+        assert_eq!(
+            UpdateTaskBreakdown::new(30, 10),
+            UpdateTaskBreakdown {
+                new_tasks: 10,
+                invalidated_tasks: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn tasks_created_without_executing() {
+        assert_eq!(
+            UpdateTaskBreakdown::new(5, 20),
+            UpdateTaskBreakdown {
+                new_tasks: 5,
+                invalidated_tasks: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_created_tasks() {
+        register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let mut counter = CreatedTaskCounter::new(&tt);
+        assert_eq!(counter.created_since_last(&tt), 0);
+
+        let create_file_systems = || {
+            tt.run_once(async move {
+                for name in ["a", "b", "c"] {
+                    DiskFileSystem::new(name.to_string(), "/".to_string()).await?;
+                }
+                Ok(())
+            })
+        };
+        create_file_systems().await.unwrap();
+        assert!(counter.created_since_last(&tt) >= 3);
+
+        // The same calls again are cache hits.
+        create_file_systems().await.unwrap();
+        assert_eq!(counter.created_since_last(&tt), 0);
+    }
+}
@@ -0,0 +1,153 @@
+use std::{sync::Arc, time::Instant};
+
+use anyhow::{Context, Result};
+use futures::{stream, StreamExt};
+use hyper::{HeaderMap, Uri};
+use owo_colors::OwoColorize;
+use turbo_tasks::{util::FormatDuration, TransientInstance, TurboTasks, Vc};
+use turbopack_binding::{
+    turbo::{tasks_fs::FileSystemPath, tasks_memory::MemoryBackend},
+    turbopack::{
+        core::issue::{handle_issues, Issue, IssueExt, IssueReporter, IssueSeverity},
+        dev_server::source::{
+            request::SourceRequest,
+            resolve::{resolve_source_request, ResolveSourceRequestResult},
+            ContentSource,
+        },
+    },
+};
+
+/// How many routes are resolved at once. Routes share most of their
+/// compilation, so a few concurrent routes keep the compiler busy without
+/// queueing every route at once.
+const CONCURRENCY: usize = 4;
+
+/// Resolves each of the given pathnames from the dev server's content source,
+/// like a `GET` request would, so they are compiled before the first
+/// navigation to them. A route which can't be resolved is reported as a
+/// warning through the dev server's issue reporter.
+pub(crate) async fn warm_routes(
+    turbo_tasks: Arc<TurboTasks<MemoryBackend>>,
+    source: impl Fn() -> Vc<Box<dyn ContentSource>> + Clone + Send + Sync + 'static,
+    project_path: impl Fn() -> Vc<FileSystemPath> + Clone + Send + Sync + 'static,
+    issue_reporter: Arc<dyn Fn() -> Vc<Box<dyn IssueReporter>> + Send + Sync>,
+    routes: Vec<String>,
+) {
+    let start = Instant::now();
+    let results = stream::iter(routes)
+        .map(|route| {
+            let pathname = if route.starts_with('/') {
+                route
+            } else {
+                format!("/{route}")
+            };
+            let source = source.clone();
+            let project_path = project_path.clone();
+            let issue_reporter = issue_reporter.clone();
+            turbo_tasks.run_once(async move {
+                let warmed = warm_route(source(), project_path(), pathname.clone());
+                handle_issues(
+                    warmed,
+                    issue_reporter(),
+                    IssueSeverity::Fatal.cell(),
+                    Some(&pathname),
+                    Some("warm route"),
+                )
+                .await?;
+                Ok(*warmed.await?)
+            })
+        })
+        .buffer_unordered(CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    let warmed = results
+        .into_iter()
+        .filter(|result| matches!(result, Ok(true)))
+        .count();
+    println!(
+        "{event_type} - warmed {warmed} routes {elapsed}",
+        event_type = "event".purple(),
+        elapsed = FormatDuration(start.elapsed()),
+    );
+}
+
+/// Resolves `pathname` from `source`, which compiles it. Emits a
+/// [WarmupIssue] and returns false when the route couldn't be resolved.
+#[turbo_tasks::function]
+async fn warm_route(
+    source: Vc<Box<dyn ContentSource>>,
+    project_path: Vc<FileSystemPath>,
+    pathname: String,
+) -> Result<Vc<bool>> {
+    let reason = match resolve(source, &pathname).await {
+        Ok(true) => None,
+        Ok(false) => Some("No route matches this pathname.".to_string()),
+        Err(err) => Some(format!("{err:#}")),
+    };
+    let warmed = reason.is_none();
+    if let Some(reason) = reason {
+        WarmupIssue {
+            path: project_path,
+            pathname,
+            reason,
+        }
+        .cell()
+        .emit();
+    }
+    Ok(Vc::cell(warmed))
+}
+
+/// Whether `source` has content for `pathname`.
+async fn resolve(source: Vc<Box<dyn ContentSource>>, pathname: &str) -> Result<bool> {
+    let uri: Uri = pathname
+        .parse()
+        .with_context(|| format!("{pathname} is not a valid pathname"))?;
+    let request = SourceRequest {
+        method: "GET".to_string(),
+        uri,
+        headers: HeaderMap::new(),
+    };
+    let result = resolve_source_request(source, TransientInstance::new(request)).await?;
+    Ok(!matches!(&*result, ResolveSourceRequestResult::NotFound))
+}
+
+/// Reported when a route passed to `--warm-routes` couldn't be compiled
+/// ahead of its first request.
+#[turbo_tasks::value(shared)]
+struct WarmupIssue {
+    path: Vc<FileSystemPath>,
+    pathname: String,
+    reason: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for WarmupIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> Vc<String> {
+        Vc::cell("warmup".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<String> {
+        Vc::cell(format!("Route {} could not be warmed", self.pathname))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<String> {
+        Vc::cell(format!(
+            "{}\n\nThe route is compiled on its first request instead.",
+            self.reason
+        ))
+    }
+}
@@ -0,0 +1,4 @@
+// Provides `experimental.turbo.freeVars` entries of the form
+// `{ global: "name" }`: the properties of the global object are the exports of
+// this module.
+module.exports = globalThis
@@ -365,7 +365,7 @@ function logLoadFailure(attempts: any, triedWasm = false) {
     })
 }
 
-interface ProjectOptions {
+export interface ProjectOptions {
   /**
    * A root path from which all files must be nested under. Trying to access
    * a file outside this root will fail. Think of this as a chroot.
@@ -398,22 +398,102 @@ interface ProjectOptions {
    */
   env: Record<string, string>
 
+  /**
+   * Dotenv files to load, relative to the project path. Variables from `env`
+   * take precedence, then the files in the given order.
+   */
+  envFiles?: string[]
+
   /**
    * Whether to watch the filesystem for file changes.
    */
   watch: boolean
 
   /**
-   * The address of the dev server.
+   * The address of the dev server, e.g. `127.0.0.1:3000`. Port `0` is
+   * replaced by a free port, which `Project.serverAddr()` returns.
    */
   serverAddr: string
+
+  /**
+   * Whether the dev server serves HTTPS, e.g. with
+   * `next dev --experimental-https`. Defaults to `false`.
+   */
+  https?: boolean
+
+  /**
+   * Whether to minify the server chunks. Defaults to `false`. Client chunks
+   * are never minified, since their chunking context has no minification.
+   */
+  minify?: boolean
+
+  /**
+   * Drops issues less severe than this, e.g. `'warning'`, before they are
+   * passed to JS. Defaults to keeping all issues.
+   */
+  minIssueSeverity?: string
+
+  /**
+   * Only passes diagnostics of these categories to JS. Defaults to keeping all
+   * diagnostics.
+   */
+  diagnosticCategories?: string[]
+
+  /**
+   * Passes issues of these categories to JS as errors, even if they are only
+   * warnings.
+   */
+  errorOn?: string[]
+
+  /**
+   * How much of each issue is passed to JS: `'full'` (the default), or
+   * `'minimal'` to only pass the severity, file path and title, which is
+   * cheaper for noisy projects.
+   */
+  issueDetail?: 'minimal' | 'full'
+
+  /**
+   * How many endpoints are built at the same time, when writing them to disk
+   * or warming them up. Defaults to the number of CPUs. Only read when the
+   * project is created.
+   */
+  maxConcurrentBuilds?: number
+
+  /**
+   * Renders every page and route with this runtime, instead of the one
+   * selected by its `runtime` export.
+   */
+  runtime?: 'nodejs' | 'edge'
+
+  /**
+   * Whether the client chunks support hot module replacement. Defaults to
+   * enabling it in development.
+   */
+  hmr?: boolean
 }
 
+/**
+ * There is no option for the number of worker threads. Turbo Engine runs on
+ * the tokio runtime that the native bindings create on their first async
+ * call, before any project options are known. That runtime is shared by all
+ * projects in the process. To size it, set the `TOKIO_WORKER_THREADS`
+ * environment variable before the bindings are loaded. The `next-dev` binary
+ * has a `--threads` option instead.
+ */
 interface TurboEngineOptions {
   /**
    * An upper bound of memory that turbopack will attempt to stay under.
    */
   memoryLimit?: number
+
+  /**
+   * Fails creating the project and building endpoints when more than this
+   * many tasks are in progress at the same time, which usually means that a
+   * misconfigured glob or import pattern matches far too many files.
+   * Only the call fails, the tasks already started keep running. The count
+   * covers all work of the project, not just the failing call.
+   */
+  maxTasks?: number
 }
 
 export interface Issue {
@@ -442,12 +522,26 @@ export interface Diagnostics {
 }
 
 export type TurbopackResult<T = {}> = T & {
+  /**
+   * The number of errors and warnings in `issues`. Bugs and fatal issues count
+   * as errors.
+   */
+  issueCounts: { errors: number; warnings: number }
   issues: Issue[]
   diagnostics: Diagnostics[]
 }
 
+export interface MiddlewareMatcher {
+  /** The regular expression request paths are matched against. */
+  regexp: string
+  /** The matcher as written in the middleware config. */
+  originalSource: string
+}
+
 export interface Middleware {
   endpoint: Endpoint
+  /** The paths the middleware runs for. */
+  matchers: MiddlewareMatcher[]
 }
 
 export interface Entrypoints {
@@ -456,10 +550,27 @@ export interface Entrypoints {
   pagesDocumentEndpoint: Endpoint
   pagesAppEndpoint: Endpoint
   pagesErrorEndpoint: Endpoint
+  /**
+   * Whether the project has an `app` directory, i.e. uses the App Router.
+   */
+  hasAppDir: boolean
+  /**
+   * The `app` directory relative to the project directory, e.g. `src/app`.
+   */
+  appDir?: string
 }
 
 export interface Update {
   update: unknown
+  /**
+   * Increases with each update of the same identifier. An update with a
+   * lower sequence than one already applied was delivered out of order.
+   */
+  sequence: number
+  /**
+   * When the update was computed, in milliseconds since the Unix epoch.
+   */
+  timestamp: number
 }
 
 export interface HmrIdentifiers {
@@ -469,6 +580,97 @@ export interface HmrIdentifiers {
 export interface UpdateInfo {
   duration: number
   tasks: number
+  /**
+   * The invalidation reasons of the update, grouped by kind.
+   */
+  reasons: { reason: string; count: number }[]
+  /**
+   * The number of executed tasks which had never run before.
+   */
+  newTasks: number
+  /**
+   * The number of executed tasks whose cached result was invalidated.
+   */
+  invalidatedTasks: number
+}
+
+export interface StartupSummary {
+  pagesRoutes: number
+  appRoutes: number
+  middleware: boolean
+  /**
+   * The time since the project was created, in milliseconds.
+   */
+  duration: number
+}
+
+export interface ClientGraph {
+  /**
+   * The paths of all client output assets of the route.
+   */
+  nodes: string[]
+  /**
+   * The references between the output assets.
+   */
+  edges: { from: string; to: string }[]
+  /**
+   * The paths of the chunks the route loads directly.
+   */
+  chunks: string[]
+}
+
+export interface ModuleGraph {
+  nodes: {
+    /**
+     * The kind of the node, e.g. a module or a chunk.
+     */
+    ty: string
+    title: string
+    /**
+     * The titles of the route's chunks that contain the node.
+     */
+    chunks: string[]
+  }[]
+  /**
+   * References between nodes, as indices into `nodes`.
+   */
+  edges: { from: number; to: number; label: string }[]
+}
+
+export interface WarmupResult {
+  pathname: string
+  /**
+   * Why the route could not be built, if it failed.
+   */
+  error?: string
+  issues: Issue[]
+}
+
+export interface TransformedModule {
+  code: string
+  /**
+   * The source map of the code, serialized to JSON.
+   */
+  sourceMap?: string
+}
+
+export interface UnsupportedModule {
+  /**
+   * The package, followed by the subpath if only that is unsupported.
+   */
+  module: string
+  /**
+   * The directories the module is requested from.
+   */
+  importers: string[]
+}
+
+export interface UnusedDependency {
+  name: string
+  /**
+   * The version range declared in `package.json`.
+   */
+  version: string
 }
 
 export enum ServerClientChangeType {
@@ -482,12 +684,67 @@ export interface ServerClientChange {
 
 export interface Project {
   update(options: ProjectOptions): Promise<void>
-  entrypointsSubscribe(): AsyncIterableIterator<TurbopackResult<Entrypoints>>
+  entrypointsSubscribe(
+    options?: EntrypointsOptions
+  ): AsyncIterableIterator<TurbopackResult<Entrypoints>>
   hmrEvents(identifier: string): AsyncIterableIterator<TurbopackResult<Update>>
   hmrIdentifiersSubscribe(): AsyncIterableIterator<
     TurbopackResult<HmrIdentifiers>
   >
-  updateInfoSubscribe(): AsyncIterableIterator<TurbopackResult<UpdateInfo>>
+  updateInfoSubscribe(
+    intervalMs?: number
+  ): AsyncIterableIterator<TurbopackResult<UpdateInfo>>
+  startupSummary(): Promise<StartupSummary>
+  clientGraph(pathname: string): Promise<TurbopackResult<ClientGraph>>
+  transformModule(path: string): Promise<TurbopackResult<TransformedModule>>
+  routeModuleGraph(pathname: string): Promise<TurbopackResult<ModuleGraph>>
+  /**
+   * Builds all routes and lists the modules reported as unsupported, e.g.
+   * Node.js-only builtins imported by client code when
+   * `experimental.turbo.reportNodeBuiltins` is set.
+   */
+  unsupportedModules(): Promise<TurbopackResult<UnsupportedModule[]>>
+  /**
+   * Builds all routes and lists the `dependencies` of `package.json` that
+   * none of them import. This is expensive, so it only runs when called.
+   */
+  unusedDependencies(): Promise<TurbopackResult<UnusedDependency[]>>
+  warmup(pathnames: string[]): Promise<WarmupResult[]>
+  /**
+   * The `serverAddr` option with port `0` replaced by a free port. This is
+   * the address the compiled app sees as its `PORT`, so a host that passed
+   * port `0` should listen on it.
+   */
+  serverAddr(): string
+  /**
+   * The URL to open the app at, e.g. `http://localhost:3000`, built from the
+   * address the dev server listens on and the `https` option.
+   */
+  serverUrl(): string
+  /**
+   * Switches between recording essential and full task stats. Full stats are
+   * costly, so enable them only while capturing a slow rebuild.
+   */
+  setStatsType(full: boolean): void
+  /**
+   * Makes every file of the project be read from disk again, as if all files
+   * had changed. Subscriptions receive fresh results afterwards. This is a
+   * debugging aid for problems which seem to go away after a restart.
+   */
+  invalidateAll(): Promise<void>
+}
+
+export interface EntrypointsOptions {
+  /**
+   * Compute `hasClientComponents` for app routes. This walks the module graph
+   * of every app route, which slows down the subscription.
+   */
+  clientComponents?: boolean
+  /**
+   * Compute the `runtime` of pages and routes. This parses the config of every
+   * route.
+   */
+  runtime?: boolean
 }
 
 export type Route =
@@ -498,24 +755,35 @@ export type Route =
       type: 'app-page'
       htmlEndpoint: Endpoint
       rscEndpoint: Endpoint
+      hasClientComponents?: boolean
+      runtime?: 'nodejs' | 'edge'
     }
   | {
       type: 'app-route'
       endpoint: Endpoint
+      hasClientComponents?: boolean
+      runtime?: 'nodejs' | 'edge'
     }
   | {
       type: 'page'
       htmlEndpoint: Endpoint
       dataEndpoint: Endpoint
+      runtime?: 'nodejs' | 'edge'
     }
   | {
       type: 'page-api'
       endpoint: Endpoint
+      runtime?: 'nodejs' | 'edge'
     }
 
 export interface Endpoint {
   /** Write files for the endpoint to disk. */
   writeToDisk(): Promise<TurbopackResult<WrittenEndpoint>>
+  /**
+   * The assets emitted for the endpoint, as of the latest build. Server asset
+   * paths are relative to the project, client ones to the client root.
+   */
+  outputAssets(): Promise<TurbopackResult<EndpointOutputAsset[]>>
   /**
    * Listen to changes to the endpoint.
    * After changed() has been awaited it will listen to changes.
@@ -524,6 +792,12 @@ export interface Endpoint {
   changed(): Promise<AsyncIterableIterator<TurbopackResult<ServerClientChange>>>
 }
 
+export interface EndpointOutputAsset {
+  path: string
+  client: boolean
+  kind: 'script' | 'stylesheet' | 'source-map' | 'asset'
+}
+
 interface EndpointConfig {
   dynamic?: 'auto' | 'force-dynamic' | 'error' | 'force-static'
   dynamicParams?: boolean
@@ -710,7 +984,7 @@ function bindingToApi(binding: any, _wasm: boolean) {
       )
     }
 
-    entrypointsSubscribe() {
+    entrypointsSubscribe(options?: EntrypointsOptions) {
       type NapiEndpoint = { __napiType: 'Endpoint' }
 
       type NapiEntrypoints = {
@@ -719,16 +993,18 @@ function bindingToApi(binding: any, _wasm: boolean) {
         pagesDocumentEndpoint: NapiEndpoint
         pagesAppEndpoint: NapiEndpoint
         pagesErrorEndpoint: NapiEndpoint
+        hasAppDir: boolean
+        appDir?: string
       }
 
       type NapiMiddleware = {
         endpoint: NapiEndpoint
-        runtime: 'nodejs' | 'edge'
-        matcher?: string[]
+        matchers: MiddlewareMatcher[]
       }
 
       type NapiRoute = {
         pathname: string
+        runtime?: 'nodejs' | 'edge'
       } & (
         | {
             type: 'page'
@@ -743,10 +1019,12 @@ function bindingToApi(binding: any, _wasm: boolean) {
             type: 'app-page'
             htmlEndpoint: NapiEndpoint
             rscEndpoint: NapiEndpoint
+            hasClientComponents?: boolean
           }
         | {
             type: 'app-route'
             endpoint: NapiEndpoint
+            hasClientComponents?: boolean
           }
         | {
             type: 'conflict'
@@ -756,7 +1034,12 @@ function bindingToApi(binding: any, _wasm: boolean) {
       const subscription = subscribe<TurbopackResult<NapiEntrypoints>>(
         false,
         async (callback) =>
-          binding.projectEntrypointsSubscribe(this._nativeProject, callback)
+          binding.projectEntrypointsSubscribe(
+            this._nativeProject,
+            callback,
+            options?.clientComponents,
+            options?.runtime
+          )
       )
       return (async function* () {
         for await (const entrypoints of subscription) {
@@ -770,12 +1053,14 @@ function bindingToApi(binding: any, _wasm: boolean) {
                   type: 'page',
                   htmlEndpoint: new EndpointImpl(nativeRoute.htmlEndpoint),
                   dataEndpoint: new EndpointImpl(nativeRoute.dataEndpoint),
+                  runtime: nativeRoute.runtime,
                 }
                 break
               case 'page-api':
                 route = {
                   type: 'page-api',
                   endpoint: new EndpointImpl(nativeRoute.endpoint),
+                  runtime: nativeRoute.runtime,
                 }
                 break
               case 'app-page':
@@ -783,12 +1068,16 @@ function bindingToApi(binding: any, _wasm: boolean) {
                   type: 'app-page',
                   htmlEndpoint: new EndpointImpl(nativeRoute.htmlEndpoint),
                   rscEndpoint: new EndpointImpl(nativeRoute.rscEndpoint),
+                  hasClientComponents: nativeRoute.hasClientComponents,
+                  runtime: nativeRoute.runtime,
                 }
                 break
               case 'app-route':
                 route = {
                   type: 'app-route',
                   endpoint: new EndpointImpl(nativeRoute.endpoint),
+                  hasClientComponents: nativeRoute.hasClientComponents,
+                  runtime: nativeRoute.runtime,
                 }
                 break
               case 'conflict':
@@ -805,10 +1094,11 @@ function bindingToApi(binding: any, _wasm: boolean) {
             }
             routes.set(pathname, route)
           }
-          const napiMiddlewareToMiddleware = (middleware: NapiMiddleware) => ({
+          const napiMiddlewareToMiddleware = (
+            middleware: NapiMiddleware
+          ): Middleware => ({
             endpoint: new EndpointImpl(middleware.endpoint),
-            runtime: middleware.runtime,
-            matcher: middleware.matcher,
+            matchers: middleware.matchers,
           })
           const middleware = entrypoints.middleware
             ? napiMiddlewareToMiddleware(entrypoints.middleware)
@@ -823,6 +1113,8 @@ function bindingToApi(binding: any, _wasm: boolean) {
             pagesErrorEndpoint: new EndpointImpl(
               entrypoints.pagesErrorEndpoint
             ),
+            hasAppDir: entrypoints.hasAppDir,
+            appDir: entrypoints.appDir,
             issues: entrypoints.issues,
             diagnostics: entrypoints.diagnostics,
           }
@@ -848,14 +1140,88 @@ function bindingToApi(binding: any, _wasm: boolean) {
       return subscription
     }
 
-    updateInfoSubscribe() {
+    updateInfoSubscribe(intervalMs?: number) {
       const subscription = subscribe<TurbopackResult<UpdateInfo>>(
         true,
         async (callback) =>
-          binding.projectUpdateInfoSubscribe(this._nativeProject, callback)
+          binding.projectUpdateInfoSubscribe(
+            this._nativeProject,
+            callback,
+            intervalMs
+          )
       )
       return subscription
     }
+
+    async startupSummary(): Promise<StartupSummary> {
+      return await withErrorCause(() =>
+        binding.projectStartupSummary(this._nativeProject)
+      )
+    }
+
+    async clientGraph(
+      pathname: string
+    ): Promise<TurbopackResult<ClientGraph>> {
+      return await withErrorCause(() =>
+        binding.projectClientGraph(this._nativeProject, pathname)
+      )
+    }
+
+    async transformModule(
+      path: string
+    ): Promise<TurbopackResult<TransformedModule>> {
+      return await withErrorCause(() =>
+        binding.projectTransformModule(this._nativeProject, path)
+      )
+    }
+
+    async routeModuleGraph(
+      pathname: string
+    ): Promise<TurbopackResult<ModuleGraph>> {
+      return await withErrorCause(() =>
+        binding.projectRouteModuleGraph(this._nativeProject, pathname)
+      )
+    }
+
+    async unsupportedModules(): Promise<
+      TurbopackResult<UnsupportedModule[]>
+    > {
+      return await withErrorCause(() =>
+        binding.projectUnsupportedModules(this._nativeProject)
+      )
+    }
+
+    async unusedDependencies(): Promise<
+      TurbopackResult<UnusedDependency[]>
+    > {
+      return await withErrorCause(() =>
+        binding.projectUnusedDependencies(this._nativeProject)
+      )
+    }
+
+    async warmup(pathnames: string[]): Promise<WarmupResult[]> {
+      return await withErrorCause(() =>
+        binding.projectWarmup(this._nativeProject, pathnames)
+      )
+    }
+
+    serverAddr(): string {
+      return binding.projectServerAddr(this._nativeProject)
+    }
+
+    serverUrl(): string {
+      return binding.projectServerUrl(this._nativeProject)
+    }
+
+    setStatsType(full: boolean): void {
+      binding.projectSetStatsType(this._nativeProject, full)
+    }
+
+    async invalidateAll(): Promise<void> {
+      await withErrorCause(() =>
+        binding.projectInvalidateAll(this._nativeProject)
+      )
+    }
   }
 
   class EndpointImpl implements Endpoint {
@@ -871,6 +1237,12 @@ function bindingToApi(binding: any, _wasm: boolean) {
       )
     }
 
+    async outputAssets(): Promise<TurbopackResult<EndpointOutputAsset[]>> {
+      return await withErrorCause(() =>
+        binding.endpointOutputAssets(this._nativeEndpoint)
+      )
+    }
+
     async changed(): Promise<
       AsyncIterableIterator<TurbopackResult<ServerClientChange>>
     > {
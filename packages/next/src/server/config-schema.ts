@@ -462,6 +462,51 @@ const configSchema = {
             resolveAlias: {
               type: 'object',
             },
+            suppressBabelWarning: {
+              type: 'boolean',
+            },
+            warnOnNodePolyfills: {
+              type: 'boolean',
+            },
+            allowGlobalCssAnywhere: {
+              type: 'boolean',
+            },
+            reportNodeBuiltins: {
+              type: 'boolean',
+            },
+            sourceMaps: {
+              enum: ['none', 'cheap', 'full'],
+              type: 'string',
+            },
+            freeVars: {
+              type: 'object',
+            },
+            runtimeConfig: {
+              enum: ['request', 'inline'],
+              type: 'string',
+            },
+            runtimeEntries: {
+              items: {
+                additionalProperties: false,
+                properties: {
+                  request: {
+                    minLength: 1,
+                    type: 'string',
+                  },
+                  position: {
+                    enum: ['before-refresh', 'after-refresh', 'last'],
+                    type: 'string',
+                  },
+                },
+                required: ['request'],
+                type: 'object',
+              },
+              type: 'array',
+            },
+            nodeEnv: {
+              minLength: 1,
+              type: 'string',
+            },
           },
         },
         optimizePackageImports: {
@@ -766,6 +811,12 @@ const configSchema = {
     sassOptions: {
       type: 'object',
     },
+    lessOptions: {
+      type: 'object',
+    },
+    stylusOptions: {
+      type: 'object',
+    },
     serverRuntimeConfig: {
       type: 'object',
     },
@@ -124,6 +124,87 @@ export interface ExperimentalTurboOptions {
    * @see [Turbopack Loaders](https://nextjs.org/docs/app/api-reference/next-config-js/turbo#webpack-loaders)
    */
   rules?: Record<string, TurboRule>
+
+  /**
+   * (`next --turbo` only) Don't warn when a babel config is found, for projects
+   * that use babel intentionally.
+   */
+  suppressBabelWarning?: boolean
+
+  /**
+   * (`next --turbo` only) Warn for each client module that references the
+   * `process` or `Buffer` globals, which add Node.js polyfills to the bundle.
+   */
+  warnOnNodePolyfills?: boolean
+
+  /**
+   * (`next --turbo` only) Allow global CSS to be imported from any module in
+   * the pages directory, not only from the custom `App`.
+   */
+  allowGlobalCssAnywhere?: boolean
+
+  /**
+   * (`next --turbo` only) Report imports of Node.js builtins without a browser
+   * polyfill, like `fs`, from client code as unsupported modules. Imports made
+   * by packages in `node_modules` are not reported.
+   */
+  reportNodeBuiltins?: boolean
+
+  /**
+   * (`next --turbo` only) Whether client chunks have source maps. `'none'`
+   * skips generating them, which makes compilation faster, but the error
+   * overlay can't show original stack frames of client errors. `'cheap'`
+   * serves line-only maps without the original sources, which the browser
+   * loads faster. Defaults to `'full'`, with columns and the original sources.
+   */
+  sourceMaps?: 'none' | 'cheap' | 'full'
+
+  /**
+   * (`next --turbo` only) Replaces free variables in client code with a
+   * module, like webpack's `ProvidePlugin`. A string provides the module
+   * namespace, `[module, export]` provides an export of the module, and
+   * `{ global: 'name' }` provides a property of the global object, e.g.
+   * `{ global: { global: 'globalThis' } }`. Variables provided by Next.js,
+   * like `process`, can't be replaced.
+   */
+  freeVars?: Record<string, string | [string, string] | { global: string }>
+
+  /**
+   * (`next --turbo` only) How `publicRuntimeConfig` and `serverRuntimeConfig`
+   * reach pages. With `'request'` (the default), they are sent along with each
+   * render and can change without a rebuild. With `'inline'`, they are
+   * compiled into the server bundles. `env` and `NEXT_PUBLIC_` variables are
+   * always inlined, so changing them requires a rebuild.
+   */
+  runtimeConfig?: 'request' | 'inline'
+
+  /**
+   * (`next --turbo` only) Modules the client evaluates on startup, e.g. to
+   * initialize error monitoring before the app. Requests are resolved from the
+   * project directory. `position` places the module before React Refresh,
+   * after it but before the Next.js bootstrap, or after everything else (the
+   * default).
+   *
+   * This can also preload modules that are only loaded conditionally, e.g.
+   * polyfills, so they are always part of the client graph. Runtime entries
+   * are included in the bootstrap chunks of every page and evaluated for
+   * their side effects, so each one adds to the JavaScript every page loads,
+   * even pages that never use it.
+   */
+  runtimeEntries?: Array<{
+    request: string
+    position?: 'before-refresh' | 'after-refresh' | 'last'
+  }>
+
+  /**
+   * (`next --turbo` only) The value `process.env.NODE_ENV` is replaced with in
+   * compiled code. It defaults to `'development'` for `next dev` and
+   * `'production'` for `next build`. Only the replacement changes: package
+   * `exports` conditions and the JSX runtime still follow the command, and
+   * libraries like React pick their development or production build from the
+   * replaced value.
+   */
+  nodeEnv?: string
 }
 
 export interface WebpackConfigContext {
@@ -526,6 +607,18 @@ export interface NextConfig extends Record<string, any> {
   /** @see [Customizing sass options](https://nextjs.org/docs/basic-features/built-in-css-support#customizing-sass-options) */
   sassOptions?: { [key: string]: any }
 
+  /**
+   * Options passed to `less-loader` (e.g. `modifyVars`, `javascriptEnabled`).
+   * Only used by Turbopack, which enables `.less` support when this is set.
+   */
+  lessOptions?: { [key: string]: any }
+
+  /**
+   * Options passed to the stylus compiler (e.g. `use` plugins, `import` paths).
+   * Only used by Turbopack, which enables `.styl` support when this is set.
+   */
+  stylusOptions?: { [key: string]: any }
+
   /**
    * Enable browser source map generation during the production build
    *
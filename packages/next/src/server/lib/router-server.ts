@@ -130,6 +130,7 @@ export async function initialize(opts: {
       isCustomServer: opts.customServer,
       turbo: !!process.env.TURBOPACK,
       port: opts.port,
+      server: opts.server,
     })
     devInstances[opts.dir] = devInstance
     ;(global as any)._nextDevHandlers = {
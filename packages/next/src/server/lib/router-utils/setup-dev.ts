@@ -27,6 +27,7 @@ import HotReloader, {
 import { setGlobal } from '../../../trace/shared'
 import { Telemetry } from '../../../telemetry/storage'
 import { IncomingMessage, ServerResponse } from 'http'
+import { Server as HttpsServer } from 'https'
 import loadJsConfig from '../../../build/load-jsconfig'
 import { createValidFileMatcher } from '../find-page-file'
 import { eventCliSession } from '../../../telemetry/events'
@@ -125,6 +126,22 @@ type SetupOpts = {
   >
   nextConfig: NextConfigComplete
   port: number
+  server?: import('http').Server
+}
+
+/**
+ * The address the dev server listens on, as the `IP:PORT` Turbopack expects.
+ * Falls back to the loopback address when the server isn't known, e.g. for a
+ * custom server.
+ */
+function getServerAddr(opts: SetupOpts): string {
+  const address = opts.server?.address()
+  if (address && typeof address === 'object') {
+    return address.address.includes(':')
+      ? `[${address.address}]:${address.port}`
+      : `${address.address}:${address.port}`
+  }
+  return `127.0.0.1:${opts.port}`
 }
 
 async function verifyTypeScript(opts: SetupOpts) {
@@ -209,7 +226,9 @@ async function startWatcher(opts: SetupOpts) {
       jsConfig,
       watch: true,
       env: process.env as Record<string, string>,
-      serverAddr: `127.0.0.1:${opts.port}`,
+      serverAddr: getServerAddr(opts),
+      // `next dev --experimental-https` passes an HTTPS server.
+      https: opts.server instanceof HttpsServer,
     })
     const iter = project.entrypointsSubscribe()
     const curEntries: Map<string, Route> = new Map()
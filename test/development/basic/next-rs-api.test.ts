@@ -7,12 +7,40 @@ import {
   Issue,
   loadBindings,
   Project,
+  ProjectOptions,
   TurbopackResult,
   UpdateInfo,
 } from 'next/src/build/swc'
 import loadConfig from 'next/src/server/config'
+import fs from 'fs/promises'
 import path from 'path'
 
+/**
+ * Creates a project for the test app of `next`, with `options` overriding the
+ * defaults.
+ */
+async function createTestProject(
+  next: NextInstance,
+  options: Partial<ProjectOptions> = {}
+): Promise<Project> {
+  const nextConfig = await loadConfig(PHASE_DEVELOPMENT_SERVER, next.testDir)
+  const bindings = await loadBindings()
+  return bindings.turbo.createProject({
+    env: {},
+    jsConfig: {
+      compilerOptions: {},
+    },
+    nextConfig: nextConfig,
+    projectPath: next.testDir,
+    rootPath: process.env.NEXT_SKIP_ISOLATE
+      ? path.resolve(__dirname, '../../..')
+      : next.testDir,
+    watch: false,
+    serverAddr: `127.0.0.1:3000`,
+    ...options,
+  })
+}
+
 function normalizePath(path: string) {
   return path
     .replace(/\[project\].+\/node_modules\//g, '[project]/.../node_modules/')
@@ -123,10 +151,24 @@ describe('next.rs api', () => {
     await trace('setup next instance').traceAsyncFn(async (rootSpan) => {
       next = await createNext({
         skipStart: true,
+        dependencies: {
+          // Declared, but not imported by any route.
+          'left-pad': '1.3.0',
+        },
         files: {
           'pages/index.js': pagesIndexCode('hello world'),
           'lib/props.js': 'export default {}',
+          'components/styled.js':
+            'export default () => <div>hello<style jsx>{`div { color: red; }`}</style></div>',
           'pages/page-nodejs.js': 'export default () => <div>hello world</div>',
+          'next.config.js':
+            'module.exports = { experimental: { turbo: { reportNodeBuiltins: true } } }',
+          'pages/page-fs.js':
+            'import fs from "fs"\nimport "guarded-fs"\nexport default () => <div>{typeof fs}</div>',
+          'node_modules/guarded-fs/package.json':
+            '{ "name": "guarded-fs", "main": "index.js" }',
+          'node_modules/guarded-fs/index.js':
+            'if (typeof window === "undefined") { require("fs") }',
           'pages/page-edge.js':
             'export default () => <div>hello world</div>\nexport const config = { runtime: "experimental-edge" }',
           'pages/api/nodejs.js':
@@ -159,21 +201,7 @@ describe('next.rs api', () => {
   >
   beforeAll(async () => {
     console.log(next.testDir)
-    const nextConfig = await loadConfig(PHASE_DEVELOPMENT_SERVER, next.testDir)
-    const bindings = await loadBindings()
-    project = await bindings.turbo.createProject({
-      env: {},
-      jsConfig: {
-        compilerOptions: {},
-      },
-      nextConfig: nextConfig,
-      projectPath: next.testDir,
-      rootPath: process.env.NEXT_SKIP_ISOLATE
-        ? path.resolve(__dirname, '../../..')
-        : next.testDir,
-      watch: true,
-      serverAddr: `127.0.0.1:3000`,
-    })
+    project = await createTestProject(next, { watch: true })
     projectUpdateSubscription = project.updateInfoSubscribe()
   })
 
@@ -191,6 +219,7 @@ describe('next.rs api', () => {
       // '/app-edge',
       '/app-nodejs',
       '/page-edge',
+      '/page-fs',
       '/page-nodejs',
       '/route-edge',
       '/route-nodejs',
@@ -199,6 +228,74 @@ describe('next.rs api', () => {
     expect(normalizeDiagnostics(entrypoints.value.diagnostics)).toMatchSnapshot(
       'diagnostics'
     )
+    const { issues, issueCounts } = entrypoints.value
+    expect(issueCounts).toEqual({
+      errors: issues.filter((issue) =>
+        ['bug', 'fatal', 'error'].includes(issue.severity)
+      ).length,
+      warnings: issues.filter((issue) => issue.severity === 'warning').length,
+    })
+    entrypointsSubscribtion.return()
+  })
+
+  it('should detect the app directory', async () => {
+    const entrypointsSubscribtion = project.entrypointsSubscribe()
+    const entrypoints = await entrypointsSubscribtion.next()
+    expect(entrypoints.done).toBe(false)
+    expect(entrypoints.value.hasAppDir).toBe(true)
+    expect(entrypoints.value.appDir).toBe('app')
+    entrypointsSubscribtion.return()
+  })
+
+  it('should detect client components when requested', async () => {
+    const entrypointsSubscribtion = project.entrypointsSubscribe({
+      clientComponents: true,
+    })
+    const entrypoints = await entrypointsSubscribtion.next()
+    expect(entrypoints.done).toBe(false)
+    const { routes } = entrypoints.value
+    expect(routes.get('/app')).toMatchObject({
+      type: 'app-page',
+      hasClientComponents: true,
+    })
+    expect(routes.get('/app-nodejs')).toMatchObject({
+      type: 'app-page',
+      hasClientComponents: false,
+    })
+    expect(routes.get('/route-nodejs')).toMatchObject({
+      type: 'app-route',
+      hasClientComponents: false,
+    })
+    entrypointsSubscribtion.return()
+  })
+
+  it('should detect the runtime of routes when requested', async () => {
+    const entrypointsSubscribtion = project.entrypointsSubscribe({
+      runtime: true,
+    })
+    const entrypoints = await entrypointsSubscribtion.next()
+    expect(entrypoints.done).toBe(false)
+    const { routes } = entrypoints.value
+    expect(routes.get('/page-edge')).toMatchObject({
+      type: 'page',
+      runtime: 'edge',
+    })
+    expect(routes.get('/page-nodejs')).toMatchObject({
+      type: 'page',
+      runtime: 'nodejs',
+    })
+    expect(routes.get('/api/edge')).toMatchObject({
+      type: 'page-api',
+      runtime: 'edge',
+    })
+    expect(routes.get('/route-edge')).toMatchObject({
+      type: 'app-route',
+      runtime: 'edge',
+    })
+    expect(routes.get('/app-nodejs')).toMatchObject({
+      type: 'app-page',
+      runtime: 'nodejs',
+    })
     entrypointsSubscribtion.return()
   })
 
@@ -337,6 +434,177 @@ describe('next.rs api', () => {
     })
   }
 
+  it('should list unsupported modules', async () => {
+    const result = await project.unsupportedModules()
+    // The guarded `require("fs")` of the guarded-fs package isn't reported.
+    expect(result.result).toEqual([
+      {
+        module: 'fs',
+        importers: [expect.stringMatching(/\/pages$/)],
+      },
+    ])
+  })
+
+  it('should compile a single module with the client transforms', async () => {
+    const result = await project.transformModule('components/styled.js')
+    expect(result.issues).toEqual([])
+    expect(result.result.code).toContain('styled-jsx/style')
+    expect(result.result.code).toMatch(
+      /div\.jsx-\d+\s*\{\s*color:\s*red;?\s*\}/
+    )
+    expect(result.result.code).toMatch(/className:\s*"jsx-\d+"/)
+  })
+
+  it('should list only the dependencies no route imports', async () => {
+    const result = await project.unusedDependencies()
+    expect(result.result).toContainEqual({ name: 'left-pad', version: '1.3.0' })
+    const names = result.result.map((dependency) => dependency.name)
+    expect(names).not.toContain('react')
+    expect(names).not.toContain('react-dom')
+
+    const diagnostic = result.diagnostics.find(
+      (diagnostic) => diagnostic.name === 'unused-dependencies'
+    )
+    expect(diagnostic?.payload).toMatchObject({ 'left-pad': '1.3.0' })
+  })
+
+  it('should report issues of the errorOn categories as errors', async () => {
+    async function writePageFs(project: Project) {
+      const entrypointsSubscribtion = project.entrypointsSubscribe()
+      const entrypoints: TurbopackResult<Entrypoints> = (
+        await entrypointsSubscribtion.next()
+      ).value
+      entrypointsSubscribtion.return()
+      const route = entrypoints.routes.get('/page-fs')
+      if (route.type !== 'page') throw new Error('unexpected route type')
+      const result = await route.htmlEndpoint.writeToDisk()
+      return result.issues.filter((issue) =>
+        issue.filePath.includes('pages/page-fs.js')
+      )
+    }
+
+    const warnings = (await writePageFs(project)).filter(
+      (issue) => issue.severity === 'warning'
+    )
+    expect(warnings).not.toHaveLength(0)
+    const { category } = warnings[0]
+
+    const strictProject = await createTestProject(next, { errorOn: [category] })
+    const issues = await writePageFs(strictProject)
+    expect(issues).toContainEqual(
+      expect.objectContaining({ category, severity: 'error' })
+    )
+    expect(issues).not.toContainEqual(
+      expect.objectContaining({ category, severity: 'warning' })
+    )
+  })
+
+  it('should minify server chunks when requested', async () => {
+    async function writeEntry(project: Project) {
+      const entrypointsSubscribtion = project.entrypointsSubscribe()
+      const entrypoints: TurbopackResult<Entrypoints> = (
+        await entrypointsSubscribtion.next()
+      ).value
+      entrypointsSubscribtion.return()
+      const route = entrypoints.routes.get('/page-nodejs')
+      if (route.type !== 'page') throw new Error('unexpected route type')
+      const result = await route.htmlEndpoint.writeToDisk()
+      if (result.type !== 'nodejs') throw new Error('unexpected runtime')
+      return fs.readFile(
+        path.join(next.testDir, '.next', result.entryPath),
+        'utf8'
+      )
+    }
+
+    const minifiedProject = await createTestProject(next, { minify: true })
+    const minified = await writeEntry(minifiedProject)
+    // Writing with the shared project restores the readable output for the
+    // following tests.
+    const readable = await writeEntry(project)
+
+    expect(minified.length).toBeLessThan(readable.length)
+    expect(minified.split('\n').length).toBeLessThan(
+      readable.split('\n').length
+    )
+  })
+
+  it('should aggregate update info over the requested interval', async () => {
+    await expect(project.updateInfoSubscribe(10).next()).rejects.toThrow(
+      'update info interval must be between 50ms and 60000ms, got 10ms'
+    )
+
+    const intervalMs = 3000
+    const subscription = project.updateInfoSubscribe(intervalMs)
+    const { next: updateComplete } = await drainAndGetNext(subscription)
+    const oldContent = await next.readFile('lib/props.js')
+    try {
+      const start = Date.now()
+      await next.patchFile('lib/props.js', 'export default { some: "info" }')
+      const { value } = await updateComplete
+      // The update is only reported once no further update happened for the
+      // interval.
+      expect(Date.now() - start).toBeGreaterThanOrEqual(intervalMs)
+      expect(value.tasks).toBePositive()
+    } finally {
+      const { next: updateComplete2 } = await drainAndGetNext(
+        projectUpdateSubscription
+      )
+      await next.patchFile('lib/props.js', oldContent)
+      await updateComplete2
+      subscription.return()
+    }
+  })
+
+  it('should number the HMR updates of each identifier', async () => {
+    const entrypointsSubscribtion = project.entrypointsSubscribe()
+    const entrypoints: TurbopackResult<Entrypoints> = (
+      await entrypointsSubscribtion.next()
+    ).value
+    entrypointsSubscribtion.return()
+    const route = entrypoints.routes.get('/')
+    if (route.type !== 'page') throw new Error('unexpected route type')
+    await route.htmlEndpoint.writeToDisk()
+
+    const { identifiers } = (await project.hmrIdentifiersSubscribe().next())
+      .value
+    const subscriptions = identifiers.map((identifier) =>
+      project.hmrEvents(identifier)
+    )
+    const initial = await Promise.all(
+      subscriptions.map(async (subscription) => {
+        const { value } = await subscription.next()
+        expect(value.sequence).toBeNumber()
+        expect(value.timestamp).toBeNumber()
+        return value
+      })
+    )
+
+    const { next: updateComplete } = await drainAndGetNext(
+      projectUpdateSubscription
+    )
+    const oldContent = await next.readFile('pages/index.js')
+    try {
+      await next.patchFile('pages/index.js', pagesIndexCode('hello sequence'))
+      // The first identifier which receives an update.
+      const [i, update] = await Promise.race(
+        subscriptions.map(async (subscription, i) => {
+          const { value } = await subscription.next()
+          return [i, value] as const
+        })
+      )
+      expect(update.sequence).toBeGreaterThan(initial[i].sequence)
+      expect(update.timestamp).toBeGreaterThanOrEqual(initial[i].timestamp)
+    } finally {
+      subscriptions.forEach((subscription) => subscription.return())
+      await updateComplete
+      const { next: updateComplete2 } = await drainAndGetNext(
+        projectUpdateSubscription
+      )
+      await next.patchFile('pages/index.js', oldContent)
+      await updateComplete2
+    }
+  })
+
   const hmrCases: {
     name: string
     path: string
@@ -545,3 +813,26 @@ describe('next.rs api', () => {
       })
   }
 })
+
+describe('next.rs api without app directory', () => {
+  let next: NextInstance
+  beforeAll(async () => {
+    next = await createNext({
+      skipStart: true,
+      files: {
+        'pages/index.js': 'export default () => <div>hello world</div>',
+      },
+    })
+  })
+  afterAll(() => next.destroy())
+
+  it('should report that there is no app directory', async () => {
+    const project = await createTestProject(next)
+    const entrypointsSubscribtion = project.entrypointsSubscribe()
+    const entrypoints = await entrypointsSubscribtion.next()
+    expect(entrypoints.done).toBe(false)
+    expect(entrypoints.value.hasAppDir).toBe(false)
+    expect(entrypoints.value.appDir).toBeUndefined()
+    entrypointsSubscribtion.return()
+  })
+})